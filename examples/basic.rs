@@ -57,13 +57,17 @@ fn main() {
 
     for (i, p) in prices.into_iter().enumerate() {
         let spread = Decimal::try_from_scaled(1, 1).unwrap();
-        let exec_orders = exchange
+        let outcome = exchange
             .update_state(&Bba {
                 bid: p,
                 ask: p + spread.into(),
                 timestamp_exchange_ns: (i as i64).into(),
             })
-            .expect("Got REKT. Try again next time :D");
+            .expect("is a valid market update");
+        if outcome.liquidated {
+            println!("Got REKT. Try again next time :D");
+        }
+        let exec_orders = outcome.limit_order_updates;
         if !exec_orders.is_empty() {
             println!("executed orders: {:?}", exec_orders);
         }
@@ -1,11 +1,13 @@
 mod account;
 mod in_memory_accounting;
+mod recording_accounting;
 mod transaction;
 mod utils;
 
 #[cfg(test)]
 pub(crate) use account::TAccount;
 pub use in_memory_accounting::*;
+pub use recording_accounting::RecordingTransactionAccounting;
 pub use transaction::Transaction;
 
 use crate::prelude::*;
@@ -25,6 +27,18 @@ where
 
     /// Query a balance of an account denoted in the margin currency.
     fn margin_balance_of(&self, account: AccountId) -> Result<BaseOrQuote>;
+
+    /// Called with the timestamp of every market update, before any transaction it causes.
+    /// Only implementations which retain their history need to know when a transaction happened.
+    #[inline]
+    fn update_timestamp(&mut self, _ts_ns: TimestampNs) {}
+
+    /// Write every settled transaction as csv with the header `timestamp_ns,debit_account,credit_account,amount`.
+    /// The amount is debited to the first and credited to the second account.
+    /// Returns `Error::TransactionHistoryNotRetained` unless the implementation retains its history.
+    fn export_csv<W: std::io::Write>(&self, _writer: W) -> Result<()> {
+        Err(Error::TransactionHistoryNotRetained)
+    }
 }
 
 /// The identifier of an account in the accounting infrastructure.
@@ -0,0 +1,102 @@
+use super::{
+    AccountId, InMemoryTransactionAccounting, Mon, TREASURY_ACCOUNT, TransactionAccounting,
+    USER_WALLET_ACCOUNT,
+    transaction::{Transaction, account_from_int},
+};
+use crate::{
+    Result,
+    types::{Error, MarginCurrency, TimestampNs},
+};
+
+/// Does the accounting like `InMemoryTransactionAccounting`,
+/// but also retains every settled `Transaction`, stamped with the timestamp at which it happened,
+/// so the full history can be exported with `export_csv`.
+/// The history is kept in chronological order, transactions with the same timestamp keep the order they settled in.
+#[derive(Debug)]
+pub struct RecordingTransactionAccounting<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    inner: InMemoryTransactionAccounting<I, D, BaseOrQuote>,
+    /// The timestamp of the last market update, at which new transactions happen.
+    current_ts_ns: TimestampNs,
+    history: Vec<Transaction<I, D, BaseOrQuote>>,
+}
+
+impl<I, const D: u8, BaseOrQuote> RecordingTransactionAccounting<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    /// All settled transactions in chronological order, including the initial funding of the user wallet.
+    #[inline]
+    pub fn history(&self) -> &[Transaction<I, D, BaseOrQuote>] {
+        &self.history
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote> TransactionAccounting<I, D, BaseOrQuote>
+    for RecordingTransactionAccounting<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    fn new(user_starting_wallet_balance: BaseOrQuote) -> Self {
+        let mut history = Vec::new();
+        if user_starting_wallet_balance > BaseOrQuote::zero() {
+            history.push(Transaction::new(
+                USER_WALLET_ACCOUNT,
+                TREASURY_ACCOUNT,
+                user_starting_wallet_balance,
+            ));
+        }
+        Self {
+            inner: InMemoryTransactionAccounting::new(user_starting_wallet_balance),
+            current_ts_ns: TimestampNs::default(),
+            history,
+        }
+    }
+
+    fn create_margin_transfer(
+        &mut self,
+        transaction: Transaction<I, D, BaseOrQuote>,
+    ) -> Result<()> {
+        let transaction = transaction.with_timestamp(self.current_ts_ns);
+        self.inner.create_margin_transfer(transaction.clone())?;
+        // Market updates normally arrive in order, so this is usually an append.
+        let idx = self
+            .history
+            .partition_point(|t| t.timestamp_ns() <= transaction.timestamp_ns());
+        self.history.insert(idx, transaction);
+        Ok(())
+    }
+
+    #[inline]
+    fn margin_balance_of(&self, account: AccountId) -> Result<BaseOrQuote> {
+        self.inner.margin_balance_of(account)
+    }
+
+    #[inline]
+    fn update_timestamp(&mut self, ts_ns: TimestampNs) {
+        self.current_ts_ns = ts_ns;
+    }
+
+    fn export_csv<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let mut w = csv::Writer::from_writer(writer);
+        let to_err = |err: csv::Error| Error::TransactionHistoryExport(err.to_string());
+        w.write_record(["timestamp_ns", "debit_account", "credit_account", "amount"])
+            .map_err(to_err)?;
+        for transaction in self.history.iter() {
+            w.write_record([
+                transaction.timestamp_ns().to_string(),
+                account_from_int(transaction.debit_account_id()).to_string(),
+                account_from_int(transaction.credit_account_id()).to_string(),
+                transaction.amount().as_ref().to_string(),
+            ])
+            .map_err(to_err)?;
+        }
+        w.flush()
+            .map_err(|err| Error::TransactionHistoryExport(err.to_string()))
+    }
+}
@@ -4,6 +4,7 @@ use super::{
     AccountId, BROKER_MARGIN_ACCOUNT, EXCHANGE_FEE_ACCOUNT, MarginCurrency, Mon, QuoteCurrency,
     TREASURY_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
 };
+use crate::types::TimestampNs;
 
 /// A transaction involves two parties.
 #[derive(Clone, CopyGetters, Debug)]
@@ -12,12 +13,19 @@ where
     I: Mon<D>,
     BaseOrQuote: MarginCurrency<I, D>,
 {
-    #[getset(get_copy = "pub(crate)")]
+    /// The account whose balance increases, e.g. `USER_WALLET_ACCOUNT`.
+    #[getset(get_copy = "pub")]
     debit_account_id: AccountId,
-    #[getset(get_copy = "pub(crate)")]
+    /// The account whose balance decreases.
+    #[getset(get_copy = "pub")]
     credit_account_id: AccountId,
-    #[getset(get_copy = "pub(crate)")]
+    /// The positive amount moved between the accounts.
+    #[getset(get_copy = "pub")]
     amount: BaseOrQuote,
+    /// The timestamp of the exchange clock at which the transaction happened.
+    /// Only set by `TransactionAccounting` implementations which retain their history, otherwise zero.
+    #[getset(get_copy = "pub")]
+    timestamp_ns: TimestampNs,
     _quote: std::marker::PhantomData<QuoteCurrency<I, D>>,
 }
 
@@ -29,16 +37,17 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Transaction( debit_account_id: {}, credit_account_id: {}, amount: {} )",
+            "Transaction( debit_account_id: {}, credit_account_id: {}, amount: {}, timestamp_ns: {} )",
             account_from_int(self.debit_account_id),
             account_from_int(self.credit_account_id),
-            self.amount
+            self.amount,
+            self.timestamp_ns
         )
     }
 }
 
 /// For making accounts more readable in `Debug` formatting.
-fn account_from_int(int: usize) -> &'static str {
+pub(crate) fn account_from_int(int: usize) -> &'static str {
     match int {
         USER_WALLET_ACCOUNT => "USER_WALLET_ACCOUNT",
         USER_ORDER_MARGIN_ACCOUNT => "USER_ORDER_MARGIN_ACCOUNT",
@@ -72,9 +81,17 @@ where
             debit_account_id,
             credit_account_id,
             amount,
+            timestamp_ns: TimestampNs::default(),
             _quote: std::marker::PhantomData,
         }
     }
+
+    /// Stamp the transaction with the time at which it happened.
+    #[inline]
+    pub(crate) fn with_timestamp(mut self, timestamp_ns: TimestampNs) -> Self {
+        self.timestamp_ns = timestamp_ns;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -87,7 +104,11 @@ mod test {
         let t = Transaction::new(0, 1, BaseCurrency::<i64, 1>::new(5, 0));
         assert_eq!(
             &t.to_string(),
-            "Transaction( debit_account_id: USER_WALLET_ACCOUNT, credit_account_id: USER_ORDER_MARGIN_ACCOUNT, amount: 5.0 Base )"
+            "Transaction( debit_account_id: USER_WALLET_ACCOUNT, credit_account_id: USER_ORDER_MARGIN_ACCOUNT, amount: 5.0 Base, timestamp_ns: 0 )"
+        );
+        assert_eq!(
+            &t.with_timestamp(42.into()).to_string(),
+            "Transaction( debit_account_id: USER_WALLET_ACCOUNT, credit_account_id: USER_ORDER_MARGIN_ACCOUNT, amount: 5.0 Base, timestamp_ns: 42 )"
         );
     }
 }
@@ -109,6 +109,18 @@ where
         self.arena.iter().find(|order| order.id() == order_id)
     }
 
+    /// Get a `LimitOrder` by the given `UserOrderId` if any.
+    /// Optimized to be fast for small number of active limit orders.
+    #[inline]
+    pub fn get_by_user_order_id(
+        &self,
+        user_order_id: UserOrderIdT,
+    ) -> Option<&LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
+        self.arena
+            .iter()
+            .find(|order| order.user_order_id() == user_order_id)
+    }
+
     /// Get a `LimitOrder` by the given `OrderId` if any.
     /// Optimized to be fast for small number of active limit orders.
     #[inline]
@@ -1,9 +1,13 @@
-use getset::{CopyGetters, Getters};
+use const_decimal::Decimal;
+use getset::{CopyGetters, Getters, Setters};
 
 use crate::{
     contract_specification::ContractSpecification,
     prelude::{ConfigError, MarginCurrency, Mon},
-    types::OrderRateLimits,
+    types::{
+        CrossedMarketPolicy, FeeMode, FeeModel, LimitFillPrice, MarketFillPrice, OrderRateLimits,
+        SelfCrossPolicy, TimestampNs, ValidationMode,
+    },
 };
 
 /// Define the Exchange configuration.
@@ -12,7 +16,7 @@ use crate::{
 /// - `I`: The numeric data type of currencies.
 /// - `D`: The constant decimal precision of the currencies.
 /// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
-#[derive(Debug, Clone, Getters, CopyGetters)]
+#[derive(Debug, Clone, Getters, CopyGetters, Setters)]
 pub struct Config<I, const D: u8, BaseOrQuote>
 where
     I: Mon<D>,
@@ -38,6 +42,88 @@ where
     /// The submission rate limits for orders.
     #[getset(get = "pub")]
     order_rate_limits: OrderRateLimits,
+
+    /// Whether fees are deferred until the position is reduced or paid upfront when filled.
+    /// Defaults to `FeeMode::Deferred`.
+    #[getset(get_copy = "pub", set = "pub")]
+    fee_mode: FeeMode,
+
+    /// How the fees of a fill are computed.
+    /// The margin checks of the risk engine always estimate fees with the percentage fees of the `contract_spec`.
+    /// Defaults to `FeeModel::Percentage`.
+    #[getset(get_copy = "pub")]
+    fee_model: FeeModel<BaseOrQuote>,
+
+    /// The price at which limit orders are filled when a market update trades through their limit price.
+    /// Defaults to `LimitFillPrice::LimitPrice`.
+    #[getset(get_copy = "pub", set = "pub")]
+    limit_fill_price: LimitFillPrice,
+
+    /// The price at which market orders are filled.
+    /// Defaults to `MarketFillPrice::BidAsk`.
+    #[getset(get_copy = "pub", set = "pub")]
+    market_fill_price: MarketFillPrice,
+
+    /// How a new limit order is handled which crosses a resting limit order of the opposite side.
+    /// Defaults to `SelfCrossPolicy::Reject`.
+    #[getset(get_copy = "pub", set = "pub")]
+    self_cross_policy: SelfCrossPolicy,
+
+    /// How a market update with a bid at or above its ask is handled.
+    /// Defaults to `CrossedMarketPolicy::Reject`.
+    #[getset(get_copy = "pub", set = "pub")]
+    crossed_market_policy: CrossedMarketPolicy,
+
+    /// Whether orders violating the price or quantity filters are rounded to valid values or rejected.
+    /// Defaults to `ValidationMode::Strict`.
+    #[getset(get_copy = "pub", set = "pub")]
+    validation_mode: ValidationMode,
+
+    /// The maximum fraction of a resting limit orders total quantity which can be filled within a single market update,
+    /// so large orders are filled over several market updates.
+    /// Disabled by default.
+    #[getset(get_copy = "pub")]
+    max_fill_ratio_per_tick: Option<Decimal<I, D>>,
+
+    /// The maximum move of the mid price or the last trade price within a single market update, in percent.
+    /// Market updates exceeding it are rejected, which protects against bad prints in the data,
+    /// so a bad `Trade` neither fills resting orders nor moves a mark price derived from the last trade.
+    /// A move is accepted if it is within the cap of either the last accepted or the last rejected update,
+    /// so a genuine gap to a new price level is accepted with its second market update.
+    /// Disabled by default.
+    #[getset(get_copy = "pub")]
+    max_price_move_pct: Option<Decimal<I, D>>,
+
+    /// The maximum number of market and limit order submissions within a single market update.
+    /// Submissions exceeding it are rejected, which catches runaway strategies.
+    /// Liquidations are not submitted by the user and thus not counted.
+    /// Disabled by default.
+    #[getset(get_copy = "pub")]
+    max_trades_per_tick: Option<u32>,
+
+    /// The minimum time after a position is closed before a new position may be opened.
+    /// Orders which only reduce the current position are exempt.
+    /// Disabled by default.
+    #[getset(get_copy = "pub")]
+    cooldown: Option<TimestampNs>,
+
+    /// The time it takes for a cancel request to reach the exchange.
+    /// Until it elapsed, the order remains active and may still be filled.
+    /// Disabled by default, which cancels orders immediately.
+    #[getset(get_copy = "pub")]
+    cancel_latency: Option<TimestampNs>,
+
+    /// The balance the `TREASURY_ACCOUNT` is seeded with, funded by the `BROKER_MARGIN_ACCOUNT`.
+    /// Defaults to zero.
+    #[getset(get_copy = "pub")]
+    initial_treasury_balance: BaseOrQuote,
+
+    /// The minimum equity of the account, being its balances plus the unrealized profit and loss net of outstanding fees.
+    /// Once the equity falls below it at the end of a market update, the account is blown up:
+    /// all orders are cancelled, the position is closed and no further orders are accepted.
+    /// Disabled by default.
+    #[getset(get_copy = "pub")]
+    min_account_balance: Option<BaseOrQuote>,
 }
 
 impl<I, const D: u8, BaseOrQuote> Config<I, D, BaseOrQuote>
@@ -75,6 +161,124 @@ where
             max_num_open_orders,
             contract_spec: contract_specification,
             order_rate_limits,
+            fee_mode: FeeMode::default(),
+            fee_model: FeeModel::default(),
+            limit_fill_price: LimitFillPrice::default(),
+            market_fill_price: MarketFillPrice::default(),
+            self_cross_policy: SelfCrossPolicy::default(),
+            crossed_market_policy: CrossedMarketPolicy::default(),
+            validation_mode: ValidationMode::default(),
+            max_fill_ratio_per_tick: None,
+            max_price_move_pct: None,
+            max_trades_per_tick: None,
+            cooldown: None,
+            cancel_latency: None,
+            initial_treasury_balance: BaseOrQuote::zero(),
+            min_account_balance: None,
         })
     }
+
+    /// Set the maximum fraction of a limit orders total quantity which can be filled within a single market update.
+    /// It must be in the range (0, 1] and `None` disables the cap.
+    pub fn set_max_fill_ratio_per_tick(
+        &mut self,
+        max_fill_ratio_per_tick: Option<Decimal<I, D>>,
+    ) -> Result<(), ConfigError> {
+        if max_fill_ratio_per_tick
+            .is_some_and(|ratio| ratio <= Decimal::ZERO || ratio > Decimal::ONE)
+        {
+            return Err(ConfigError::InvalidMaxFillRatioPerTick);
+        }
+        self.max_fill_ratio_per_tick = max_fill_ratio_per_tick;
+        Ok(())
+    }
+
+    /// Set the maximum move of the mid price or the last trade price within a single market update, in percent.
+    /// `None` disables the check.
+    pub fn set_max_price_move_pct(
+        &mut self,
+        max_price_move_pct: Option<Decimal<I, D>>,
+    ) -> Result<(), ConfigError> {
+        if let Some(pct) = max_price_move_pct {
+            if pct <= Decimal::ZERO {
+                return Err(ConfigError::InvalidMaxPriceMove);
+            }
+        }
+        self.max_price_move_pct = max_price_move_pct;
+        Ok(())
+    }
+
+    /// Set the maximum number of market and limit order submissions within a single market update.
+    /// `None` disables the check.
+    pub fn set_max_trades_per_tick(
+        &mut self,
+        max_trades_per_tick: Option<u32>,
+    ) -> Result<(), ConfigError> {
+        if max_trades_per_tick == Some(0) {
+            return Err(ConfigError::InvalidMaxTradesPerTick);
+        }
+        self.max_trades_per_tick = max_trades_per_tick;
+        Ok(())
+    }
+
+    /// Set the minimum time after a position is closed before a new position may be opened.
+    /// `None` disables the check.
+    pub fn set_cooldown(&mut self, cooldown: Option<TimestampNs>) -> Result<(), ConfigError> {
+        if cooldown.is_some_and(|cooldown| cooldown <= TimestampNs::from(0)) {
+            return Err(ConfigError::InvalidCooldown);
+        }
+        self.cooldown = cooldown;
+        Ok(())
+    }
+
+    /// Set the time it takes for a cancel request to reach the exchange.
+    /// `None` disables the latency.
+    pub fn set_cancel_latency(
+        &mut self,
+        cancel_latency: Option<TimestampNs>,
+    ) -> Result<(), ConfigError> {
+        if cancel_latency.is_some_and(|latency| latency <= TimestampNs::from(0)) {
+            return Err(ConfigError::InvalidCancelLatency);
+        }
+        self.cancel_latency = cancel_latency;
+        Ok(())
+    }
+
+    /// Set the balance the `TREASURY_ACCOUNT` is seeded with. Must not be negative.
+    pub fn set_initial_treasury_balance(
+        &mut self,
+        initial_treasury_balance: BaseOrQuote,
+    ) -> Result<(), ConfigError> {
+        if initial_treasury_balance < BaseOrQuote::zero() {
+            return Err(ConfigError::InvalidInitialTreasuryBalance);
+        }
+        self.initial_treasury_balance = initial_treasury_balance;
+        Ok(())
+    }
+
+    /// Set the minimum equity below which the account is blown up.
+    /// It must be positive and smaller than the starting wallet balance. `None` disables the check.
+    pub fn set_min_account_balance(
+        &mut self,
+        min_account_balance: Option<BaseOrQuote>,
+    ) -> Result<(), ConfigError> {
+        if min_account_balance.is_some_and(|min_balance| {
+            min_balance <= BaseOrQuote::zero() || min_balance >= self.starting_wallet_balance
+        }) {
+            return Err(ConfigError::InvalidMinAccountBalance);
+        }
+        self.min_account_balance = min_account_balance;
+        Ok(())
+    }
+
+    /// Set how the fees of a fill are computed. A per contract fee must not be negative.
+    pub fn set_fee_model(&mut self, fee_model: FeeModel<BaseOrQuote>) -> Result<(), ConfigError> {
+        if let FeeModel::PerContract(per_contract) = fee_model {
+            if per_contract < BaseOrQuote::zero() {
+                return Err(ConfigError::InvalidPerContractFee);
+            }
+        }
+        self.fee_model = fee_model;
+        Ok(())
+    }
 }
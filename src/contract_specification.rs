@@ -4,8 +4,12 @@ use num_traits::{One, Zero};
 
 use crate::{
     leverage,
-    prelude::{ConfigError, Currency, Maker, Mon, PriceFilter, QuantityFilter, Taker},
-    types::{Fee, Leverage},
+    market_state::MarketState,
+    prelude::{
+        ConfigError, Currency, Maker, Mon, PriceFilter, QuantityFilter, QuoteCurrency, Taker,
+    },
+    types::{Fee, Leverage, PriceSources, Side, TimestampNs},
+    utils::max,
 };
 
 /// Specifies the details of the futures contract
@@ -23,17 +27,49 @@ where
     #[getset(get = "pub", set = "pub")]
     ticker: String,
 
-    /// The initial deposit required to open a new futures position.
+    /// The initial deposit required to open a new long futures position.
     /// Expressed as basis points.
     #[getset(get_copy = "pub")]
     init_margin_req: Decimal<I, D>,
 
     /// The minimum amount that must be maintained in the traders account to
-    /// keep existing positions open.
+    /// keep existing long positions open.
     /// Expressed as basis points.
     #[getset(get_copy = "pub")]
     maintenance_margin: Decimal<I, D>,
 
+    /// The initial deposit required to open a new short futures position.
+    /// Same as `init_margin_req` unless set with `set_short_margin`.
+    #[getset(get_copy = "pub")]
+    init_margin_req_short: Decimal<I, D>,
+
+    /// The maintenance margin of short positions.
+    /// Same as `maintenance_margin` unless set with `set_short_margin`.
+    #[getset(get_copy = "pub")]
+    maintenance_margin_short: Decimal<I, D>,
+
+    /// The fraction by which the maintenance margin is increased when checking for liquidation.
+    /// A position is liquidated once its equity falls below `maintenance_margin * (1 + buffer)`.
+    /// Defaults to zero.
+    #[getset(get_copy = "pub")]
+    maintenance_margin_buffer: Decimal<I, D>,
+
+    /// If set, the realized profit and loss is rounded down to a multiple of this increment before it is settled,
+    /// so the rounding residual stays with the treasury.
+    /// Defaults to `None`, which settles the exact profit and loss.
+    #[getset(get_copy = "pub")]
+    pnl_settlement_increment: Option<BaseOrQuote::PairedCurrency>,
+
+    /// The timestamp at which a dated futures contract expires.
+    /// Defaults to `None`, which is a perpetual contract.
+    #[getset(get_copy = "pub")]
+    expiry: Option<TimestampNs>,
+
+    /// The reference price at which the position is settled on `expiry`.
+    /// Only set together with the `expiry`.
+    #[getset(get_copy = "pub")]
+    settlement_price: Option<QuoteCurrency<I, D>>,
+
     /// The method for computing `mark-to-market`.
     #[getset(get_copy = "pub", set = "pub")]
     mark_method: MarkMethod,
@@ -83,12 +119,22 @@ where
             return Err(ConfigError::InvalidMaintenanceMarginFraction);
         }
 
+        // A leverage whose initial margin requirement truncates to zero at the precision `D` would not lock any margin.
         let init_margin_req = leverage.init_margin_req();
+        if init_margin_req <= Decimal::zero() {
+            return Err(ConfigError::InvalidLeverage);
+        }
 
         Ok(Self {
             ticker: String::new(),
             init_margin_req,
             maintenance_margin: init_margin_req * maintenance_margin,
+            init_margin_req_short: init_margin_req,
+            maintenance_margin_short: init_margin_req * maintenance_margin,
+            maintenance_margin_buffer: Decimal::zero(),
+            pnl_settlement_increment: None,
+            expiry: None,
+            settlement_price: None,
             mark_method: MarkMethod::default(),
             price_filter,
             quantity_filter,
@@ -96,6 +142,103 @@ where
             fee_taker,
         })
     }
+
+    /// Start building a `ContractSpecification` for `ticker`, with all other parameters defaulted.
+    pub fn builder(ticker: impl Into<String>) -> ContractSpecificationBuilder<I, D, BaseOrQuote> {
+        ContractSpecificationBuilder::new(ticker.into())
+    }
+
+    /// Set margin requirements for short positions which differ from the ones of long positions.
+    ///
+    /// # Arguments:
+    /// `leverage`: The leverage of short positions.
+    /// `maintenance_margin_fraction`: The fraction (in range [0..1]) that the maintenance margin will be relative to the computed `initial_margin`.
+    pub fn set_short_margin(
+        &mut self,
+        leverage: Leverage<I, D>,
+        maintenance_margin: Decimal<I, D>,
+    ) -> Result<(), ConfigError> {
+        if maintenance_margin > Decimal::one() || maintenance_margin <= Decimal::zero() {
+            return Err(ConfigError::InvalidMaintenanceMarginFraction);
+        }
+        if leverage.init_margin_req() <= Decimal::zero() {
+            return Err(ConfigError::InvalidLeverage);
+        }
+        self.init_margin_req_short = leverage.init_margin_req();
+        self.maintenance_margin_short = self.init_margin_req_short * maintenance_margin;
+        Ok(())
+    }
+
+    /// Set the fraction by which the maintenance margin is increased when checking for liquidation.
+    /// Must not be negative.
+    pub fn set_maintenance_margin_buffer(
+        &mut self,
+        maintenance_margin_buffer: Decimal<I, D>,
+    ) -> Result<(), ConfigError> {
+        if maintenance_margin_buffer < Decimal::zero() {
+            return Err(ConfigError::InvalidMaintenanceMarginBuffer);
+        }
+        self.maintenance_margin_buffer = maintenance_margin_buffer;
+        Ok(())
+    }
+
+    /// Set the increment to which the realized profit and loss is rounded down before it is settled.
+    /// Must be greater than zero if provided.
+    pub fn set_pnl_settlement_increment(
+        &mut self,
+        pnl_settlement_increment: Option<BaseOrQuote::PairedCurrency>,
+    ) -> Result<(), ConfigError> {
+        if pnl_settlement_increment
+            .is_some_and(|increment| increment <= BaseOrQuote::PairedCurrency::zero())
+        {
+            return Err(ConfigError::InvalidPnlSettlementIncrement);
+        }
+        self.pnl_settlement_increment = pnl_settlement_increment;
+        Ok(())
+    }
+
+    /// Turn the contract into a dated futures contract which expires at `expiry`,
+    /// where the position is settled at the `settlement_price` and no further trading is accepted.
+    /// The `settlement_price` must be greater than zero.
+    pub fn set_expiry(
+        &mut self,
+        expiry: TimestampNs,
+        settlement_price: QuoteCurrency<I, D>,
+    ) -> Result<(), ConfigError> {
+        if settlement_price <= QuoteCurrency::zero() {
+            return Err(ConfigError::InvalidSettlementPrice);
+        }
+        self.expiry = Some(expiry);
+        self.settlement_price = Some(settlement_price);
+        Ok(())
+    }
+
+    /// The initial margin requirement of a position in the direction of `side`,
+    /// where `Side::Buy` refers to long positions and `Side::Sell` to short positions.
+    #[inline]
+    pub fn init_margin_req_for(&self, side: Side) -> Decimal<I, D> {
+        match side {
+            Side::Buy => self.init_margin_req,
+            Side::Sell => self.init_margin_req_short,
+        }
+    }
+
+    /// The maintenance margin of a position in the direction of `side`,
+    /// where `Side::Buy` refers to long positions and `Side::Sell` to short positions.
+    #[inline]
+    pub fn maintenance_margin_for(&self, side: Side) -> Decimal<I, D> {
+        match side {
+            Side::Buy => self.maintenance_margin,
+            Side::Sell => self.maintenance_margin_short,
+        }
+    }
+
+    /// The initial margin requirement used for reserving order margin,
+    /// which is the stricter one of the long and short requirements.
+    #[inline]
+    pub fn order_init_margin_req(&self) -> Decimal<I, D> {
+        max(self.init_margin_req, self.init_margin_req_short)
+    }
 }
 
 impl<I, const D: u8, BaseOrQuote> Default for ContractSpecification<I, D, BaseOrQuote>
@@ -116,9 +259,150 @@ where
     }
 }
 
-// TODO: actually switch between the methods.
+/// Builds a validated `ContractSpecification`, see `ContractSpecification::builder`.
+/// Unless overridden, it uses the same parameters as `ContractSpecification::default`.
+#[derive(Debug, Clone)]
+pub struct ContractSpecificationBuilder<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    ticker: String,
+    leverage: Leverage<I, D>,
+    maintenance_margin: Decimal<I, D>,
+    short_margin: Option<(Leverage<I, D>, Decimal<I, D>)>,
+    maintenance_margin_buffer: Decimal<I, D>,
+    pnl_settlement_increment: Option<BaseOrQuote::PairedCurrency>,
+    expiry: Option<(TimestampNs, QuoteCurrency<I, D>)>,
+    mark_method: MarkMethod,
+    price_filter: PriceFilter<I, D>,
+    quantity_filter: QuantityFilter<I, D, BaseOrQuote>,
+    fee_maker: Fee<I, D, Maker>,
+    fee_taker: Fee<I, D, Taker>,
+}
+
+impl<I, const D: u8, BaseOrQuote> ContractSpecificationBuilder<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    fn new(ticker: String) -> Self {
+        let default = ContractSpecification::<I, D, BaseOrQuote>::default();
+        Self {
+            ticker,
+            leverage: leverage!(1),
+            maintenance_margin: Decimal::one() / Decimal::TWO,
+            short_margin: None,
+            maintenance_margin_buffer: default.maintenance_margin_buffer,
+            pnl_settlement_increment: default.pnl_settlement_increment,
+            expiry: None,
+            mark_method: default.mark_method,
+            price_filter: default.price_filter,
+            quantity_filter: default.quantity_filter,
+            fee_maker: default.fee_maker,
+            fee_taker: default.fee_taker,
+        }
+    }
+
+    /// Set the `leverage`, which dictates the margin requirements of a position.
+    pub fn leverage(mut self, leverage: Leverage<I, D>) -> Self {
+        self.leverage = leverage;
+        self
+    }
+
+    /// Set the fraction (in range [0..1]) that the maintenance margin will be relative to the initial margin.
+    pub fn maintenance_margin(mut self, maintenance_margin: Decimal<I, D>) -> Self {
+        self.maintenance_margin = maintenance_margin;
+        self
+    }
+
+    /// Set margin requirements for short positions which differ from the ones of long positions.
+    pub fn short_margin(
+        mut self,
+        leverage: Leverage<I, D>,
+        maintenance_margin: Decimal<I, D>,
+    ) -> Self {
+        self.short_margin = Some((leverage, maintenance_margin));
+        self
+    }
+
+    /// Set the fraction by which the maintenance margin is increased when checking for liquidation.
+    pub fn maintenance_margin_buffer(mut self, maintenance_margin_buffer: Decimal<I, D>) -> Self {
+        self.maintenance_margin_buffer = maintenance_margin_buffer;
+        self
+    }
+
+    /// Set the increment to which the realized profit and loss is rounded down before it is settled.
+    pub fn pnl_settlement_increment(
+        mut self,
+        pnl_settlement_increment: BaseOrQuote::PairedCurrency,
+    ) -> Self {
+        self.pnl_settlement_increment = Some(pnl_settlement_increment);
+        self
+    }
+
+    /// Set the `expiry` of a dated futures contract and the `settlement_price` of the position on expiry.
+    pub fn expiry(mut self, expiry: TimestampNs, settlement_price: QuoteCurrency<I, D>) -> Self {
+        self.expiry = Some((expiry, settlement_price));
+        self
+    }
+
+    /// Set the method for computing `mark-to-market`.
+    pub fn mark_method(mut self, mark_method: MarkMethod) -> Self {
+        self.mark_method = mark_method;
+        self
+    }
+
+    /// Set the rules for prices in the market.
+    pub fn price_filter(mut self, price_filter: PriceFilter<I, D>) -> Self {
+        self.price_filter = price_filter;
+        self
+    }
+
+    /// Set the rules for quantities in the market.
+    pub fn quantity_filter(mut self, quantity_filter: QuantityFilter<I, D, BaseOrQuote>) -> Self {
+        self.quantity_filter = quantity_filter;
+        self
+    }
+
+    /// Set the fee a maker pays.
+    pub fn fee_maker(mut self, fee_maker: Fee<I, D, Maker>) -> Self {
+        self.fee_maker = fee_maker;
+        self
+    }
+
+    /// Set the fee a taker pays.
+    pub fn fee_taker(mut self, fee_taker: Fee<I, D, Taker>) -> Self {
+        self.fee_taker = fee_taker;
+        self
+    }
+
+    /// Validate the parameters and create the `ContractSpecification`.
+    pub fn build(self) -> Result<ContractSpecification<I, D, BaseOrQuote>, ConfigError> {
+        let mut spec = ContractSpecification::new(
+            self.leverage,
+            self.maintenance_margin,
+            self.price_filter,
+            self.quantity_filter,
+            self.fee_maker,
+            self.fee_taker,
+        )?;
+        if let Some((leverage, maintenance_margin)) = self.short_margin {
+            spec.set_short_margin(leverage, maintenance_margin)?;
+        }
+        spec.set_maintenance_margin_buffer(self.maintenance_margin_buffer)?;
+        spec.set_pnl_settlement_increment(self.pnl_settlement_increment)?;
+        if let Some((expiry, settlement_price)) = self.expiry {
+            spec.set_expiry(expiry, settlement_price)?;
+        }
+        spec.ticker = self.ticker;
+        spec.mark_method = self.mark_method;
+        Ok(spec)
+    }
+}
+
 /// Which price to use in `mark-to-market` calculations
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarkMethod {
     /// Take the last mid price of the market.
     MidPrice,
@@ -129,6 +413,14 @@ pub enum MarkMethod {
     /// is illiquid, or the Mark Price swings unnecessarily relative to its Index Price.
     /// The system is able to achieve this by setting the Mark Price of the contract to the `FairPrice` instead of the `LastPrice`.
     FairPrice,
+    /// Use the first of the `sources` which is neither missing nor stale, see `MarketState::fallback_price`.
+    /// If none of them was ever observed, the position is marked like with `MarkMethod::BidAsk`.
+    Fallback {
+        /// The price sources in order of priority.
+        sources: PriceSources,
+        /// A price last updated longer ago than this is stale. `None` only skips missing prices.
+        max_age: Option<TimestampNs>,
+    },
 }
 
 impl Default for MarkMethod {
@@ -136,3 +428,141 @@ impl Default for MarkMethod {
         Self::BidAsk
     }
 }
+
+impl MarkMethod {
+    /// The price at which a position on `side` is marked to market.
+    /// A long position is on the `Buy` side and a short position on the `Sell` side.
+    /// The `mark_price_override` of the `market_state` takes precedence, if set.
+    pub fn mark_price<I, const D: u8>(
+        &self,
+        market_state: &MarketState<I, D>,
+        side: Side,
+    ) -> QuoteCurrency<I, D>
+    where
+        I: Mon<D>,
+    {
+        if let Some(mark_price) = market_state.mark_price_override() {
+            return mark_price;
+        }
+        match self {
+            // There is no index price available, so the mid price is the best estimate of the fair price.
+            MarkMethod::MidPrice | MarkMethod::FairPrice => market_state.mid_price(),
+            MarkMethod::BidAsk => match side {
+                Side::Buy => market_state.bid(),
+                Side::Sell => market_state.ask(),
+            },
+            MarkMethod::Fallback { sources, max_age } => market_state
+                .fallback_price(sources.as_slice(), side, *max_age)
+                .unwrap_or_else(|| MarkMethod::BidAsk.mark_price(market_state, side)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::BaseCurrency;
+
+    #[test]
+    fn contract_specification_init_margin_req_truncated_to_zero() {
+        // At a precision of 2 decimals, the initial margin requirement of 1 / 255 truncates to zero.
+        let new_spec = |leverage| {
+            ContractSpecification::<i64, 2, BaseCurrency<i64, 2>>::new(
+                leverage,
+                Decimal::one() / Decimal::TWO,
+                PriceFilter::default(),
+                QuantityFilter::default(),
+                Fee::from(Decimal::zero()),
+                Fee::from(Decimal::zero()),
+            )
+        };
+        assert_eq!(
+            new_spec(leverage!(255)).unwrap_err(),
+            ConfigError::InvalidLeverage
+        );
+        let mut spec = new_spec(leverage!(100)).unwrap();
+        assert_eq!(
+            spec.set_short_margin(leverage!(255), Decimal::one() / Decimal::TWO),
+            Err(ConfigError::InvalidLeverage)
+        );
+        assert_eq!(spec.init_margin_req_short(), spec.init_margin_req());
+    }
+
+    #[test]
+    fn contract_specification_builder_defaults() {
+        let spec = ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("BTCUSDT")
+            .build()
+            .unwrap();
+        let default = ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::default();
+        assert_eq!(spec.ticker(), "BTCUSDT");
+        assert_eq!(spec.init_margin_req(), default.init_margin_req());
+        assert_eq!(spec.maintenance_margin(), default.maintenance_margin());
+        assert_eq!(
+            spec.init_margin_req_short(),
+            default.init_margin_req_short()
+        );
+        assert_eq!(spec.mark_method(), MarkMethod::BidAsk);
+        assert_eq!(
+            spec.price_filter().tick_size(),
+            default.price_filter().tick_size()
+        );
+        assert_eq!(
+            spec.quantity_filter().tick_size(),
+            default.quantity_filter().tick_size()
+        );
+        assert_eq!(spec.fee_maker().as_ref(), default.fee_maker().as_ref());
+        assert_eq!(spec.fee_taker().as_ref(), default.fee_taker().as_ref());
+    }
+
+    #[test]
+    fn contract_specification_builder_override() {
+        let spec = ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("ETHUSDT")
+            .leverage(leverage!(2))
+            .build()
+            .unwrap();
+        assert_eq!(spec.ticker(), "ETHUSDT");
+        assert_eq!(
+            spec.init_margin_req(),
+            Decimal::try_from_scaled(5, 1).unwrap()
+        );
+        assert_eq!(
+            spec.maintenance_margin(),
+            Decimal::try_from_scaled(25, 2).unwrap()
+        );
+        assert_eq!(spec.init_margin_req_short(), spec.init_margin_req());
+        assert_eq!(spec.mark_method(), MarkMethod::BidAsk);
+
+        let spec = ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("ETHUSDT")
+            .short_margin(leverage!(1), Decimal::try_from_scaled(5, 1).unwrap())
+            .mark_method(MarkMethod::MidPrice)
+            .build()
+            .unwrap();
+        assert_eq!(spec.init_margin_req_short(), Decimal::one());
+        assert_eq!(spec.mark_method(), MarkMethod::MidPrice);
+    }
+
+    #[test]
+    fn contract_specification_builder_invalid() {
+        assert_eq!(
+            ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("BTCUSDT")
+                .maintenance_margin(Decimal::zero())
+                .build()
+                .unwrap_err(),
+            ConfigError::InvalidMaintenanceMarginFraction
+        );
+        assert_eq!(
+            ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("BTCUSDT")
+                .pnl_settlement_increment(QuoteCurrency::zero())
+                .build()
+                .unwrap_err(),
+            ConfigError::InvalidPnlSettlementIncrement
+        );
+        assert_eq!(
+            ContractSpecification::<i64, 5, BaseCurrency<i64, 5>>::builder("BTCUSDT")
+                .expiry(10.into(), QuoteCurrency::zero())
+                .build()
+                .unwrap_err(),
+            ConfigError::InvalidSettlementPrice
+        );
+    }
+}
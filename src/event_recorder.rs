@@ -0,0 +1,202 @@
+use crate::{
+    accounting::TransactionAccounting,
+    exchange::{CancelBy, Exchange},
+    prelude::{Bba, Candle, Currency, Mon, SmartCandle, Trade},
+    risk_engine::RiskEngine,
+    types::{
+        LimitOrder, MarginCurrency, MarketOrder, NewOrder, OrderId, QuoteCurrency, Result,
+        UserOrderId,
+    },
+};
+
+/// An input to the `Exchange` which can be recorded and replayed.
+/// Events can be serialized, so a recorded session can be stored alongside a bug report.
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Event<I, const D: u8, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// A `Bba` market update, passed to `Exchange::update_state`.
+    Bba(Bba<I, D>),
+    /// A `Trade` market update, passed to `Exchange::update_state`.
+    Trade(Trade<I, D, BaseOrQuote>),
+    /// A `Candle` market update, passed to `Exchange::update_state`.
+    Candle(Candle<I, D>),
+    /// A `SmartCandle` market update, passed to `Exchange::update_state`.
+    SmartCandle(SmartCandle<I, D, BaseOrQuote>),
+    /// See `Exchange::submit_market_order`.
+    SubmitMarketOrder(MarketOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>),
+    /// See `Exchange::submit_limit_order`.
+    SubmitLimitOrder(LimitOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>),
+    /// See `Exchange::cancel_limit_order`.
+    CancelLimitOrder(CancelBy<UserOrderIdT>),
+    /// See `Exchange::amend_limit_order`.
+    AmendLimitOrder {
+        /// The id of the active order which is replaced.
+        existing_order_id: OrderId,
+        /// The order which replaces it.
+        new_order: LimitOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
+    },
+    /// See `Exchange::amend_order`.
+    AmendOrder {
+        /// The id of the active order which is amended.
+        existing_order_id: OrderId,
+        /// The new limit price of the order.
+        new_price: QuoteCurrency<I, D>,
+        /// The new quantity of the order.
+        new_quantity: BaseOrQuote,
+    },
+    /// See `Exchange::add_isolated_margin`.
+    AddIsolatedMargin(BaseOrQuote::PairedCurrency),
+    /// See `Exchange::remove_isolated_margin`.
+    RemoveIsolatedMargin(BaseOrQuote::PairedCurrency),
+    /// See `Exchange::set_mark_price_override`.
+    SetMarkPriceOverride(Option<QuoteCurrency<I, D>>),
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> Event<I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// Drive the `exchange` with this event, discarding the output apart from errors.
+    pub fn apply<TransactionAccountingT, RiskEngineT>(
+        &self,
+        exchange: &mut Exchange<
+            I,
+            D,
+            BaseOrQuote,
+            UserOrderIdT,
+            TransactionAccountingT,
+            RiskEngineT,
+        >,
+    ) -> Result<()>
+    where
+        TransactionAccountingT:
+            TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+        RiskEngineT: RiskEngine<I, D, BaseOrQuote, UserOrderIdT>,
+    {
+        match self {
+            Event::Bba(bba) => exchange.update_state(bba).map(|_| ()),
+            Event::Trade(trade) => exchange.update_state(trade).map(|_| ()),
+            Event::Candle(candle) => exchange.update_state(candle).map(|_| ()),
+            Event::SmartCandle(candle) => exchange.update_state(candle).map(|_| ()),
+            Event::SubmitMarketOrder(order) => {
+                exchange.submit_market_order(order.clone()).map(|_| ())
+            }
+            Event::SubmitLimitOrder(order) => {
+                exchange.submit_limit_order(order.clone()).map(|_| ())
+            }
+            Event::CancelLimitOrder(cancel_by) => {
+                exchange.cancel_limit_order(*cancel_by).map(|_| ())
+            }
+            Event::AmendLimitOrder {
+                existing_order_id,
+                new_order,
+            } => exchange
+                .amend_limit_order(*existing_order_id, new_order.clone())
+                .map(|_| ()),
+            Event::AmendOrder {
+                existing_order_id,
+                new_price,
+                new_quantity,
+            } => exchange.amend_order(*existing_order_id, *new_price, *new_quantity),
+            Event::AddIsolatedMargin(amount) => exchange.add_isolated_margin(*amount),
+            Event::RemoveIsolatedMargin(amount) => exchange.remove_isolated_margin(*amount),
+            Event::SetMarkPriceOverride(mark_price) => {
+                exchange.set_mark_price_override(*mark_price);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Records every input event to the `Exchange`, so a session can be replayed deterministically,
+/// e.g to reproduce a bug report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecorder<I, const D: u8, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    events: Vec<Event<I, D, BaseOrQuote, UserOrderIdT>>,
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> Default
+    for EventRecorder<I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    fn default() -> Self {
+        Self { events: Vec::new() }
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> EventRecorder<I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// Record the `event` and drive the `exchange` with it.
+    pub fn apply<TransactionAccountingT, RiskEngineT>(
+        &mut self,
+        exchange: &mut Exchange<
+            I,
+            D,
+            BaseOrQuote,
+            UserOrderIdT,
+            TransactionAccountingT,
+            RiskEngineT,
+        >,
+        event: Event<I, D, BaseOrQuote, UserOrderIdT>,
+    ) -> Result<()>
+    where
+        TransactionAccountingT:
+            TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+        RiskEngineT: RiskEngine<I, D, BaseOrQuote, UserOrderIdT>,
+    {
+        let res = event.apply(exchange);
+        self.events.push(event);
+        res
+    }
+
+    /// The recorded events in the order they occurred.
+    #[inline]
+    pub fn events(&self) -> &[Event<I, D, BaseOrQuote, UserOrderIdT>] {
+        &self.events
+    }
+}
+
+/// Re-drive the `exchange` with the recorded `events`.
+/// Starting from a fresh `Exchange` with the same `Config`, this reproduces the state of the recorded session.
+/// Errors of individual events are part of the recorded session and are therefore ignored.
+pub fn replay<I, const D: u8, BaseOrQuote, UserOrderIdT, TransactionAccountingT, RiskEngineT>(
+    exchange: &mut Exchange<I, D, BaseOrQuote, UserOrderIdT, TransactionAccountingT, RiskEngineT>,
+    events: &[Event<I, D, BaseOrQuote, UserOrderIdT>],
+) where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderIdT: UserOrderId,
+    TransactionAccountingT:
+        TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+    RiskEngineT: RiskEngine<I, D, BaseOrQuote, UserOrderIdT>,
+{
+    for event in events {
+        let _ = event.apply(exchange);
+    }
+}
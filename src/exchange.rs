@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
 
 use assert2::assert;
+use const_decimal::Decimal;
 use getset::Getters;
-use num_traits::Zero;
+use num_traits::{Signed, Zero};
 use tracing::{debug, info, trace, warn};
 
 use crate::{
@@ -12,21 +13,24 @@ use crate::{
     order_margin::OrderMargin,
     order_rate_limiter::OrderRateLimiter,
     prelude::{
-        ActiveLimitOrders, Currency, EXCHANGE_FEE_ACCOUNT, MarketUpdate, Mon, OrderError, Position,
-        QuoteCurrency, RePricing, Transaction, USER_ORDER_MARGIN_ACCOUNT,
-        USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
+        ActiveLimitOrders, BROKER_MARGIN_ACCOUNT, Currency, EXCHANGE_FEE_ACCOUNT, MarketUpdate,
+        Mon, OrderError, Position, QuoteCurrency, RePricing, TREASURY_ACCOUNT, Transaction,
+        USER_ORDER_MARGIN_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
     },
     risk_engine::{IsolatedMarginRiskEngine, RiskEngine},
     types::{
-        Error, ExchangeOrderMeta, Filled, LimitOrder, LimitOrderUpdate, MarginCurrency,
-        MarketOrder, NewOrder, OrderId, Pending, Result, Side, UserBalances, UserOrderId,
+        ClosedTrade, Error, ExchangeOrderMeta, Fee, FeeMode, FeeModel, Fill, Filled, Leverage,
+        LimitOrder, LimitOrderSubmission, LimitOrderUpdate, LiquidityRole, MarginBreakdown,
+        MarginCurrency, MarketOrder, NewOrder, OrderId, Pending, PositionSizing, Result, RiskError,
+        SelfCrossPolicy, Side, Taker, TimestampNs, TradeBlotter, UserBalances, UserOrderId,
+        ValidationMode,
     },
     utils::assert_user_wallet_balance,
 };
 
 /// Whether to cancel a limit order by its `OrderId` or the `UserOrderId`.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CancelBy<UserOrderIdT: UserOrderId> {
     OrderId(OrderId),
     UserOrderId(UserOrderIdT),
@@ -52,12 +56,159 @@ where
     pub position: &'a Position<I, D, BaseOrQuote>,
     /// The TAccount balances of the account.
     pub balances: UserBalances<I, D, BaseOrQuote::PairedCurrency>,
+    /// The mark price at which the position is liquidated, if the `RiskEngine` provides one.
+    pub liquidation_price: Option<QuoteCurrency<I, D>>,
+    /// The maintenance margin of the position at its entry price, as defined by the `RiskEngine`.
+    pub maintenance_margin: BaseOrQuote::PairedCurrency,
+    /// The taker fee of the `ContractSpecification`, with which the fees of exiting the position are estimated.
+    pub fee_taker: Fee<I, D, Taker>,
+    /// How the fees of a fill are computed, as defined by the `Config`.
+    pub fee_model: FeeModel<BaseOrQuote::PairedCurrency>,
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> Account<'_, I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// The initial margin actually locked for the position, read from the `USER_POSITION_MARGIN_ACCOUNT`,
+    /// which includes any margin added with `Exchange::add_isolated_margin`.
+    /// It may deviate slightly from `position.total_cost() * init_margin_req`,
+    /// because the entry price is a weighted average rounded to the decimal precision `D`.
+    #[inline]
+    pub fn locked_initial_margin(&self) -> BaseOrQuote::PairedCurrency {
+        self.balances.position_margin
+    }
+
+    /// The signed exposure of the position at the `mark_price`, which is the futures equivalent of delta.
+    /// It is positive when long, negative when short and denoted in the margin currency.
+    /// For linear futures it is the notional value `quantity * mark_price`.
+    /// For inverse futures it is `quantity / mark_price`, which changes with the price even though the quantity does not.
+    #[inline]
+    pub fn dollar_delta(&self, mark_price: QuoteCurrency<I, D>) -> BaseOrQuote::PairedCurrency {
+        BaseOrQuote::PairedCurrency::convert_from(self.position.quantity(), mark_price)
+    }
+
+    /// The fees of the fills which opened or increased the position, regardless of the `FeeMode`.
+    /// Reducing the position does not change them, so they are the entry fees of the whole position.
+    #[inline]
+    pub fn entry_fees_paid(&self) -> BaseOrQuote::PairedCurrency {
+        self.position.entry_fees()
+    }
+
+    /// The estimated fees of exiting the whole position with a market order at the `mark_price`,
+    /// which is the taker fee of its notional value with `FeeModel::Percentage`.
+    /// Together with `entry_fees_paid` it gives the true break-even of the position.
+    #[inline]
+    pub fn estimated_exit_fees(
+        &self,
+        mark_price: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        self.fee_model.fee_of(
+            self.position.quantity().abs(),
+            mark_price,
+            *self.fee_taker.as_ref(),
+        )
+    }
+
+    /// The exit price at which the current position realizes the `target` profit and loss.
+    /// Fees are not taken into account.
+    ///
+    /// # Returns:
+    /// Either the exit price, or an error if the position is neutral or no positive price realizes the `target`.
+    pub fn price_for_target_pnl(
+        &self,
+        target: BaseOrQuote::PairedCurrency,
+    ) -> Result<QuoteCurrency<I, D>> {
+        let (entry_price, quantity) = match self.position {
+            Position::Neutral => return Err(Error::NoPosition),
+            Position::Long(inner) => (inner.entry_price(), inner.quantity()),
+            Position::Short(inner) => (inner.entry_price(), -inner.quantity()),
+        };
+        BaseOrQuote::PairedCurrency::exit_price_for_pnl(entry_price, target, quantity)
+            .ok_or(Error::UnreachablePnl)
+    }
+
+    /// How far the `mark_price` can move against the position before it is liquidated,
+    /// both as an absolute price difference and in percent of the `mark_price`.
+    /// A mark price beyond the liquidation price has a distance of zero.
+    ///
+    /// # Returns:
+    /// `None` if the position is neutral or the `RiskEngine` provides no liquidation price.
+    pub fn distance_to_liquidation(
+        &self,
+        mark_price: QuoteCurrency<I, D>,
+    ) -> Option<(QuoteCurrency<I, D>, Decimal<I, D>)> {
+        let liquidation_price = self.liquidation_price?;
+        let distance = match self.position {
+            Position::Neutral => return None,
+            Position::Long(_) => mark_price - liquidation_price,
+            Position::Short(_) => liquidation_price - mark_price,
+        }
+        .max(QuoteCurrency::zero());
+        let pct = *distance.as_ref() / *mark_price.as_ref()
+            * Decimal::try_from_scaled(I::from(100).expect("can convert"), 0)
+                .expect("can create decimal");
+        Some((distance, pct))
+    }
+
+    /// Aggregate the margin locked by the position and orders, together with the outstanding fees.
+    pub fn margin_breakdown(&self) -> MarginBreakdown<I, D, BaseOrQuote::PairedCurrency> {
+        MarginBreakdown {
+            initial_margin: self.balances.position_margin,
+            maintenance_margin: self.maintenance_margin,
+            order_margin: self.balances.order_margin,
+            outstanding_fees: self.position.outstanding_fees(),
+            _q: std::marker::PhantomData,
+        }
+    }
+
+    /// The fraction of the collateral which is used as position and order margin,
+    /// being `used_margin / (used_margin + available_wallet_balance)`.
+    /// A flat account without any collateral has a utilization of zero.
+    pub fn margin_utilization(&self) -> Decimal<I, D> {
+        let used_margin = self.balances.position_margin + self.balances.order_margin;
+        let total = used_margin + self.balances.available_wallet_balance;
+        if total <= BaseOrQuote::PairedCurrency::zero() {
+            return Decimal::zero();
+        }
+        *used_margin.as_ref() / *total.as_ref()
+    }
+
+    /// The current leverage of the position at the `mark_price` relative to the `max_leverage`.
+    /// The current leverage is the absolute notional value divided by the equity, which includes the unrealized profit and loss.
+    /// A neutral position or an account without equity has a utilization of zero.
+    pub fn leverage_utilization(
+        &self,
+        mark_price: QuoteCurrency<I, D>,
+        max_leverage: Leverage<I, D>,
+    ) -> Decimal<I, D> {
+        let equity = self.balances.sum() + self.position.unrealized_pnl(mark_price, mark_price);
+        if matches!(self.position, Position::Neutral)
+            || equity <= BaseOrQuote::PairedCurrency::zero()
+        {
+            return Decimal::zero();
+        }
+        let notional = self.dollar_delta(mark_price).abs();
+        *notional.as_ref() / *equity.as_ref() * max_leverage.init_margin_req()
+    }
 }
 
 /// The main leveraged futures exchange for simulated trading
+///
+/// The margin requirements are checked by the `RiskEngineT`, which defaults to isolated margining.
+/// Use `Exchange::with_risk_engine` to provide a different `RiskEngine` implementation.
 #[derive(Debug, Clone, Getters)]
-pub struct Exchange<I, const D: u8, BaseOrQuote, UserOrderIdT, TransactionAccountingT>
-where
+pub struct Exchange<
+    I,
+    const D: u8,
+    BaseOrQuote,
+    UserOrderIdT,
+    TransactionAccountingT,
+    RiskEngineT = IsolatedMarginRiskEngine<I, D, BaseOrQuote>,
+> where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
     BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
@@ -71,11 +222,12 @@ where
     #[getset(get = "pub")]
     market_state: MarketState<I, D>,
 
-    risk_engine: IsolatedMarginRiskEngine<I, D, BaseOrQuote>,
+    risk_engine: RiskEngineT,
 
     next_order_id: OrderId,
 
     /// Does the accounting for transactions, moving balances between accounts.
+    #[getset(get = "pub")]
     transaction_accounting: TransactionAccountingT,
 
     /// Get the current position of the user.
@@ -92,9 +244,31 @@ where
 
     // To avoid allocations in hot-paths
     limit_order_updates: Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>>,
+    // The limit order updates which happened outside of `update_state`, reported with the next `UpdateOutcome`.
+    deferred_limit_order_updates: Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>>,
     ids_to_remove: Vec<OrderId>,
 
     order_rate_limiter: OrderRateLimiter,
+
+    // The number of market and limit orders submitted since the last market update.
+    trades_this_tick: u32,
+
+    // When the last position was closed, which starts the `cooldown`.
+    last_close_ts_ns: Option<TimestampNs>,
+
+    trade_blotter: TradeBlotter<I, D, BaseOrQuote>,
+
+    // Whether the equity fell below the `min_account_balance`, which halts all trading.
+    blown_up: bool,
+
+    // Whether the contract reached its `expiry` and the position was settled, which halts all trading.
+    expired: bool,
+
+    // Cancel requests which take effect once the `cancel_latency` elapsed, with the timestamp at which they do.
+    pending_cancels: Vec<(TimestampNs, CancelBy<UserOrderIdT>)>,
+
+    // The mark price of the last maintenance margin check, which is reset whenever the position changes.
+    maintenance_mark_price: Option<QuoteCurrency<I, D>>,
 }
 
 impl<I, const D: u8, BaseOrQuote, UserOrderIdT, TransactionAccountingT>
@@ -109,14 +283,45 @@ where
 {
     /// Create a new Exchange with the desired config and whether to use candles
     /// as infomation source
+    /// The margin requirements are checked with an `IsolatedMarginRiskEngine`.
     pub fn new(config: Config<I, D, BaseOrQuote::PairedCurrency>) -> Self {
-        let market_state = MarketState::default();
         let risk_engine = IsolatedMarginRiskEngine::new(config.contract_spec().clone());
+        Self::with_risk_engine(config, risk_engine)
+    }
+}
 
-        let transaction_accounting = TransactionAccountingT::new(config.starting_wallet_balance());
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT, TransactionAccountingT, RiskEngineT>
+    Exchange<I, D, BaseOrQuote, UserOrderIdT, TransactionAccountingT, RiskEngineT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    UserOrderIdT: UserOrderId,
+    TransactionAccountingT:
+        TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+    RiskEngineT: RiskEngine<I, D, BaseOrQuote, UserOrderIdT>,
+{
+    /// Create a new Exchange with the desired config, which checks the margin requirements with a custom `risk_engine`.
+    pub fn with_risk_engine(
+        config: Config<I, D, BaseOrQuote::PairedCurrency>,
+        risk_engine: RiskEngineT,
+    ) -> Self {
+        let market_state = MarketState::default();
+
+        let mut transaction_accounting =
+            TransactionAccountingT::new(config.starting_wallet_balance());
+        if config.initial_treasury_balance() > BaseOrQuote::PairedCurrency::zero() {
+            let transaction = Transaction::new(
+                TREASURY_ACCOUNT,
+                BROKER_MARGIN_ACCOUNT,
+                config.initial_treasury_balance(),
+            );
+            transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works");
+        }
         let max_active_orders = config.max_num_open_orders();
-        let order_rate_limiter =
-            OrderRateLimiter::new(config.order_rate_limits().orders_per_second());
+        let order_rate_limiter = OrderRateLimiter::new(*config.order_rate_limits());
         Self {
             config,
             market_state,
@@ -128,8 +333,16 @@ where
             active_limit_orders: ActiveLimitOrders::new(10_000),
             order_margin: OrderMargin::new(max_active_orders),
             limit_order_updates: Vec::with_capacity(max_active_orders),
+            deferred_limit_order_updates: Vec::new(),
             ids_to_remove: Vec::with_capacity(max_active_orders),
             order_rate_limiter,
+            trades_this_tick: 0,
+            last_close_ts_ns: None,
+            trade_blotter: TradeBlotter::default(),
+            blown_up: false,
+            expired: false,
+            pending_cancels: Vec::with_capacity(max_active_orders),
+            maintenance_mark_price: None,
         }
     }
 
@@ -139,7 +352,100 @@ where
             active_limit_orders: &self.active_limit_orders,
             position: &self.position,
             balances: self.user_balances(),
+            liquidation_price:
+                <RiskEngineT as RiskEngine<I, D, BaseOrQuote, UserOrderIdT>>::liquidation_price(
+                    &self.risk_engine,
+                    &self.position,
+                ),
+            maintenance_margin:
+                <RiskEngineT as RiskEngine<I, D, BaseOrQuote, UserOrderIdT>>::maintenance_margin(
+                    &self.risk_engine,
+                    &self.position,
+                ),
+            fee_taker: self.config.contract_spec().fee_taker(),
+            fee_model: self.config.fee_model(),
+        }
+    }
+
+    /// The number of open positions, which is either 0 or 1 as there is a single net position.
+    #[inline]
+    pub fn open_position_count(&self) -> usize {
+        match self.position {
+            Position::Neutral => 0,
+            Position::Long(_) | Position::Short(_) => 1,
+        }
+    }
+
+    /// Whether the account equity fell below the `min_account_balance` of the `Config`.
+    /// A blown up account has no position or orders and rejects all further orders with `Error::AccountBlownUp`.
+    #[inline]
+    pub fn is_blown_up(&self) -> bool {
+        self.blown_up
+    }
+
+    /// The completed round-trip trades of the position, oldest first.
+    /// A trade is recorded once the position returns to flat, including by liquidation or at the `expiry`.
+    #[inline]
+    pub fn closed_trades(&self) -> &[ClosedTrade<I, D, BaseOrQuote>] {
+        self.trade_blotter.closed_trades()
+    }
+
+    /// The realized profit and loss of the long `closed_trades`, gross of fees and denoted in the margin currency.
+    /// Together with `short_realized_pnl` it sums to the `realized_pnl` whenever the position is neutral.
+    pub fn long_realized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.closed_trades_realized_pnl(Side::Buy)
+    }
+
+    /// The realized profit and loss of the short `closed_trades`, gross of fees and denoted in the margin currency.
+    pub fn short_realized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.closed_trades_realized_pnl(Side::Sell)
+    }
+
+    /// The average net profit and loss per closed trade, denoted in the margin currency.
+    /// It equals `win_rate * average_win - loss_rate * average_loss`.
+    /// `None` if no trade was closed yet.
+    pub fn expectancy(&self) -> Option<BaseOrQuote::PairedCurrency> {
+        let closed_trades = self.closed_trades();
+        if closed_trades.is_empty() {
+            return None;
+        }
+        let mut net_pnl = BaseOrQuote::PairedCurrency::zero();
+        for trade in closed_trades {
+            net_pnl += trade.net_pnl();
+        }
+        let num_trades =
+            Decimal::try_from_scaled(I::from(closed_trades.len()).expect("can convert"), 0)
+                .expect("can convert");
+        Some(BaseOrQuote::PairedCurrency::from(
+            *net_pnl.as_ref() / num_trades,
+        ))
+    }
+
+    fn closed_trades_realized_pnl(&self, side: Side) -> BaseOrQuote::PairedCurrency {
+        let mut pnl = BaseOrQuote::PairedCurrency::zero();
+        for trade in self
+            .closed_trades()
+            .iter()
+            .filter(|trade| trade.side() == side)
+        {
+            pnl += trade.gross_pnl();
         }
+        pnl
+    }
+
+    /// Whether the dated futures contract reached the `expiry` of its `ContractSpecification`.
+    /// An expired contract has no position or orders and rejects all further orders with `Error::ContractExpired`.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Get the available wallet balance of the user, denoted in the margin currency.
+    #[inline]
+    pub fn wallet_balance(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(USER_WALLET_ACCOUNT)
+            .expect("is valid account")
     }
 
     /// Get the total amount of fees paid to the exchange.
@@ -149,6 +455,57 @@ where
             .expect("is valid account")
     }
 
+    /// Get the fees accrued by the open position, which are not yet settled.
+    /// They are paid once the position is reduced or closed, so they should be deducted in equity estimates.
+    #[inline]
+    pub fn outstanding_fees(&self) -> BaseOrQuote::PairedCurrency {
+        self.position.outstanding_fees()
+    }
+
+    /// Get the total amount of fees the exchange collected, which is the balance of the `EXCHANGE_FEE_ACCOUNT`.
+    #[inline]
+    pub fn exchange_fees_collected(&self) -> BaseOrQuote::PairedCurrency {
+        self.fees_paid()
+    }
+
+    /// Get the balance of the `TREASURY_ACCOUNT`, which is the counterparty of the users profit and loss.
+    /// It starts out at the `initial_treasury_balance` minus the starting wallet balance of the user,
+    /// decreases with the users realized profits and increases with the users realized losses.
+    pub fn treasury_balance(&self) -> BaseOrQuote::PairedCurrency {
+        self.transaction_accounting
+            .margin_balance_of(TREASURY_ACCOUNT)
+            .expect("is valid account")
+    }
+
+    /// Get the cumulative realized profit and loss of the user, gross of fees and denoted in the margin currency.
+    /// It is the amount the `TREASURY_ACCOUNT` paid out to the user since the start.
+    pub fn realized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.config.initial_treasury_balance()
+            - self.config.starting_wallet_balance()
+            - self.treasury_balance()
+    }
+
+    /// Get the unrealized profit and loss of the position, marked with the `MarkMethod` of the `ContractSpecification`.
+    /// It is gross of the outstanding fees and denoted in the margin currency.
+    pub fn unrealized_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        let mark_method = self.config.contract_spec().mark_method();
+        match &self.position {
+            Position::Neutral => BaseOrQuote::PairedCurrency::zero(),
+            Position::Long(inner) => {
+                inner.unrealized_pnl(mark_method.mark_price(&self.market_state, Side::Buy))
+            }
+            Position::Short(inner) => {
+                -inner.unrealized_pnl(mark_method.mark_price(&self.market_state, Side::Sell))
+            }
+        }
+    }
+
+    /// Get the sum of the `realized_pnl` and the `unrealized_pnl`.
+    #[inline]
+    pub fn total_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.realized_pnl() + self.unrealized_pnl()
+    }
+
     /// Update the exchange state with new information
     /// Returns a reference to order updates vector for performance reasons.
     ///
@@ -158,33 +515,230 @@ where
     /// `market_update`: Newest market information
     ///
     /// ### Returns:
-    /// If Ok, returns updates regarding limit orders, wether partially filled or fully.
+    /// If Ok, returns the `UpdateOutcome` with the fills and cancellations of limit orders,
+    /// and whether the position was liquidated, the account blew up or the contract expired.
+    /// If the position is liquidated at the end of the update, the fills of this update are still reported,
+    /// as is the flattening of the account once its equity falls below the `min_account_balance`.
+    /// Once the `expiry` of a dated futures contract is reached, all active limit orders are cancelled
+    /// and the position is settled at the `settlement_price`, after which the exchange halts.
+    ///
+    /// The maintenance margin is only checked here, never when submitting orders,
+    /// and only if the mark price of the position changed since the last check, or the position changed in the meantime.
     pub fn update_state<U>(
         &mut self,
         market_update: &U,
-    ) -> Result<&Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>>>
+    ) -> Result<UpdateOutcome<'_, I, D, BaseOrQuote, UserOrderIdT>>
     where
         U: MarketUpdate<I, D, BaseOrQuote>,
     {
         trace!("update_state: market_update: {market_update}");
 
-        self.market_state
-            .update_state(market_update, self.config.contract_spec().price_filter())?;
+        self.market_state.update_state(
+            market_update,
+            self.config.contract_spec().price_filter(),
+            self.config.crossed_market_policy(),
+            self.config.max_price_move_pct(),
+        )?;
+        self.transaction_accounting
+            .update_timestamp(self.market_state.current_timestamp_ns());
+        self.trades_this_tick = 0;
+        self.limit_order_updates.clear();
+        self.limit_order_updates
+            .append(&mut self.deferred_limit_order_updates);
+        self.apply_due_cancels();
 
-        if let Err(e) = <IsolatedMarginRiskEngine<I, D, BaseOrQuote> as RiskEngine<
-            I,
-            D,
-            BaseOrQuote,
-            UserOrderIdT,
-        >>::check_maintenance_margin(
-            &self.risk_engine, &self.market_state, &self.position
-        ) {
+        if self.expired {
+            return Ok(self.update_outcome());
+        }
+        if self
+            .config
+            .contract_spec()
+            .expiry()
+            .is_some_and(|expiry| self.market_state.current_timestamp_ns() >= expiry)
+        {
+            self.settle_at_expiry();
+            return Ok(UpdateOutcome {
+                limit_order_updates: &self.limit_order_updates,
+                liquidated: false,
+                blown_up: false,
+                expired: true,
+            });
+        }
+
+        // A flat account without active limit orders can neither be filled nor liquidated.
+        if matches!(self.position, Position::Neutral) && self.active_limit_orders.is_empty() {
+            return Ok(self.update_outcome());
+        }
+
+        // All fills of this update are processed before the maintenance margin check,
+        // so a fill which reduces the position is not preempted by a premature liquidation.
+        self.fill_active_orders(market_update);
+
+        let maintenance_check = if self.update_maintenance_mark_price() {
+            <RiskEngineT as RiskEngine<I, D, BaseOrQuote, UserOrderIdT>>::check_maintenance_margin(
+                &self.risk_engine,
+                &self.market_state,
+                &self.position,
+            )
+        } else {
+            Ok(())
+        };
+        let liquidated = maintenance_check.is_err();
+        if liquidated {
+            self.liquidate();
+        }
+        let blown_up = self.is_below_min_account_balance();
+        if blown_up {
+            self.blow_up();
+        }
+
+        Ok(UpdateOutcome {
+            limit_order_updates: &self.limit_order_updates,
+            liquidated,
+            blown_up,
+            expired: false,
+        })
+    }
+
+    // The outcome of an update in which neither the position nor the account changed, other than by due cancellations.
+    fn update_outcome(&self) -> UpdateOutcome<'_, I, D, BaseOrQuote, UserOrderIdT> {
+        UpdateOutcome {
+            limit_order_updates: &self.limit_order_updates,
+            liquidated: false,
+            blown_up: false,
+            expired: false,
+        }
+    }
+
+    // Record the current mark price of the position,
+    // returning whether it differs from the one of the last maintenance margin check.
+    fn update_maintenance_mark_price(&mut self) -> bool {
+        let side = match self.position {
+            Position::Neutral => {
+                self.maintenance_mark_price = None;
+                return false;
+            }
+            Position::Long(_) => Side::Buy,
+            Position::Short(_) => Side::Sell,
+        };
+        let mark_price = self
+            .config
+            .contract_spec()
+            .mark_method()
+            .mark_price(&self.market_state, side);
+        let changed = self.maintenance_mark_price != Some(mark_price);
+        self.maintenance_mark_price = Some(mark_price);
+        changed
+    }
+
+    // Whether the equity of the account is below the `min_account_balance`.
+    fn is_below_min_account_balance(&self) -> bool {
+        let Some(min_account_balance) = self.config.min_account_balance() else {
+            return false;
+        };
+        let balances = self.user_balances();
+        let equity = balances.available_wallet_balance
+            + balances.position_margin
+            + balances.order_margin
+            + self
+                .position
+                .unrealized_pnl(self.market_state.bid(), self.market_state.ask())
+            - self.position.outstanding_fees();
+        equity < min_account_balance
+    }
+
+    // Cancel all active limit orders and close the position, after which no further orders are accepted.
+    fn blow_up(&mut self) {
+        warn!(
+            "account blown up with balances {:?} and position {}",
+            self.user_balances(),
+            self.position
+        );
+        self.cancel_all_active_orders();
+        if !matches!(self.position, Position::Neutral) {
             self.liquidate();
-            return Err(e.into());
+        }
+        self.blown_up = true;
+    }
+
+    // Cancel all active limit orders and close the position at the `settlement_price` without fees,
+    // after which no further orders are accepted.
+    fn settle_at_expiry(&mut self) {
+        let settlement_price = self
+            .config
+            .contract_spec()
+            .settlement_price()
+            .expect("Is set together with the `expiry`");
+        info!(
+            "contract expired, settling position {} at {settlement_price}",
+            self.position
+        );
+        self.cancel_all_active_orders();
+        let (side, quantity) = match &self.position {
+            Position::Neutral => {
+                self.expired = true;
+                return;
+            }
+            Position::Long(inner) => (Side::Sell, inner.quantity()),
+            Position::Short(inner) => (Side::Buy, inner.quantity()),
         };
+        self.maintenance_mark_price = None;
+        self.trade_blotter.record_fill(
+            &self.position,
+            side,
+            quantity,
+            settlement_price,
+            BaseOrQuote::PairedCurrency::zero(),
+            self.market_state.current_timestamp_ns(),
+        );
+        self.position.change_position(
+            quantity,
+            settlement_price,
+            side,
+            &mut self.transaction_accounting,
+            self.config.contract_spec(),
+            BaseOrQuote::PairedCurrency::zero(),
+        );
+        self.last_close_ts_ns = Some(self.market_state.current_timestamp_ns());
+        self.debug_assert_position_margin();
+        self.debug_assert_pnl_conservation();
+        self.expired = true;
+    }
 
-        self.check_active_orders(market_update);
-        Ok(&self.limit_order_updates)
+    // Remove all active limit orders and pending cancels, returning the order margin to the wallet.
+    // The cancelled orders are reported as `LimitOrderUpdate::Cancelled`.
+    fn cancel_all_active_orders(&mut self) {
+        self.ids_to_remove
+            .extend(self.active_limit_orders.values().map(|order| order.id()));
+        for id in self.ids_to_remove.drain(..) {
+            let order = self
+                .active_limit_orders
+                .remove_by_order_id(id)
+                .expect("The order is active");
+            self.order_margin.remove(CancelBy::OrderId(id));
+            self.limit_order_updates
+                .push(LimitOrderUpdate::Cancelled(order));
+        }
+        let order_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+            .expect("is a valid account");
+        if order_margin > BaseOrQuote::PairedCurrency::zero() {
+            let transaction =
+                Transaction::new(USER_WALLET_ACCOUNT, USER_ORDER_MARGIN_ACCOUNT, order_margin);
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works");
+        }
+        self.pending_cancels.clear();
+    }
+
+    /// Override the mark price of the `MarkMethod`, e.g to stress test liquidations while the bid and ask stay fillable.
+    /// It is used by the maintenance margin check of the next `update_state`, while orders still fill at the bid and ask.
+    /// `None` reverts to the configured `MarkMethod`.
+    #[inline]
+    pub fn set_mark_price_override(&mut self, mark_price: Option<QuoteCurrency<I, D>>) {
+        self.market_state.set_mark_price_override(mark_price);
     }
 
     /// Set the best bid and ask, alternatively a `Bba` `MarketUpdate` can be passed into `update_state`
@@ -195,22 +749,82 @@ where
         self.market_state.set_ask(ask);
     }
 
-    // Liquidate the position by closing it with a market order.
+    /// Move the `amount` from the wallet into the isolated margin of the open position without changing its size,
+    /// which moves the liquidation price away from the entry price.
+    /// Errors if there is no open position or the available wallet balance is insufficient.
+    pub fn add_isolated_margin(&mut self, amount: BaseOrQuote::PairedCurrency) -> Result<()> {
+        if amount <= BaseOrQuote::PairedCurrency::zero() {
+            return Err(Error::InvalidMarginAmount);
+        }
+        let available_wallet_balance = self
+            .transaction_accounting
+            .margin_balance_of(USER_WALLET_ACCOUNT)?;
+        let inner = match &mut self.position {
+            Position::Neutral => return Err(Error::NoPosition),
+            Position::Long(inner) | Position::Short(inner) => inner,
+        };
+        if amount > available_wallet_balance {
+            return Err(RiskError::NotEnoughAvailableBalance.into());
+        }
+        inner.add_margin(amount, &mut self.transaction_accounting);
+        // The liquidation price moved, so the next market update checks the maintenance margin again.
+        self.maintenance_mark_price = None;
+        Ok(())
+    }
+
+    /// Move the `amount` of margin previously added with `add_isolated_margin` from the open position back into the wallet,
+    /// which moves the liquidation price towards the entry price.
+    /// Errors if there is no open position, the `amount` exceeds the added margin
+    /// or the position would no longer satisfy its maintenance margin at the current mark price.
+    pub fn remove_isolated_margin(&mut self, amount: BaseOrQuote::PairedCurrency) -> Result<()> {
+        if amount <= BaseOrQuote::PairedCurrency::zero() {
+            return Err(Error::InvalidMarginAmount);
+        }
+        let mut position = self.position.clone();
+        let inner = match &mut position {
+            Position::Neutral => return Err(Error::NoPosition),
+            Position::Long(inner) | Position::Short(inner) => inner,
+        };
+        if amount > inner.added_margin() {
+            return Err(RiskError::NotEnoughAvailableBalance.into());
+        }
+        inner.reduce_added_margin(amount);
+        <RiskEngineT as RiskEngine<I, D, BaseOrQuote, UserOrderIdT>>::check_maintenance_margin(
+            &self.risk_engine,
+            &self.market_state,
+            &position,
+        )?;
+
+        let transaction =
+            Transaction::new(USER_WALLET_ACCOUNT, USER_POSITION_MARGIN_ACCOUNT, amount);
+        self.transaction_accounting
+            .create_margin_transfer(transaction)
+            .expect("is an internal call and must work");
+        self.position = position;
+        self.maintenance_mark_price = None;
+        Ok(())
+    }
+
+    // Liquidate the position by closing it entirely at the `MarketFillPrice`.
+    // Unlike a user submitted market order, the close is neither rounded by the `ValidationMode`
+    // nor subject to the order rate limit, `max_trades_per_tick` or the `cooldown`,
+    // so it can not be rejected or be partial.
     fn liquidate(&mut self) {
         warn!("liquidating position {}", self.position);
-        debug_assert!(self.market_state.ask() > QuoteCurrency::zero());
-        debug_assert!(self.market_state.bid() > QuoteCurrency::zero());
-        let order = match &self.position {
-            Position::Long(pos) => {
-                MarketOrder::new(Side::Sell, pos.quantity()).expect("Can create market order.")
-            }
-            Position::Short(pos) => {
-                MarketOrder::new(Side::Buy, pos.quantity()).expect("Can create market order.")
-            }
+        let bid = self.market_state.bid();
+        let ask = self.market_state.ask();
+        debug_assert!(ask > QuoteCurrency::zero());
+        debug_assert!(bid > QuoteCurrency::zero());
+        let (side, quantity) = match &self.position {
+            Position::Long(pos) => (Side::Sell, pos.quantity()),
+            Position::Short(pos) => (Side::Buy, pos.quantity()),
             Position::Neutral => panic!("A neutral position can not be liquidated"),
         };
-        self.submit_market_order(order)
-            .expect("Must be able to submit liquidation order");
+        let market_fill_price = self.config.market_fill_price();
+        let fill_price = market_fill_price.fill_price(side, bid, ask);
+        let spread_cost = market_fill_price.spread_cost(side, quantity, bid, ask);
+        self.settle_taker_fill(side, quantity, fill_price, spread_cost);
+        debug_assert!(matches!(self.position, Position::Neutral));
         info!("balances after liquidation: {:?}", self.user_balances());
     }
 
@@ -220,19 +834,34 @@ where
     /// `order`: The order that is being submitted.
     ///
     /// # Returns:
-    /// If Ok, the order with timestamp and id filled in.
+    /// If Ok, the `Fill` containing the order with timestamp and id filled in, as well as the fee charged.
     /// Else its an error.
     pub fn submit_market_order(
         &mut self,
-        order: MarketOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
-    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>> {
+        mut order: MarketOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
+    ) -> Result<Fill<I, D, BaseOrQuote, UserOrderIdT>> {
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if !self.market_state.has_market_data() {
+            return Err(Error::NoMarketData);
+        }
         self.order_rate_limiter
             .aquire(self.market_state.current_ts_ns())?;
+        self.count_trade()?;
+        if self.config.validation_mode() == ValidationMode::Lenient {
+            let quantity_filter = self.config.contract_spec().quantity_filter();
+            order.set_quantity(quantity_filter.round_order_quantity(order.quantity()));
+        }
         // Basic checks
         self.config
             .contract_spec()
             .quantity_filter()
             .validate_order_quantity(order.quantity())?;
+        self.check_cooldown(order.side(), order.quantity())?;
 
         let meta = ExchangeOrderMeta::new(
             self.next_order_id(),
@@ -242,10 +871,11 @@ where
 
         debug_assert!(self.market_state.ask() > QuoteCurrency::zero());
         debug_assert!(self.market_state.bid() > QuoteCurrency::zero());
-        let fill_price = match order.side() {
-            Side::Buy => self.market_state.ask(),
-            Side::Sell => self.market_state.bid(),
-        };
+        let fill_price = self.config.market_fill_price().fill_price(
+            order.side(),
+            self.market_state.bid(),
+            self.market_state.ask(),
+        );
         let position_margin = self
             .transaction_accounting
             .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)?;
@@ -260,32 +890,235 @@ where
             available_wallet_balance,
         )?;
 
+        let spread_cost = self.config.market_fill_price().spread_cost(
+            order.side(),
+            order.quantity(),
+            self.market_state.bid(),
+            self.market_state.ask(),
+        );
         let filled_order = order.into_filled(fill_price, self.market_state.current_timestamp_ns());
-        self.settle_filled_market_order(filled_order.clone());
+        let fee = self.settle_filled_market_order(filled_order.clone(), spread_cost);
+
+        Ok(Fill::new(filled_order, fee, LiquidityRole::Taker))
+    }
+
+    // Enforces `max_trades_per_tick`, counting the submission if it is allowed.
+    fn count_trade(&mut self) -> Result<()> {
+        if let Some(max_trades) = self.config.max_trades_per_tick() {
+            if self.trades_this_tick >= max_trades {
+                return Err(Error::MaxTradesPerTickReached);
+            }
+        }
+        self.trades_this_tick += 1;
+        Ok(())
+    }
 
-        Ok(filled_order)
+    // Rejects orders which open a new position before the `cooldown` since the last close has elapsed.
+    // Orders which only reduce the current position are exempt.
+    fn check_cooldown(&self, side: Side, quantity: BaseOrQuote) -> Result<()> {
+        let (Some(cooldown), Some(last_close_ts_ns)) =
+            (self.config.cooldown(), self.last_close_ts_ns)
+        else {
+            return Ok(());
+        };
+        if self.market_state.current_timestamp_ns() >= last_close_ts_ns + cooldown {
+            return Ok(());
+        }
+        let reduces_position = match &self.position {
+            Position::Neutral => false,
+            Position::Long(inner) => side == Side::Sell && quantity <= inner.quantity(),
+            Position::Short(inner) => side == Side::Buy && quantity <= inner.quantity(),
+        };
+        if reduces_position {
+            Ok(())
+        } else {
+            Err(Error::CooldownActive)
+        }
+    }
+
+    // Reject a limit order of `side` at `limit_price` which would cross a resting limit order under `SelfCrossPolicy::Reject`.
+    fn check_self_cross(&self, side: Side, limit_price: QuoteCurrency<I, D>) -> Result<()> {
+        if matches!(self.config.self_cross_policy(), SelfCrossPolicy::Reject)
+            && self.self_crossed_orders(side, limit_price).next().is_some()
+        {
+            return Err(Error::SelfCross);
+        }
+        Ok(())
+    }
+
+    // The ids of the resting limit orders of the opposite side which a limit order of `side` at `limit_price` crosses.
+    fn self_crossed_orders(
+        &self,
+        side: Side,
+        limit_price: QuoteCurrency<I, D>,
+    ) -> impl Iterator<Item = OrderId> + '_ {
+        self.active_limit_orders
+            .values()
+            .filter(move |resting| match side {
+                Side::Buy => resting.side() == Side::Sell && resting.limit_price() <= limit_price,
+                Side::Sell => resting.side() == Side::Buy && resting.limit_price() >= limit_price,
+            })
+            .map(|resting| resting.id())
     }
 
+    // Cancel the resting limit orders crossed by an accepted limit order under `SelfCrossPolicy::CancelResting`,
+    // once the new order passed all checks. The cancellations are reported with the next `UpdateOutcome`.
+    fn cancel_self_crossed_orders(&mut self, side: Side, limit_price: QuoteCurrency<I, D>) {
+        if !matches!(
+            self.config.self_cross_policy(),
+            SelfCrossPolicy::CancelResting
+        ) {
+            return;
+        }
+        let mut crossed = std::mem::take(&mut self.ids_to_remove);
+        crossed.extend(self.self_crossed_orders(side, limit_price));
+        for id in crossed.drain(..) {
+            let order = self
+                .remove_limit_order(CancelBy::OrderId(id))
+                .expect("The order is active");
+            self.deferred_limit_order_updates
+                .push(LimitOrderUpdate::Cancelled(order));
+        }
+        self.ids_to_remove = crossed;
+    }
+
+    // Whether the position was closed, either to neutral or by flipping its side.
+    fn is_position_closed(
+        was_long: bool,
+        was_short: bool,
+        position: &Position<I, D, BaseOrQuote>,
+    ) -> bool {
+        match position {
+            Position::Neutral => was_long || was_short,
+            Position::Long(_) => was_short,
+            Position::Short(_) => was_long,
+        }
+    }
+
+    // Returns the total fee charged for the fill.
     fn settle_filled_market_order(
         &mut self,
         order: MarketOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>,
-    ) {
-        let filled_qty = order.quantity();
+        spread_cost: BaseOrQuote::PairedCurrency,
+    ) -> BaseOrQuote::PairedCurrency {
+        self.settle_taker_fill(
+            order.side(),
+            order.quantity(),
+            order.state().avg_fill_price(),
+            spread_cost,
+        )
+    }
+
+    // Settles a fill which took liquidity, returning the total fees charged for it,
+    // which include the explicit `spread_cost` of a fill at the mid price.
+    fn settle_taker_fill(
+        &mut self,
+        side: Side,
+        filled_qty: BaseOrQuote,
+        fill_price: QuoteCurrency<I, D>,
+        spread_cost: BaseOrQuote::PairedCurrency,
+    ) -> BaseOrQuote::PairedCurrency {
         assert!(filled_qty > BaseOrQuote::zero());
-        let fill_price = order.state().avg_fill_price();
         assert!(fill_price > QuoteCurrency::zero());
 
-        let value = BaseOrQuote::PairedCurrency::convert_from(filled_qty, fill_price);
-        let fees = value * *self.config.contract_spec().fee_taker().as_ref();
+        let total_fees = self.config.fee_model().fee_of(
+            filled_qty,
+            fill_price,
+            *self.config.contract_spec().fee_taker().as_ref(),
+        ) + spread_cost;
+        let fees = Self::charge_fees(
+            self.config.fee_mode(),
+            &mut self.transaction_accounting,
+            total_fees,
+        );
 
+        let was_long = matches!(self.position, Position::Long(_));
+        let was_short = matches!(self.position, Position::Short(_));
+        self.maintenance_mark_price = None;
+        self.trade_blotter.record_fill(
+            &self.position,
+            side,
+            filled_qty,
+            fill_price,
+            total_fees,
+            self.market_state.current_timestamp_ns(),
+        );
         self.position.change_position(
             filled_qty,
             fill_price,
-            order.side(),
+            side,
             &mut self.transaction_accounting,
-            self.config.contract_spec().init_margin_req(),
+            self.config.contract_spec(),
             fees,
         );
+        self.position.add_entry_fees(side, filled_qty, total_fees);
+        if Self::is_position_closed(was_long, was_short, &self.position) {
+            self.last_close_ts_ns = Some(self.market_state.current_timestamp_ns());
+        }
+        self.debug_assert_position_margin();
+        self.debug_assert_pnl_conservation();
+
+        total_fees
+    }
+
+    // The position margin must be released entirely once the position is closed, and be locked while it is open.
+    #[inline]
+    fn debug_assert_position_margin(&self) {
+        debug_assert!({
+            let position_margin = self
+                .transaction_accounting
+                .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
+                .expect("is a valid account");
+            match &self.position {
+                Position::Neutral => position_margin.is_zero(),
+                Position::Long(_) | Position::Short(_) => {
+                    position_margin > BaseOrQuote::PairedCurrency::zero()
+                }
+            }
+        });
+    }
+
+    // The users realized profits are paid by the treasury and its losses are paid to it,
+    // so the changes of both balances only differ by the fees paid to the exchange.
+    #[inline]
+    fn debug_assert_pnl_conservation(&self) {
+        debug_assert!({
+            let balance_of = |account| {
+                self.transaction_accounting
+                    .margin_balance_of(account)
+                    .expect("is a valid account")
+            };
+            let user_change = balance_of(USER_WALLET_ACCOUNT)
+                + balance_of(USER_ORDER_MARGIN_ACCOUNT)
+                + balance_of(USER_POSITION_MARGIN_ACCOUNT)
+                - self.config.starting_wallet_balance();
+            let treasury_change = balance_of(TREASURY_ACCOUNT)
+                - (self.config.initial_treasury_balance() - self.config.starting_wallet_balance());
+            user_change + treasury_change == -balance_of(EXCHANGE_FEE_ACCOUNT)
+        });
+    }
+
+    /// Pays the `fees` of a fill immediately if the `FeeMode` requires it.
+    /// Returns the fees which remain outstanding with the position.
+    #[inline]
+    fn charge_fees(
+        fee_mode: FeeMode,
+        transaction_accounting: &mut TransactionAccountingT,
+        fees: BaseOrQuote::PairedCurrency,
+    ) -> BaseOrQuote::PairedCurrency {
+        match fee_mode {
+            FeeMode::Deferred => fees,
+            FeeMode::Upfront => {
+                if fees > BaseOrQuote::PairedCurrency::zero() {
+                    let transaction =
+                        Transaction::new(EXCHANGE_FEE_ACCOUNT, USER_WALLET_ACCOUNT, fees);
+                    transaction_accounting
+                        .create_margin_transfer(transaction)
+                        .expect("margin transfer works");
+                }
+                BaseOrQuote::PairedCurrency::zero()
+            }
+        }
     }
 
     #[inline]
@@ -295,20 +1128,110 @@ where
         oid
     }
 
+    /// Create a `MarketOrder` with a notional value of `fraction` times the wallet balance.
+    /// The notional value is converted at the current best price the order would be filled at
+    /// and the quantity is rounded towards zero to the `tick_size` of the `QuantityFilter`.
+    ///
+    /// # Arguments:
+    /// `side`: The side of the order.
+    /// `fraction`: The fraction of the wallet balance to use.
+    /// `sizing`: Whether to use the current or the starting wallet balance.
+    ///
+    /// # Returns:
+    /// If Ok, a new `MarketOrder` which can be submitted.
+    /// Else its an error, e.g if the resulting quantity is zero.
+    pub fn order_by_balance_fraction(
+        &self,
+        side: Side,
+        fraction: Decimal<I, D>,
+        sizing: PositionSizing,
+    ) -> Result<MarketOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>> {
+        let balance = match sizing {
+            PositionSizing::Compounding => self.wallet_balance(),
+            PositionSizing::Fixed => self.config.starting_wallet_balance(),
+        };
+        let price = match side {
+            Side::Buy => self.market_state.ask(),
+            Side::Sell => self.market_state.bid(),
+        };
+        debug_assert!(price > QuoteCurrency::zero());
+        let quantity = BaseOrQuote::convert_from(balance * fraction, price);
+        let tick_size = self.config.contract_spec().quantity_filter().tick_size();
+        let quantity = quantity - quantity % tick_size;
+
+        Ok(MarketOrder::new(side, quantity)?)
+    }
+
+    /// Compute the wallet balance required to open a position of `quantity` at `price` from a neutral position,
+    /// being the initial margin plus the fee of the fill.
+    /// The maker or taker fee is chosen by the `LiquidityRole` an order at `price` would have.
+    /// This is the inverse of `order_by_balance_fraction`.
+    pub fn required_balance_to_open(
+        &self,
+        side: Side,
+        quantity: BaseOrQuote,
+        price: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency {
+        let margin = BaseOrQuote::PairedCurrency::convert_from(quantity, price)
+            * self.config.contract_spec().init_margin_req_for(side);
+        margin + self.estimated_fee(quantity, price, self.liquidity_role(side, price))
+    }
+
+    /// Compute the fee a fill of `quantity` at `price` would be charged with the configured `FeeModel`,
+    /// using the maker or taker fee depending on the `liquidity_role`.
+    /// The fee does not depend on the side of the order.
+    pub fn estimated_fee(
+        &self,
+        quantity: BaseOrQuote,
+        price: QuoteCurrency<I, D>,
+        liquidity_role: LiquidityRole,
+    ) -> BaseOrQuote::PairedCurrency {
+        let contract_spec = self.config.contract_spec();
+        let fee = match liquidity_role {
+            LiquidityRole::Maker => *contract_spec.fee_maker().as_ref(),
+            LiquidityRole::Taker => *contract_spec.fee_taker().as_ref(),
+        };
+        self.config.fee_model().fee_of(quantity, price, fee)
+    }
+
     /// # Arguments:
     /// `order`: The order that is being submitted.
     ///
     /// # Returns:
-    /// If Ok, the order with timestamp and id filled in.
+    /// If Ok, the resting order with timestamp and id filled in,
+    /// or the `Fill` of a marketable order with `RePricing::Marketable`, which is filled immediately.
     /// Else its an error.
     pub fn submit_limit_order(
         &mut self,
-        order: LimitOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
-    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
+        mut order: LimitOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
+    ) -> Result<LimitOrderSubmission<I, D, BaseOrQuote, UserOrderIdT>> {
         trace!("submit_order: {}", order);
 
+        if self.blown_up {
+            return Err(Error::AccountBlownUp);
+        }
+        if self.expired {
+            return Err(Error::ContractExpired);
+        }
+        if !self.market_state.has_market_data() {
+            return Err(Error::NoMarketData);
+        }
         self.order_rate_limiter
             .aquire(self.market_state.current_ts_ns())?;
+        self.count_trade()?;
+        if self.config.validation_mode() == ValidationMode::Lenient {
+            let contract_spec = self.config.contract_spec();
+            order.set_remaining_quantity(
+                contract_spec
+                    .quantity_filter()
+                    .round_order_quantity(order.remaining_quantity()),
+            );
+            order.set_limit_price(contract_spec.price_filter().round_limit_price(
+                order.limit_price(),
+                self.market_state.mid_price(),
+                order.side(),
+            ));
+        }
         // Basic checks
         self.config
             .contract_spec()
@@ -318,6 +1241,8 @@ where
             .contract_spec()
             .price_filter()
             .validate_limit_price(order.limit_price(), self.market_state.mid_price())?;
+        self.check_cooldown(order.side(), order.remaining_quantity())?;
+        self.check_self_cross(order.side(), order.limit_price())?;
 
         let meta = ExchangeOrderMeta::new(
             self.next_order_id(),
@@ -325,6 +1250,15 @@ where
         );
         let order = order.into_pending(meta);
 
+        // If a limit order is marketable, it will take liquidity from the book at the `limit_price` price level and pay the taker fee,
+        let marketable = matches!(
+            self.liquidity_role(order.side(), order.limit_price()),
+            LiquidityRole::Taker
+        );
+        if marketable && matches!(order.re_pricing(), RePricing::Marketable) {
+            return self.fill_marketable_limit_order(order);
+        }
+
         let available_wallet_balance = self
             .transaction_accounting
             .margin_balance_of(USER_WALLET_ACCOUNT)?;
@@ -335,11 +1269,6 @@ where
             &self.order_margin,
         )?;
 
-        // If a limit order is marketable, it will take liquidity from the book at the `limit_price` price level and pay the taker fee,
-        let marketable = match order.side() {
-            Side::Buy => order.limit_price() >= self.market_state.ask(),
-            Side::Sell => order.limit_price() <= self.market_state.bid(),
-        };
         match order.re_pricing() {
             RePricing::GoodTilCrossing => {
                 if marketable {
@@ -354,11 +1283,127 @@ where
                     ));
                 }
             }
+            RePricing::Marketable => {}
         }
 
+        self.cancel_self_crossed_orders(order.side(), order.limit_price());
         self.append_limit_order(order.clone(), marketable)?;
 
-        Ok(order)
+        Ok(LimitOrderSubmission::Resting(order))
+    }
+
+    // Fill a marketable limit order entirely at the away market quotation, capped by its limit price.
+    // The margin requirements are the ones of a market order, as the order takes liquidity.
+    fn fill_marketable_limit_order(
+        &mut self,
+        order: LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>,
+    ) -> Result<LimitOrderSubmission<I, D, BaseOrQuote, UserOrderIdT>> {
+        let fill_price = match order.side() {
+            Side::Buy => order.limit_price().min(self.market_state.ask()),
+            Side::Sell => order.limit_price().max(self.market_state.bid()),
+        };
+        let quantity = order.remaining_quantity();
+        let market_order =
+            MarketOrder::new_with_user_order_id(order.side(), quantity, order.user_order_id())?
+                .into_pending(order.state().meta().clone());
+        let position_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)?;
+        let available_wallet_balance = self
+            .transaction_accounting
+            .margin_balance_of(USER_WALLET_ACCOUNT)?;
+        self.risk_engine.check_market_order(
+            &self.position,
+            position_margin,
+            &market_order,
+            fill_price,
+            available_wallet_balance,
+        )?;
+        self.cancel_self_crossed_orders(order.side(), order.limit_price());
+
+        let fee = self.settle_taker_fill(
+            order.side(),
+            quantity,
+            fill_price,
+            BaseOrQuote::PairedCurrency::zero(),
+        );
+        let filled_market_order =
+            market_order.into_filled(fill_price, self.market_state.current_timestamp_ns());
+
+        Ok(LimitOrderSubmission::Filled(Fill::new(
+            filled_market_order,
+            fee,
+            LiquidityRole::Taker,
+        )))
+    }
+
+    /// Get the `LiquidityRole` a limit order with the given `side` and `limit_price` would have,
+    /// based on the current bid and ask.
+    /// A buy at or above the ask and a sell at or below the bid would take liquidity.
+    pub fn liquidity_role(&self, side: Side, limit_price: QuoteCurrency<I, D>) -> LiquidityRole {
+        let crosses = match side {
+            Side::Buy => limit_price >= self.market_state.ask(),
+            Side::Sell => limit_price <= self.market_state.bid(),
+        };
+        if crosses {
+            LiquidityRole::Taker
+        } else {
+            LiquidityRole::Maker
+        }
+    }
+
+    /// Simulate the resting limit `order` against a `price_path` of market updates, without changing the exchange state.
+    ///
+    /// # Returns:
+    /// The index of the first market update which would (at least partially) fill the order,
+    /// together with the fill price according to the `LimitFillPrice` of the `Config`.
+    /// `None` if the order would never be filled along the path.
+    pub fn would_fill<U>(
+        &self,
+        order: &LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>,
+        price_path: &[U],
+    ) -> Option<(usize, QuoteCurrency<I, D>)>
+    where
+        U: MarketUpdate<I, D, BaseOrQuote>,
+    {
+        if !U::CAN_FILL_LIMIT_ORDERS {
+            return None;
+        }
+        price_path
+            .iter()
+            .position(|market_update| market_update.limit_order_filled(order).is_some())
+            .map(|idx| {
+                let fill_price = self.config.limit_fill_price().fill_price(
+                    order.side(),
+                    order.limit_price(),
+                    price_path[idx].touch_price(order.side()),
+                );
+                (idx, fill_price)
+            })
+    }
+
+    /// How far the current market price has to move until the active limit order with `order_id` would be filled.
+    /// A buy order requires the ask to drop to its limit price and a sell order requires the bid to rise to it.
+    /// There is no queue position model, so an order is assumed to fill once the market reaches its limit price.
+    ///
+    /// # Returns:
+    /// The non-negative price distance, or an error if the order is unknown or no longer active.
+    pub fn order_distance_to_fill(&self, order_id: OrderId) -> Result<QuoteCurrency<I, D>> {
+        let order = self
+            .active_limit_orders
+            .get_by_id(order_id)
+            .ok_or_else(|| {
+                if order_id < self.next_order_id {
+                    Error::OrderNoLongerActive
+                } else {
+                    Error::OrderIdNotFound { order_id }
+                }
+            })?;
+        let distance = match order.side() {
+            Side::Buy => self.market_state.ask() - order.limit_price(),
+            Side::Sell => order.limit_price() - self.market_state.bid(),
+        };
+        Ok(distance.max(QuoteCurrency::zero()))
     }
 
     /// Amend an existing limit order.
@@ -367,11 +1412,12 @@ where
     /// Requests which cannot be processed will be rejected with an error.
     ///
     /// The new order get a new `OrderId` as well.
+    /// Amends are not subject to the `cancel_latency`.
     pub fn amend_limit_order(
         &mut self,
         existing_order_id: OrderId,
         mut new_order: LimitOrder<I, D, BaseOrQuote, UserOrderIdT, NewOrder>,
-    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
+    ) -> Result<LimitOrderSubmission<I, D, BaseOrQuote, UserOrderIdT>> {
         self.order_rate_limiter
             .aquire(self.market_state.current_ts_ns())?;
         let existing_order = self
@@ -398,17 +1444,113 @@ where
         trace!("qty_delta: {qty_delta}");
         let new_leaves_qty = existing_order.remaining_quantity() + qty_delta;
         if new_leaves_qty <= BaseOrQuote::zero() {
-            self.cancel_limit_order(CancelBy::OrderId(existing_order_id))
+            self.cancel_limit_order_immediately(CancelBy::OrderId(existing_order_id))
                 .expect("Can cancel this order");
             return Err(Error::AmendQtyAlreadyFilled);
         }
 
         new_order.set_remaining_quantity(new_leaves_qty);
 
-        self.cancel_limit_order(CancelBy::OrderId(existing_order_id))?;
+        self.cancel_limit_order_immediately(CancelBy::OrderId(existing_order_id))?;
         self.submit_limit_order(new_order)
     }
 
+    /// Amend the `limit_price` and the total `quantity` of an active limit order.
+    ///
+    /// If only the quantity is reduced, the order is amended in place, keeping its `OrderId` and queue priority,
+    /// and the released order margin is returned to the wallet.
+    /// Otherwise the order is replaced with `amend_limit_order`, which assigns a new `OrderId` and resets the queue priority.
+    /// In both cases the change of the total quantity is applied to the remaining quantity,
+    /// and an order without any remaining quantity is cancelled with `Error::AmendQtyAlreadyFilled`.
+    pub fn amend_order(
+        &mut self,
+        existing_order_id: OrderId,
+        new_price: QuoteCurrency<I, D>,
+        new_quantity: BaseOrQuote,
+    ) -> Result<()> {
+        let existing_order = self
+            .active_limit_orders
+            .get_by_id(existing_order_id)
+            .ok_or_else(|| {
+                if existing_order_id < self.next_order_id {
+                    Error::OrderNoLongerActive
+                } else {
+                    Error::OrderIdNotFound {
+                        order_id: existing_order_id,
+                    }
+                }
+            })?;
+        if new_price != existing_order.limit_price()
+            || new_quantity > existing_order.total_quantity()
+        {
+            let mut new_order = LimitOrder::new_with_user_order_id(
+                existing_order.side(),
+                new_price,
+                new_quantity,
+                existing_order.user_order_id(),
+            )?;
+            new_order.set_re_pricing(existing_order.re_pricing());
+            return self
+                .amend_limit_order(existing_order_id, new_order)
+                .map(|_| ());
+        }
+
+        let new_leaves_qty =
+            existing_order.remaining_quantity() + new_quantity - existing_order.total_quantity();
+        self.order_rate_limiter
+            .aquire(self.market_state.current_ts_ns())?;
+        if new_leaves_qty <= BaseOrQuote::zero() {
+            self.cancel_limit_order_immediately(CancelBy::OrderId(existing_order_id))
+                .expect("Can cancel this order");
+            return Err(Error::AmendQtyAlreadyFilled);
+        }
+        let new_leaves_qty = match self.config.validation_mode() {
+            ValidationMode::Lenient => self
+                .config
+                .contract_spec()
+                .quantity_filter()
+                .round_order_quantity(new_leaves_qty),
+            ValidationMode::Strict => new_leaves_qty,
+        };
+        if new_leaves_qty == existing_order.remaining_quantity() {
+            return Ok(());
+        }
+        self.config
+            .contract_spec()
+            .quantity_filter()
+            .validate_order_quantity(new_leaves_qty)?;
+
+        let order_margin = self
+            .transaction_accounting
+            .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
+            .expect("is valid");
+        let order = self
+            .active_limit_orders
+            .get_mut_by_id(existing_order_id)
+            .expect("The order is active");
+        order.set_remaining_quantity(new_leaves_qty);
+        let order = order.clone();
+        self.order_margin.update(&order)?;
+
+        let new_order_margin = self.order_margin.order_margin(
+            self.config.contract_spec().order_init_margin_req(),
+            &self.position,
+        );
+        debug_assert!(new_order_margin <= order_margin);
+        if new_order_margin < order_margin {
+            let transaction = Transaction::new(
+                USER_WALLET_ACCOUNT,
+                USER_ORDER_MARGIN_ACCOUNT,
+                order_margin - new_order_margin,
+            );
+            self.transaction_accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer works.");
+        }
+
+        Ok(())
+    }
+
     /// Append a new limit order as active order.
     /// If limit order is `marketable`, the order will take liquidity from the book at the `limit_price` price level.
     /// Then it pays the taker fee for the quantity that was taken from the book, the rest of the quantity (if any)
@@ -427,7 +1569,7 @@ where
         self.order_margin.update(&order)?;
         self.active_limit_orders.insert(order)?;
         let new_order_margin = self.order_margin.order_margin(
-            self.config.contract_spec().init_margin_req(),
+            self.config.contract_spec().order_init_margin_req(),
             &self.position,
         );
         let order_margin = self
@@ -471,13 +1613,81 @@ where
 
     /// Cancel an active limit order.
     /// returns Some order if successful with given order_id
+    ///
+    /// With a `cancel_latency` configured, the order stays active until the latency elapsed, so it may still be filled.
+    /// The returned order is then its state at the time of the request,
+    /// and the eventual cancellation is reported by `update_state` as `LimitOrderUpdate::Cancelled`.
     pub fn cancel_limit_order(
         &mut self,
         cancel_by: CancelBy<UserOrderIdT>,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
+        let Some(cancel_latency) = self.config.cancel_latency() else {
+            return self.cancel_limit_order_immediately(cancel_by);
+        };
+        trace!(
+            "cancel_order: by {:?} with latency {cancel_latency}",
+            cancel_by
+        );
+        self.order_rate_limiter
+            .aquire(self.market_state.current_ts_ns())?;
+        let order = match cancel_by {
+            CancelBy::OrderId(order_id) => self
+                .active_limit_orders
+                .get_by_id(order_id)
+                .ok_or_else(|| {
+                    if order_id < self.next_order_id {
+                        Error::OrderNoLongerActive
+                    } else {
+                        Error::OrderIdNotFound { order_id }
+                    }
+                })?,
+            CancelBy::UserOrderId(user_order_id) => self
+                .active_limit_orders
+                .get_by_user_order_id(user_order_id)
+                .ok_or(Error::UserOrderIdNotFound)?,
+        }
+        .clone();
+        self.pending_cancels.push((
+            self.market_state.current_timestamp_ns() + cancel_latency,
+            cancel_by,
+        ));
+
+        Ok(order)
+    }
+
+    // Cancel the orders of all `pending_cancels` whose `cancel_latency` elapsed.
+    // Orders which were filled in the meantime are no longer active, so their cancellation has no effect.
+    fn apply_due_cancels(&mut self) {
+        let now = self.market_state.current_timestamp_ns();
+        let mut i = 0;
+        while i < self.pending_cancels.len() {
+            if self.pending_cancels[i].0 > now {
+                i += 1;
+                continue;
+            }
+            let (_, cancel_by) = self.pending_cancels.remove(i);
+            if let Ok(order) = self.remove_limit_order(cancel_by) {
+                self.limit_order_updates
+                    .push(LimitOrderUpdate::Cancelled(order));
+            }
+        }
+    }
+
+    fn cancel_limit_order_immediately(
+        &mut self,
+        cancel_by: CancelBy<UserOrderIdT>,
     ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
         trace!("cancel_order: by {:?}", cancel_by);
         self.order_rate_limiter
             .aquire(self.market_state.current_ts_ns())?;
+        self.remove_limit_order(cancel_by)
+    }
+
+    // Remove an active limit order and release its order margin.
+    fn remove_limit_order(
+        &mut self,
+        cancel_by: CancelBy<UserOrderIdT>,
+    ) -> Result<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
         let order_margin = self
             .transaction_accounting
             .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
@@ -485,7 +1695,7 @@ where
         assert_eq!(
             order_margin,
             self.order_margin.order_margin(
-                self.config.contract_spec().init_margin_req(),
+                self.config.contract_spec().order_init_margin_req(),
                 &self.position,
             )
         );
@@ -508,7 +1718,7 @@ where
         self.order_margin.remove(cancel_by);
 
         let new_order_margin = self.order_margin.order_margin(
-            self.config.contract_spec().init_margin_req(),
+            self.config.contract_spec().order_init_margin_req(),
             &self.position,
         );
 
@@ -562,7 +1772,14 @@ where
     {
         // Clear any potential order updates from the previous iteration.
         self.limit_order_updates.clear();
+        self.fill_active_orders(market_update);
+    }
 
+    // Fills the active limit orders triggered by the `market_update`, appending to the `limit_order_updates`.
+    fn fill_active_orders<U>(&mut self, market_update: &U)
+    where
+        U: MarketUpdate<I, D, BaseOrQuote>,
+    {
         if !U::CAN_FILL_LIMIT_ORDERS {
             return;
         }
@@ -573,7 +1790,14 @@ where
         );
 
         for order in self.active_limit_orders.values_mut() {
-            if let Some(filled_qty) = market_update.limit_order_filled(order) {
+            if let Some(mut filled_qty) = market_update.limit_order_filled(order) {
+                if let Some(ratio) = self.config.max_fill_ratio_per_tick() {
+                    // At least one `tick_size` is filled, so every order makes progress.
+                    let tick_size = self.config.contract_spec().quantity_filter().tick_size();
+                    let max_fill = order.total_quantity() * ratio;
+                    let max_fill = (max_fill - max_fill % tick_size).max(tick_size);
+                    filled_qty = filled_qty.min(max_fill);
+                }
                 trace!(
                     "filled limit {} order {}: {filled_qty}/{} @ {}",
                     order.side(),
@@ -593,14 +1817,21 @@ where
                 debug_assert_eq!(
                     order_margin,
                     self.order_margin.order_margin(
-                        self.config.contract_spec().init_margin_req(),
+                        self.config.contract_spec().order_init_margin_req(),
                         &self.position
                     )
                 );
 
-                if let Some(filled_order) =
-                    order.fill(filled_qty, market_update.timestamp_exchange_ns())
-                {
+                let fill_price = self.config.limit_fill_price().fill_price(
+                    order.side(),
+                    order.limit_price(),
+                    market_update.touch_price(order.side()),
+                );
+                if let Some(filled_order) = order.fill_at(
+                    filled_qty,
+                    fill_price,
+                    market_update.timestamp_exchange_ns(),
+                ) {
                     self.ids_to_remove.push(order.state().meta().id());
                     self.order_margin.remove(CancelBy::OrderId(order.id()));
                     self.limit_order_updates
@@ -614,20 +1845,43 @@ where
                         .expect("Can update an existing order");
                 }
 
-                let value =
-                    BaseOrQuote::PairedCurrency::convert_from(filled_qty, order.limit_price());
-                let fees = value * *self.config.contract_spec().fee_maker().as_ref();
+                let total_fees = self.config.fee_model().fee_of(
+                    filled_qty,
+                    fill_price,
+                    *self.config.contract_spec().fee_maker().as_ref(),
+                );
+                let fees = Self::charge_fees(
+                    self.config.fee_mode(),
+                    &mut self.transaction_accounting,
+                    total_fees,
+                );
+                let was_long = matches!(self.position, Position::Long(_));
+                let was_short = matches!(self.position, Position::Short(_));
+                self.maintenance_mark_price = None;
+                self.trade_blotter.record_fill(
+                    &self.position,
+                    order.side(),
+                    filled_qty,
+                    fill_price,
+                    total_fees,
+                    market_update.timestamp_exchange_ns(),
+                );
                 self.position.change_position(
                     filled_qty,
-                    order.limit_price(),
+                    fill_price,
                     order.side(),
                     &mut self.transaction_accounting,
-                    self.config.contract_spec().init_margin_req(),
+                    self.config.contract_spec(),
                     fees,
                 );
+                self.position
+                    .add_entry_fees(order.side(), filled_qty, total_fees);
+                if Self::is_position_closed(was_long, was_short, &self.position) {
+                    self.last_close_ts_ns = Some(market_update.timestamp_exchange_ns());
+                }
 
                 let new_order_margin = self.order_margin.order_margin(
-                    self.config.contract_spec().init_margin_req(),
+                    self.config.contract_spec().order_init_margin_req(),
                     &self.position,
                 );
                 debug_assert!(
@@ -671,10 +1925,12 @@ where
                 .margin_balance_of(USER_ORDER_MARGIN_ACCOUNT)
                 .expect("is valid"),
             self.order_margin.order_margin(
-                self.config.contract_spec().init_margin_req(),
+                self.config.contract_spec().order_init_margin_req(),
                 &self.position
             )
         );
+        self.debug_assert_position_margin();
+        self.debug_assert_pnl_conservation();
         assert_user_wallet_balance(&self.transaction_accounting);
     }
 
@@ -10,6 +10,7 @@ mod accounting;
 mod active_limit_orders;
 mod config;
 mod contract_specification;
+mod event_recorder;
 mod exchange;
 mod load_trades_from_csv;
 mod market_state;
@@ -21,6 +22,7 @@ mod order_rate_limiter;
 mod position;
 mod position_inner;
 mod risk_engine;
+mod scripted_strategy;
 #[cfg(test)]
 mod tests;
 #[cfg(feature = "trade_aggregation")]
@@ -42,13 +44,19 @@ pub mod prelude {
         active_limit_orders::ActiveLimitOrders,
         config::Config,
         contract_specification::*,
+        event_recorder::{Event, EventRecorder, replay},
         exchange::{Account, CancelBy, Exchange},
         leverage,
         market_state::MarketState,
         market_update::*,
-        order_filters::{PriceFilter, QuantityFilter},
+        order_filters::{PriceFilter, QuantityFilter, TickRounding},
+        order_margin::OrderMargin,
         position::Position,
         position_inner::PositionInner,
+        risk_engine::{
+            FlatMaintenanceMargin, IsolatedMarginRiskEngine, MaintenanceMarginModel, RiskEngine,
+        },
+        scripted_strategy::ScriptedStrategy,
         types::*,
         utils::{NoUserOrderId, decimal_from_f64},
     };
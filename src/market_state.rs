@@ -1,9 +1,11 @@
 use const_decimal::Decimal;
 use getset::{CopyGetters, Getters, Setters};
+use num_traits::{Signed, Zero};
+use tracing::warn;
 
 use crate::{
     prelude::{Currency, MarketUpdate, Mon, PriceFilter, QuoteCurrency},
-    types::{Result, TimestampNs},
+    types::{CrossedMarketPolicy, Error, PriceSource, Result, Side, TimestampNs},
 };
 
 /// Some information regarding the state of the market.
@@ -16,17 +18,25 @@ where
     I: Mon<D>,
 {
     /// The current bid
-    #[getset(get_copy = "pub", set = "pub(crate)")]
+    #[getset(get_copy = "pub")]
     bid: QuoteCurrency<I, D>,
 
     /// The current ask
-    #[getset(get_copy = "pub", set = "pub(crate)")]
+    #[getset(get_copy = "pub")]
     ask: QuoteCurrency<I, D>,
 
+    /// The timestamp at which the bid or ask was last updated.
+    #[getset(get_copy = "pub")]
+    bba_ts_ns: TimestampNs,
+
     /// The last trade price.
-    #[getset(get_copy = "pub", set = "pub")]
+    #[getset(get_copy = "pub")]
     last_trade_price: QuoteCurrency<I, D>,
 
+    /// The timestamp at which the last trade price was last updated.
+    #[getset(get_copy = "pub")]
+    last_trade_ts_ns: TimestampNs,
+
     /// The current timestamp in nanoseconds
     #[getset(get_copy = "pub")]
     current_ts_ns: TimestampNs,
@@ -34,6 +44,16 @@ where
     /// Used for synchronizing orders.
     #[getset(get_copy = "pub")]
     step: u64,
+
+    /// Replaces the mark price of every `MarkMethod` while set, e.g for stress testing liquidations.
+    #[getset(get_copy = "pub", set = "pub(crate)")]
+    mark_price_override: Option<QuoteCurrency<I, D>>,
+
+    // The mid price of the last market update checked against the `max_price_move_pct`, even if it was rejected.
+    last_received_mid_price: QuoteCurrency<I, D>,
+
+    // The last trade price of the last market update checked against the `max_price_move_pct`, even if it was rejected.
+    last_received_trade_price: QuoteCurrency<I, D>,
 }
 
 impl<I: Mon<D>, const D: u8> std::fmt::Display for MarketState<I, D> {
@@ -55,32 +75,185 @@ where
     /// ### Parameters:
     /// `market_update`: Newest market information
     /// `price_filter`: The pricing rules.
+    /// `crossed_market_policy`: How a bid at or above the ask is handled.
+    /// `max_price_move_pct`: The maximum move of the mid or last trade price in percent, beyond which the update is rejected.
     ///
     pub(crate) fn update_state<U, BaseOrQuote>(
         &mut self,
         market_update: &U,
         price_filter: &PriceFilter<I, D>,
+        crossed_market_policy: CrossedMarketPolicy,
+        max_price_move_pct: Option<Decimal<I, D>>,
     ) -> Result<()>
     where
         U: MarketUpdate<I, D, BaseOrQuote>,
         BaseOrQuote: Currency<I, D>,
     {
-        // Only in debug mode do we care to validate the market update, because usually the update comes from an exchange source.
-        debug_assert!(market_update.validate_market_update(price_filter).is_ok());
+        let (prev_bid, prev_ask, prev_bba_ts_ns, prev_ts_ns) =
+            (self.bid, self.ask, self.bba_ts_ns, self.current_ts_ns);
+        let (prev_last_trade_price, prev_last_trade_ts_ns) =
+            (self.last_trade_price, self.last_trade_ts_ns);
+        let prev_mid_price = self.mid_price();
+        // The setters stamp the prices they update with the timestamp of the market update.
+        self.current_ts_ns = market_update.timestamp_exchange_ns();
         market_update.update_market_state(self);
+        // Market updates which don't provide a bid and ask, e.g `Trade`, leave them untouched.
+        let quotes_changed = self.bid != prev_bid || self.ask != prev_ask;
+        if quotes_changed && self.bid >= self.ask {
+            match crossed_market_policy {
+                CrossedMarketPolicy::Reject => {
+                    self.bid = prev_bid;
+                    self.ask = prev_ask;
+                    self.bba_ts_ns = prev_bba_ts_ns;
+                    self.current_ts_ns = prev_ts_ns;
+                    return Err(Error::CrossedMarket);
+                }
+                CrossedMarketPolicy::Clamp => {
+                    let tick_size = price_filter.tick_size();
+                    let mid_price = (self.bid + self.ask) / Decimal::TWO;
+                    self.bid = mid_price.quantize_round_to_zero(tick_size);
+                    self.ask = self.bid + tick_size;
+                }
+            }
+        } else {
+            // Only in debug mode do we care to validate the market update, because usually the update comes from an exchange source.
+            debug_assert!(market_update.validate_market_update(price_filter).is_ok());
+        }
+        if let Some(max_move_pct) = max_price_move_pct {
+            let mid_price = self.mid_price();
+            let last_received_mid_price =
+                std::mem::replace(&mut self.last_received_mid_price, mid_price);
+            let last_received_trade_price =
+                std::mem::replace(&mut self.last_received_trade_price, self.last_trade_price);
+            // A bad `Trade` print would otherwise fill resting orders and move the `LastTrade` mark price.
+            if !Self::is_within_price_move_cap(
+                mid_price,
+                prev_mid_price,
+                last_received_mid_price,
+                max_move_pct,
+            ) || !Self::is_within_price_move_cap(
+                self.last_trade_price,
+                prev_last_trade_price,
+                last_received_trade_price,
+                max_move_pct,
+            ) {
+                warn!(
+                    "rejecting market update {market_update}: mid price moved from {prev_mid_price} to {mid_price}, last trade price moved from {prev_last_trade_price} to {}",
+                    self.last_trade_price
+                );
+                self.bid = prev_bid;
+                self.ask = prev_ask;
+                self.bba_ts_ns = prev_bba_ts_ns;
+                self.last_trade_price = prev_last_trade_price;
+                self.last_trade_ts_ns = prev_last_trade_ts_ns;
+                self.current_ts_ns = prev_ts_ns;
+                return Err(Error::PriceMoveTooLarge);
+            }
+        }
 
-        self.current_ts_ns = market_update.timestamp_exchange_ns();
         self.step += 1;
 
         Ok(())
     }
 
+    // Whether the `price` moved by at most `max_move_pct` percent from either the `prev_price` of the last
+    // accepted market update or the `last_received_price` of the last received one.
+    // A single bad print is rejected, while a persistent move to a new level is accepted with its second market update.
+    fn is_within_price_move_cap(
+        price: QuoteCurrency<I, D>,
+        prev_price: QuoteCurrency<I, D>,
+        last_received_price: QuoteCurrency<I, D>,
+        max_move_pct: Decimal<I, D>,
+    ) -> bool {
+        let hundred = Decimal::try_from_scaled(I::from(100).expect("can convert"), 0)
+            .expect("can create decimal");
+        let is_within = |reference: QuoteCurrency<I, D>| {
+            (price - reference).abs() <= reference * max_move_pct / hundred
+        };
+        prev_price <= QuoteCurrency::zero()
+            || is_within(prev_price)
+            || (last_received_price > QuoteCurrency::zero() && is_within(last_received_price))
+    }
+
+    /// Set the current bid, stamped with the current timestamp.
+    #[inline]
+    pub(crate) fn set_bid(&mut self, bid: QuoteCurrency<I, D>) -> &mut Self {
+        self.bid = bid;
+        self.bba_ts_ns = self.current_ts_ns;
+        self
+    }
+
+    /// Set the current ask, stamped with the current timestamp.
+    #[inline]
+    pub(crate) fn set_ask(&mut self, ask: QuoteCurrency<I, D>) -> &mut Self {
+        self.ask = ask;
+        self.bba_ts_ns = self.current_ts_ns;
+        self
+    }
+
+    /// Set the last trade price, stamped with the current timestamp.
+    #[inline]
+    pub fn set_last_trade_price(&mut self, last_trade_price: QuoteCurrency<I, D>) -> &mut Self {
+        self.last_trade_price = last_trade_price;
+        self.last_trade_ts_ns = self.current_ts_ns;
+        self
+    }
+
+    /// The price of `source` for a position on `side`.
+    /// `None` if the price was never observed or, with a `max_age`, was last updated longer than `max_age` ago.
+    pub fn price_of(
+        &self,
+        source: PriceSource,
+        side: Side,
+        max_age: Option<TimestampNs>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        let (price, ts_ns) = match source {
+            PriceSource::LastTrade => (self.last_trade_price, self.last_trade_ts_ns),
+            PriceSource::MidPrice if self.bid.is_zero() || self.ask.is_zero() => return None,
+            PriceSource::MidPrice => (self.mid_price(), self.bba_ts_ns),
+            PriceSource::BidAsk => match side {
+                Side::Buy => (self.bid, self.bba_ts_ns),
+                Side::Sell => (self.ask, self.bba_ts_ns),
+            },
+        };
+        if price.is_zero() || max_age.is_some_and(|max_age| self.current_ts_ns - ts_ns > max_age) {
+            return None;
+        }
+        Some(price)
+    }
+
+    /// The price of the first of `sources` which is neither missing nor stale, see `price_of`.
+    /// If all of them are stale, the first one which was observed at all is used,
+    /// and `None` if none of them was ever observed.
+    pub fn fallback_price(
+        &self,
+        sources: &[PriceSource],
+        side: Side,
+        max_age: Option<TimestampNs>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        sources
+            .iter()
+            .find_map(|source| self.price_of(*source, side, max_age))
+            .or_else(|| {
+                sources
+                    .iter()
+                    .find_map(|source| self.price_of(*source, side, None))
+            })
+    }
+
     /// Get the mid price
     #[inline(always)]
     pub fn mid_price(&self) -> QuoteCurrency<I, D> {
         (self.bid + self.ask) / Decimal::TWO
     }
 
+    /// Whether any market information is available,
+    /// either from a processed market update or from setting the best bid and ask directly.
+    #[inline(always)]
+    pub fn has_market_data(&self) -> bool {
+        self.step > 0 || self.bid > QuoteCurrency::zero()
+    }
+
     /// Get the last observed timestamp in nanoseconts
     #[inline(always)]
     pub fn current_timestamp_ns(&self) -> TimestampNs {
@@ -99,9 +272,14 @@ where
         Self {
             bid,
             ask,
+            bba_ts_ns: current_ts_ns,
             last_trade_price,
+            last_trade_ts_ns: current_ts_ns,
             current_ts_ns,
             step,
+            mark_price_override: None,
+            last_received_mid_price: QuoteCurrency::zero(),
+            last_received_trade_price: QuoteCurrency::zero(),
         }
     }
 }
@@ -132,8 +310,45 @@ mod test {
                     timestamp_exchange_ns: 1.into(),
                 },
                 &pf,
+                CrossedMarketPolicy::default(),
+                None,
             )
             .unwrap();
         assert_eq!(state.mid_price(), QuoteCurrency::new(1005, 1));
     }
+
+    #[test]
+    fn market_state_price_of() {
+        let state = MarketState::<i64, 1>::from_components(
+            QuoteCurrency::new(100, 0),
+            QuoteCurrency::new(102, 0),
+            QuoteCurrency::zero(),
+            5.into(),
+            1,
+        );
+        assert_eq!(
+            state.price_of(PriceSource::LastTrade, Side::Buy, None),
+            None
+        );
+        assert_eq!(
+            state.price_of(PriceSource::BidAsk, Side::Buy, Some(0.into())),
+            Some(QuoteCurrency::new(100, 0))
+        );
+        assert_eq!(
+            state.price_of(PriceSource::BidAsk, Side::Sell, None),
+            Some(QuoteCurrency::new(102, 0))
+        );
+        assert_eq!(
+            state.fallback_price(
+                &[PriceSource::LastTrade, PriceSource::MidPrice],
+                Side::Buy,
+                None
+            ),
+            Some(QuoteCurrency::new(101, 0))
+        );
+        assert_eq!(
+            state.fallback_price(&[PriceSource::LastTrade], Side::Buy, None),
+            None
+        );
+    }
 }
@@ -13,7 +13,7 @@ use crate::{
 /// An update to the best bid and ask has occured.
 /// For now we don't handle the quantity a these price levels.
 /// This will change in future versions.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Bba<I, const D: u8>
 where
     I: Mon<D>,
@@ -14,7 +14,7 @@ use crate::{
 /// A new candle has been created.
 /// Here we can use the `high` and `low` prices to see if our simulated resting orders
 /// have been executed over the last period as a proxy in absence of actual `Trade` flow.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, TypedBuilder, CopyGetters)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, TypedBuilder, CopyGetters, Serialize, Deserialize)]
 pub struct Candle<I, const D: u8>
 where
     I: Mon<D>,
@@ -132,6 +132,15 @@ where
         }
     }
 
+    // The candle has no open price, so its extremes are the most favorable prices that were touched.
+    #[inline]
+    fn touch_price(&self, side: Side) -> Option<QuoteCurrency<I, D>> {
+        Some(match side {
+            Side::Buy => self.low,
+            Side::Sell => self.high,
+        })
+    }
+
     fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()> {
         enforce_min_price(price_filter.min_price(), self.bid)?;
         enforce_min_price(price_filter.min_price(), self.ask)?;
@@ -1,6 +1,6 @@
 use crate::{
     Result,
-    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter},
+    prelude::{Currency, LimitOrder, MarketState, Mon, Pending, PriceFilter, QuoteCurrency, Side},
     types::{TimestampNs, UserOrderId},
 };
 
@@ -20,6 +20,14 @@ where
         limit_order: &LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>,
     ) -> Option<BaseOrQuote>;
 
+    /// The most favorable price at which this market update trades against a limit order on `side`,
+    /// used by `LimitFillPrice::FavorableTouch`.
+    /// Defaults to `None`, in which case limit orders are filled at their limit price.
+    #[inline]
+    fn touch_price(&self, _side: Side) -> Option<QuoteCurrency<I, D>> {
+        None
+    }
+
     /// Checks if the market update satisfies the `PriceFilter`.
     fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()>;
 
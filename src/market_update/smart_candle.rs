@@ -9,7 +9,7 @@ use crate::{
 
 /// A datastructure for aggregated trades with the ability to approximate realistic taker fill flow.
 /// Basically a `Candle` buy one that does not blindly fill active limit orders with taker flow that does not exist.
-#[derive(Debug, Clone, Eq, PartialEq, Getters, CopyGetters)]
+#[derive(Debug, Clone, Eq, PartialEq, Getters, CopyGetters, Serialize, Deserialize)]
 pub struct SmartCandle<I, const D: u8, BaseOrQuote>
 where
     I: Mon<D>,
@@ -178,6 +178,14 @@ where
         }
     }
 
+    #[inline]
+    fn touch_price(&self, side: Side) -> Option<QuoteCurrency<I, D>> {
+        Some(match side {
+            Side::Buy => self.low,
+            Side::Sell => self.high,
+        })
+    }
+
     #[inline(always)]
     fn validate_market_update(
         &self,
@@ -10,7 +10,7 @@ use crate::{
 };
 
 /// A taker trade that consumes liquidity in the book.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Trade<I, const D: u8, BaseOrQuote>
 where
     I: Mon<D>,
@@ -89,6 +89,11 @@ where
         }
     }
 
+    #[inline]
+    fn touch_price(&self, _side: Side) -> Option<QuoteCurrency<I, D>> {
+        Some(self.price)
+    }
+
     fn validate_market_update(&self, price_filter: &PriceFilter<I, D>) -> Result<()> {
         debug_assert!(self.price > QuoteCurrency::zero());
         enforce_min_price(price_filter.min_price(), self.price)?;
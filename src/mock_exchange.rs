@@ -17,17 +17,10 @@ pub fn test_fee_taker() -> Fee<i64, DECIMALS, Taker> {
     Fee::from(Decimal::try_from_scaled(6, 4).unwrap())
 }
 
-/// Constructs a mock exchange (for linear futures) for testing.
-/// The size is denoted in `BaseCurrency`
-/// and the margin currency is `QuoteCurency`
-pub fn mock_exchange_linear() -> Exchange<
-    i64,
-    DECIMALS,
-    BaseCurrency<i64, DECIMALS>,
-    NoUserOrderId,
-    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
-> {
-    let contract_spec = ContractSpecification::new(
+/// The contract specification of `mock_exchange_linear`, to change a single option of it in tests.
+pub fn mock_contract_spec_linear()
+-> ContractSpecification<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    ContractSpecification::new(
         leverage!(1),
         Decimal::try_from_scaled(5, 1).unwrap(),
         PriceFilter::default(),
@@ -35,17 +28,56 @@ pub fn mock_exchange_linear() -> Exchange<
         test_fee_maker(),
         test_fee_taker(),
     )
-    .expect("works");
-    let config = Config::new(
+    .expect("works")
+}
+
+/// The config of `mock_exchange_linear`, to change a single option of it in tests.
+pub fn mock_config_linear() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    Config::new(
         QuoteCurrency::new(1000, 0),
         10,
-        contract_spec,
+        mock_contract_spec_linear(),
         OrderRateLimits::default(),
     )
-    .unwrap();
+    .unwrap()
+}
+
+/// Constructs a mock exchange (for linear futures) for testing.
+/// The size is denoted in `BaseCurrency`
+/// and the margin currency is `QuoteCurency`
+pub fn mock_exchange_linear() -> Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    NoUserOrderId,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+> {
+    Exchange::new(mock_config_linear())
+}
+
+/// Constructs a mock exchange (for linear futures) for testing from a `config`,
+/// e.g the `mock_config_linear` with a single option changed.
+pub fn mock_exchange_linear_with_config(
+    config: Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+) -> Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    NoUserOrderId,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+> {
     Exchange::new(config)
 }
 
+/// The best bid and ask at `timestamp_exchange_ns`, in whole units of `QuoteCurrency`.
+pub fn test_bba(bid: i64, ask: i64, timestamp_exchange_ns: i64) -> Bba<i64, DECIMALS> {
+    Bba {
+        bid: QuoteCurrency::new(bid, 0),
+        ask: QuoteCurrency::new(ask, 0),
+        timestamp_exchange_ns: timestamp_exchange_ns.into(),
+    }
+}
+
 /// Constructs a mock exchange (for linear futures) for testing.
 /// The size is denoted in `BaseCurrency`
 /// and the margin currency is `QuoteCurency`
@@ -58,15 +90,7 @@ pub fn mock_exchange_linear_with_account_tracker(
     NoUserOrderId,
     InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
 > {
-    let contract_spec = ContractSpecification::new(
-        leverage!(1),
-        Decimal::try_from_scaled(5, 1).unwrap(),
-        PriceFilter::default(),
-        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
-        test_fee_maker(),
-        test_fee_taker(),
-    )
-    .expect("works");
+    let contract_spec = mock_contract_spec_linear();
     let config = Config::new(
         starting_balance,
         200,
@@ -1,8 +1,20 @@
 use const_decimal::Decimal;
 use getset::CopyGetters;
-use num_traits::{One, Zero};
-
-use crate::prelude::{ConfigError, FilterError, Mon, OrderError, QuoteCurrency};
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::prelude::{ConfigError, FilterError, Mon, OrderError, QuoteCurrency, Side};
+
+/// How to round a price which is not exactly on a tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TickRounding {
+    /// Round down to the next lower tick.
+    #[default]
+    Down,
+    /// Round up to the next higher tick.
+    Up,
+    /// Round to the nearest tick, with half a tick rounding up.
+    Nearest,
+}
 
 /// The `PriceFilter` defines the price rules for a symbol
 #[derive(Debug, Clone, CopyGetters)]
@@ -112,6 +124,42 @@ where
         })
     }
 
+    /// Convert a positive `price` into the number of ticks of size `tick_size`.
+    /// Prices which are not exactly on a tick are rounded according to `rounding`.
+    /// Returns `None` if the number of ticks does not fit into an `i64`.
+    pub fn price_to_ticks(
+        &self,
+        price: QuoteCurrency<I, D>,
+        rounding: TickRounding,
+    ) -> Option<i64> {
+        debug_assert!(price >= QuoteCurrency::zero());
+        let remainder = price % self.tick_size;
+        let on_tick = price - remainder;
+        let price = if remainder.is_zero() {
+            price
+        } else {
+            match rounding {
+                TickRounding::Down => on_tick,
+                TickRounding::Up => on_tick + self.tick_size,
+                TickRounding::Nearest => {
+                    if remainder + remainder >= self.tick_size {
+                        on_tick + self.tick_size
+                    } else {
+                        on_tick
+                    }
+                }
+            }
+        };
+        // Both are scaled by the same precision and the price is on a tick, so the integer division is exact.
+        (price.as_ref().0 / self.tick_size.as_ref().0).to_i64()
+    }
+
+    /// Convert a number of `ticks` of size `tick_size` into a price.
+    /// Returns `None` if the number of ticks does not fit into `I`.
+    pub fn ticks_to_price(&self, ticks: i64) -> Option<QuoteCurrency<I, D>> {
+        Decimal::try_from_scaled(I::from(ticks)?, 0).map(|ticks| self.tick_size * ticks)
+    }
+
     /// check if an `Order` is valid
     pub fn validate_limit_price(
         &self,
@@ -132,6 +180,43 @@ where
         }
         Ok(())
     }
+
+    /// Round the `limit_price` of an order on `side` to a valid price by clamping it to the allowed range
+    /// and rounding it to a tick, down for buys and up for sells unless that leaves the allowed range,
+    /// so the order does not become more aggressive.
+    pub(crate) fn round_limit_price(
+        &self,
+        limit_price: QuoteCurrency<I, D>,
+        mark_price: QuoteCurrency<I, D>,
+        side: Side,
+    ) -> QuoteCurrency<I, D> {
+        let mut lower = self.min_price.unwrap_or(self.tick_size);
+        if self.multiplier_down != Decimal::zero() {
+            lower = lower.max(mark_price * self.multiplier_down);
+        }
+        let mut upper = self.max_price;
+        if self.multiplier_up != Decimal::zero() {
+            let max_price = mark_price * self.multiplier_up;
+            upper = Some(upper.map_or(max_price, |upper| upper.min(max_price)));
+        }
+
+        let mut price = limit_price.max(lower);
+        if let Some(upper) = upper {
+            price = price.min(upper);
+        }
+        let remainder = price % self.tick_size;
+        if remainder.is_zero() {
+            return price;
+        }
+        let tick_below = price - remainder;
+        let tick_above = tick_below + self.tick_size;
+        match side {
+            Side::Buy if tick_below >= lower => tick_below,
+            Side::Buy => tick_above,
+            Side::Sell if upper.is_none_or(|upper| tick_above <= upper) => tick_above,
+            Side::Sell => tick_below,
+        }
+    }
 }
 
 /// Errors if there is no bid-ask spread
@@ -366,6 +451,86 @@ mod tests {
         );
     }
 
+    #[test_case::test_matrix([1, 2, 999, 100_000])]
+    fn price_filter_ticks_round_trip(ticks: i64) {
+        let filter = PriceFilter::<i64, 5>::new(
+            None,
+            None,
+            QuoteCurrency::new(5, 1),
+            Decimal::TWO,
+            Decimal::zero(),
+        )
+        .unwrap();
+        let price = filter.ticks_to_price(ticks).unwrap();
+        assert_eq!(price, QuoteCurrency::new(ticks * 5, 1));
+        for rounding in [TickRounding::Down, TickRounding::Up, TickRounding::Nearest] {
+            assert_eq!(filter.price_to_ticks(price, rounding), Some(ticks));
+        }
+    }
+
+    #[test]
+    fn price_filter_ticks_out_of_range() {
+        let filter = PriceFilter::<i32, 2>::new(
+            None,
+            None,
+            QuoteCurrency::new(1, 2),
+            Decimal::TWO,
+            Decimal::zero(),
+        )
+        .unwrap();
+        assert_eq!(filter.ticks_to_price(i64::MAX), None);
+        assert_eq!(
+            filter.price_to_ticks(QuoteCurrency::new(i32::MAX, 2), TickRounding::Down),
+            Some(i32::MAX as i64)
+        );
+    }
+
+    #[test_case::test_case(QuoteCurrency::new(1001, 1), TickRounding::Down, 200)]
+    #[test_case::test_case(QuoteCurrency::new(1001, 1), TickRounding::Up, 201)]
+    #[test_case::test_case(QuoteCurrency::new(1001, 1), TickRounding::Nearest, 200)]
+    #[test_case::test_case(QuoteCurrency::new(10025, 2), TickRounding::Nearest, 201)]
+    #[test_case::test_case(QuoteCurrency::new(1004, 1), TickRounding::Nearest, 201)]
+    #[test_case::test_case(QuoteCurrency::new(1004, 1), TickRounding::Down, 200)]
+    fn price_filter_price_to_ticks_rounding(
+        price: QuoteCurrency<i64, 5>,
+        rounding: TickRounding,
+        expected_ticks: i64,
+    ) {
+        let filter = PriceFilter::<i64, 5>::new(
+            None,
+            None,
+            QuoteCurrency::new(5, 1),
+            Decimal::TWO,
+            Decimal::zero(),
+        )
+        .unwrap();
+        assert_eq!(filter.price_to_ticks(price, rounding), Some(expected_ticks));
+    }
+
+    #[test_case::test_case(QuoteCurrency::new(1002, 1), Side::Buy, QuoteCurrency::new(100, 0))]
+    #[test_case::test_case(QuoteCurrency::new(1002, 1), Side::Sell, QuoteCurrency::new(1005, 1))]
+    #[test_case::test_case(QuoteCurrency::new(1005, 1), Side::Buy, QuoteCurrency::new(1005, 1))]
+    #[test_case::test_case(QuoteCurrency::new(1, 1), Side::Buy, QuoteCurrency::new(5, 1))]
+    #[test_case::test_case(QuoteCurrency::new(300, 0), Side::Sell, QuoteCurrency::new(200, 0))]
+    fn price_filter_round_limit_price(
+        limit_price: QuoteCurrency<i64, 5>,
+        side: Side,
+        expected: QuoteCurrency<i64, 5>,
+    ) {
+        let filter = PriceFilter::<i64, 5>::new(
+            None,
+            None,
+            QuoteCurrency::new(5, 1),
+            Decimal::TWO,
+            Decimal::zero(),
+        )
+        .unwrap();
+        assert_eq!(
+            filter.round_limit_price(limit_price, QuoteCurrency::new(100, 0), side),
+            expected
+        );
+    }
+
     #[test]
     fn size_of_price_filter() {
         assert_eq!(std::mem::size_of::<PriceFilter<i64, 5>>(), 56);
@@ -100,6 +100,22 @@ where
         }
         Ok(())
     }
+
+    /// Round a positive `quantity` to a valid order quantity by clamping it to the `min_quantity` and `max_quantity`
+    /// and rounding it toward zero to the closest step of `tick_size`, but never down to zero.
+    pub(crate) fn round_order_quantity(&self, quantity: BaseOrQuote) -> BaseOrQuote {
+        debug_assert!(quantity > BaseOrQuote::zero());
+        let min_qty = self.min_quantity.unwrap_or(BaseOrQuote::zero());
+        let mut quantity = quantity.max(min_qty);
+        if let Some(max_qty) = self.max_quantity {
+            quantity = quantity.min(max_qty);
+        }
+        let quantity = quantity - (quantity - min_qty) % self.tick_size;
+        if quantity.is_zero() {
+            return self.tick_size;
+        }
+        quantity
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +177,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn quantity_filter_round_order_quantity() {
+        let filter = QuantityFilter::new(
+            Some(QuoteCurrency::<i32, 2>::new(10, 0)),
+            Some(QuoteCurrency::new(1000, 0)),
+            QuoteCurrency::new(5, 1),
+        )
+        .unwrap();
+        assert_eq!(
+            filter.round_order_quantity(QuoteCurrency::new(5, 0)),
+            QuoteCurrency::new(10, 0)
+        );
+        assert_eq!(
+            filter.round_order_quantity(QuoteCurrency::new(5075, 2)),
+            QuoteCurrency::new(505, 1)
+        );
+        assert_eq!(
+            filter.round_order_quantity(QuoteCurrency::new(5000, 0)),
+            QuoteCurrency::new(1000, 0)
+        );
+
+        let filter = QuantityFilter::new(None, None, QuoteCurrency::<i32, 2>::one()).unwrap();
+        assert_eq!(
+            filter.round_order_quantity(QuoteCurrency::new(5, 1)),
+            QuoteCurrency::one()
+        );
+    }
+
     #[test]
     fn size_of_quantity_filter() {
         assert_eq!(
@@ -15,7 +15,7 @@ use crate::{
 
 /// An implementation for computing the order margin online, aka with every change to the active orders.
 #[derive(Debug, Clone, CopyGetters, Getters)]
-pub(crate) struct OrderMargin<I, const D: u8, BaseOrQuote, UserOrderIdT>
+pub struct OrderMargin<I, const D: u8, BaseOrQuote, UserOrderIdT>
 where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
@@ -87,7 +87,7 @@ where
     }
 
     /// The margin requirement for all the tracked orders.
-    pub(crate) fn order_margin(
+    pub fn order_margin(
         &self,
         init_margin_req: Decimal<I, D>,
         position: &Position<I, D, BaseOrQuote>,
@@ -189,7 +189,7 @@ where
     }
 
     /// Get the order margin if a new order were to be added.
-    pub(crate) fn order_margin_with_order(
+    pub fn order_margin_with_order(
         &self,
         order: &LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>,
         init_margin_req: Decimal<I, D>,
@@ -1,53 +1,55 @@
-use crate::types::{Error, TimestampNs};
+use crate::types::{Error, OrderRateLimits, TimestampNs};
 
-/// Limits the rate at which limit orders can be submitted.
-/// Operates on buckets measured in seconds.
+/// Limits the rate at which order actions can be submitted with a token bucket.
+/// The bucket holds up to `capacity` permits and refills evenly over the `window_ns`,
+/// so a burst of `capacity` actions is allowed, after which the rate is throttled.
 #[derive(Clone, Debug)]
 pub(crate) struct OrderRateLimiter {
-    /// The start of the rate limiting bucket in seconds.
-    bucket_start_ns: TimestampNs,
-    /// The maximum number of order actions per second.
-    orders_per_second: u16,
-    /// The number of remaining order actions that can be submitted during the period.
+    /// The maximum number of permits in the bucket.
+    capacity: u16,
+    /// The time it takes to refill the empty bucket.
+    window_ns: TimestampNs,
+    /// The number of permits left in the bucket.
     remaining: u16,
+    /// The timestamp up to which refilled permits have been accounted for.
+    last_refill_ns: TimestampNs,
 }
 
 impl OrderRateLimiter {
-    pub(crate) fn new(orders_per_second: u16) -> Self {
+    pub(crate) fn new(limits: OrderRateLimits) -> Self {
         Self {
-            bucket_start_ns: 0.into(),
-            orders_per_second,
-            remaining: orders_per_second,
+            capacity: limits.orders_per_window(),
+            window_ns: limits.window_ns(),
+            remaining: limits.orders_per_window(),
+            last_refill_ns: 0.into(),
         }
     }
 
-    /// If `true`, the `current_ts_ns` falls within the current bucket.
+    /// Add the permits which refilled since the last refill, up to the `capacity`.
     #[inline(always)]
-    fn is_in_bucket(&self, current_ts_ns: TimestampNs) -> bool {
-        debug_assert!(
-            current_ts_ns >= self.bucket_start_ns,
-            "Timestamps are assumed to always increment. Here we don't additionally check for the lower bound of the bucket."
-        );
-        let bucket_end_ts_ns = self.bucket_start_ns + crate::types::NANOS_PER_SECOND.into();
-        current_ts_ns < bucket_end_ts_ns
-    }
-
-    /// Set the new bucket start timestamp by rounding to the nearest second.
-    #[inline(always)]
-    fn new_bucket(&mut self, current_ts_ns: TimestampNs) {
-        self.bucket_start_ns = current_ts_ns.floor_to_nearest_second();
-        self.remaining = self.orders_per_second;
+    fn refill(&mut self, current_ts_ns: TimestampNs) {
+        let elapsed_ns = i64::from(current_ts_ns - self.last_refill_ns);
+        if elapsed_ns <= 0 {
+            return;
+        }
+        let capacity = i128::from(self.capacity);
+        let window_ns = i128::from(i64::from(self.window_ns));
+        let refilled = i128::from(elapsed_ns) * capacity / window_ns;
+        if i128::from(self.remaining) + refilled >= capacity {
+            self.remaining = self.capacity;
+            self.last_refill_ns = current_ts_ns;
+        } else if refilled > 0 {
+            self.remaining += refilled as u16;
+            // Only advance by the time the refilled permits took, so partial progress toward the next one is kept.
+            self.last_refill_ns += ((refilled * window_ns / capacity) as i64).into();
+        }
     }
 
     /// Aquire a single permit for a new order related action.
-    /// returns `true` if the rate limit has been reached.
+    /// Returns `Error::RateLimitReached` if the bucket is empty.
     #[inline(always)]
     pub(crate) fn aquire(&mut self, current_ts_ns: TimestampNs) -> crate::Result<()> {
-        if !self.is_in_bucket(current_ts_ns) {
-            self.new_bucket(current_ts_ns);
-            self.remaining -= 1;
-            return Ok(());
-        }
+        self.refill(current_ts_ns);
         if self.remaining == 0 {
             return Err(Error::RateLimitReached);
         }
@@ -62,7 +64,7 @@ mod tests {
 
     #[test]
     fn order_rate_limiter() {
-        let mut limiter = OrderRateLimiter::new(5);
+        let mut limiter = OrderRateLimiter::new(OrderRateLimits::new(5).unwrap());
         for _i in 0..5 {
             assert!(limiter.aquire(0.into()).is_ok());
         }
@@ -73,4 +75,25 @@ mod tests {
         }
         assert!(limiter.aquire(1_000_000_000.into()).is_err());
     }
+
+    #[test]
+    fn order_rate_limiter_refills_evenly() {
+        let mut limiter =
+            OrderRateLimiter::new(OrderRateLimits::per_window(4, 1_000.into()).unwrap());
+        for _i in 0..4 {
+            assert!(limiter.aquire(0.into()).is_ok());
+        }
+        assert!(limiter.aquire(100.into()).is_err());
+        // One permit refills every 250 ns.
+        assert!(limiter.aquire(300.into()).is_ok());
+        assert!(limiter.aquire(300.into()).is_err());
+        // The 50 ns since the last permit count toward the next one.
+        assert!(limiter.aquire(499.into()).is_err());
+        assert!(limiter.aquire(500.into()).is_ok());
+        // The bucket never holds more than its capacity.
+        for _i in 0..4 {
+            assert!(limiter.aquire(10_000.into()).is_ok());
+        }
+        assert!(limiter.aquire(10_000.into()).is_err());
+    }
 }
@@ -1,9 +1,9 @@
 use std::{cmp::Ordering, ops::Neg};
 
-use const_decimal::Decimal;
 use num_traits::Zero;
 
 use crate::{
+    contract_specification::ContractSpecification,
     position_inner::PositionInner,
     prelude::{Currency, Mon, QuoteCurrency, TransactionAccounting, USER_POSITION_MARGIN_ACCOUNT},
     types::{MarginCurrency, Side},
@@ -62,6 +62,37 @@ where
         }
     }
 
+    /// Get the total fees of the fills which opened or increased the position, regardless of the `FeeMode`.
+    pub fn entry_fees(&self) -> BaseOrQuote::PairedCurrency {
+        match self {
+            Position::Neutral => BaseOrQuote::PairedCurrency::zero(),
+            Position::Long(inner) => inner.entry_fees(),
+            Position::Short(inner) => inner.entry_fees(),
+        }
+    }
+
+    /// Attribute the `fees` of a fill of `filled_qty` on `side` to the position, once the fill was applied.
+    /// Only the part of the fill which entered the position counts,
+    /// so a fill which flips the position contributes in proportion to the quantity of the new position.
+    pub(crate) fn add_entry_fees(
+        &mut self,
+        side: Side,
+        filled_qty: BaseOrQuote,
+        fees: BaseOrQuote::PairedCurrency,
+    ) {
+        let inner = match (self, side) {
+            (Position::Long(inner), Side::Buy) | (Position::Short(inner), Side::Sell) => inner,
+            _ => return,
+        };
+        if filled_qty <= inner.quantity() {
+            inner.add_entry_fees(fees);
+        } else {
+            inner.add_entry_fees(BaseOrQuote::PairedCurrency::from(
+                *fees.as_ref() * *inner.quantity().as_ref() / *filled_qty.as_ref(),
+            ));
+        }
+    }
+
     /// The entry price of the position which is the total cost of the position relative to its quantity.
     pub fn entry_price(&self) -> QuoteCurrency<I, D> {
         match self {
@@ -81,6 +112,7 @@ where
     }
 
     /// Change a position while doing proper accounting and balance transfers.
+    /// The margin requirement is chosen by the direction of the position from the `contract_spec`.
     #[tracing::instrument(level = "debug")]
     pub(crate) fn change_position<Acc>(
         &mut self,
@@ -88,7 +120,7 @@ where
         fill_price: QuoteCurrency<I, D>,
         side: Side,
         transaction_accounting: &mut Acc,
-        init_margin_req: Decimal<I, D>,
+        contract_spec: &ContractSpecification<I, D, BaseOrQuote>,
         fees: BaseOrQuote::PairedCurrency,
     ) where
         Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
@@ -97,6 +129,8 @@ where
             filled_qty > BaseOrQuote::zero(),
             "The filled_qty must be greater than zero"
         );
+        let init_margin_req_long = contract_spec.init_margin_req_for(Side::Buy);
+        let init_margin_req_short = contract_spec.init_margin_req_for(Side::Sell);
         match self {
             Position::Neutral => {
                 debug_assert_eq!(
@@ -111,7 +145,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            init_margin_req_long,
                             fees,
                         ))
                     }
@@ -120,7 +154,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            init_margin_req_short,
                             fees,
                         ))
                     }
@@ -132,7 +166,7 @@ where
                         filled_qty,
                         fill_price,
                         transaction_accounting,
-                        init_margin_req,
+                        init_margin_req_long,
                         fees,
                     );
                 }
@@ -142,7 +176,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             1,
                             fees,
                         );
@@ -152,7 +186,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             1,
                             fees,
                         );
@@ -170,7 +204,7 @@ where
                             inner.quantity(),
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             1,
                             fees,
                         );
@@ -185,7 +219,7 @@ where
                             new_short_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            init_margin_req_short,
                             BaseOrQuote::PairedCurrency::zero(),
                         ));
                     }
@@ -198,7 +232,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             -1,
                             fees,
                         );
@@ -208,7 +242,7 @@ where
                             filled_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             -1,
                             fees,
                         );
@@ -226,7 +260,7 @@ where
                             inner.quantity(),
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            contract_spec,
                             -1,
                             fees,
                         );
@@ -241,7 +275,7 @@ where
                             new_long_qty,
                             fill_price,
                             transaction_accounting,
-                            init_margin_req,
+                            init_margin_req_long,
                             BaseOrQuote::PairedCurrency::zero(),
                         ));
                     }
@@ -251,7 +285,7 @@ where
                         filled_qty,
                         fill_price,
                         transaction_accounting,
-                        init_margin_req,
+                        init_margin_req_short,
                         fees,
                     );
                 }
@@ -281,6 +315,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use const_decimal::Decimal;
+
     use super::*;
     use crate::{MockTransactionAccounting, prelude::*};
 
@@ -303,14 +339,14 @@ mod tests {
             QuoteCurrency::zero(),
         ));
         let mut acc = MockTransactionAccounting::default();
-        let init_margin_req = Decimal::ONE;
+        let contract_spec = ContractSpecification::default();
         let fees = QuoteCurrency::zero();
         pos.change_position(
             BaseCurrency::new(317, 3),
             QuoteCurrency::new(3020427, 2),
             Side::Buy,
             &mut acc,
-            init_margin_req,
+            &contract_spec,
             fees,
         );
     }
@@ -354,14 +390,14 @@ mod tests {
                 QuoteCurrency::from(Decimal::try_from_scaled(1000000000, 5).unwrap()),
             ),
         ]);
-        let init_margin_req = Decimal::ONE;
+        let contract_spec = ContractSpecification::default();
         let fees = QuoteCurrency::zero();
         pos.change_position(
             filled_qty,
             fill_price,
             Side::Sell,
             &mut acc,
-            init_margin_req,
+            &contract_spec,
             fees,
         );
     }
@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 use const_decimal::Decimal;
 use getset::{CopyGetters, Getters};
 use num::One;
@@ -7,11 +5,12 @@ use num_traits::{Signed, Zero};
 use tracing::{debug, trace};
 
 use crate::{
+    contract_specification::ContractSpecification,
     prelude::{
         Currency, EXCHANGE_FEE_ACCOUNT, Mon, QuoteCurrency, TREASURY_ACCOUNT, Transaction,
         TransactionAccounting, USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT,
     },
-    types::MarginCurrency,
+    types::{MarginCurrency, Side},
 };
 
 /// Describes the position information of the account.
@@ -33,6 +32,16 @@ where
     /// The outstanding fees of the position that will be payed when reducing the position.
     #[getset(get_copy = "pub")]
     outstanding_fees: BaseOrQuote::PairedCurrency,
+
+    /// The total fees of the fills which opened or increased the position, regardless of the `FeeMode`.
+    /// Reducing the position does not change them.
+    #[getset(get_copy = "pub")]
+    entry_fees: BaseOrQuote::PairedCurrency,
+
+    /// The margin added on top of the initial margin with `Exchange::add_isolated_margin`,
+    /// which absorbs losses and thus moves the liquidation price away from the entry price.
+    #[getset(get_copy = "pub")]
+    added_margin: BaseOrQuote::PairedCurrency,
 }
 
 impl<I, const D: u8, BaseOrQuote> std::fmt::Display for PositionInner<I, D, BaseOrQuote>
@@ -41,11 +50,18 @@ where
     BaseOrQuote: Currency<I, D>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "PositionInner( quantity: {}, outstanding_fees: {})",
-            self.quantity, self.outstanding_fees
-        )
+        match f.precision() {
+            Some(precision) => write!(
+                f,
+                "PositionInner( quantity: {:.precision$}, outstanding_fees: {:.precision$})",
+                self.quantity, self.outstanding_fees
+            ),
+            None => write!(
+                f,
+                "PositionInner( quantity: {}, outstanding_fees: {})",
+                self.quantity, self.outstanding_fees
+            ),
+        }
     }
 }
 
@@ -65,11 +81,15 @@ where
             quantity,
             entry_price,
             outstanding_fees,
+            entry_fees: BaseOrQuote::PairedCurrency::zero(),
+            added_margin: BaseOrQuote::PairedCurrency::zero(),
         }
     }
 
     /// Create a new instance.
     ///
+    /// The `init_margin_req` must be in the range (0, 1], as guaranteed by the `ContractSpecification`.
+    ///
     /// # Panics:
     /// if `quantity` or `entry_price` are invalid.
     pub fn new<Acc>(
@@ -101,10 +121,17 @@ where
             quantity,
             entry_price,
             outstanding_fees: fees,
+            entry_fees: BaseOrQuote::PairedCurrency::zero(),
+            added_margin: BaseOrQuote::PairedCurrency::zero(),
         }
     }
 
     /// The cost of the position.
+    ///
+    /// The `entry_price` is a weighted average which is rounded to the decimal precision `D`,
+    /// so the cost may deviate from the summed notional value of the individual fills.
+    /// For linear futures the deviation is bounded by `quantity` times the smallest representable price increment.
+    /// The margin actually locked for the position is tracked by the `USER_POSITION_MARGIN_ACCOUNT`.
     #[inline(always)]
     pub fn total_cost(&self) -> BaseOrQuote::PairedCurrency {
         BaseOrQuote::PairedCurrency::convert_from(self.quantity, self.entry_price)
@@ -138,6 +165,8 @@ where
         );
         assert2::assert!(qty > BaseOrQuote::zero());
         assert2::assert!(entry_price > QuoteCurrency::zero());
+        debug_assert!(init_margin_req > Decimal::zero());
+        debug_assert!(init_margin_req <= Decimal::one());
 
         let value = BaseOrQuote::PairedCurrency::convert_from(qty, entry_price);
         let new_entry_price = QuoteCurrency::new_weighted_price(
@@ -159,13 +188,42 @@ where
             .expect("is an internal call and must work");
     }
 
+    /// Add the `fees` of a fill which entered the position.
+    #[inline]
+    pub(crate) fn add_entry_fees(&mut self, fees: BaseOrQuote::PairedCurrency) {
+        self.entry_fees += fees;
+    }
+
+    /// Move the `amount` from the users wallet into the position margin.
+    pub(crate) fn add_margin<Acc>(
+        &mut self,
+        amount: BaseOrQuote::PairedCurrency,
+        accounting: &mut Acc,
+    ) where
+        Acc: TransactionAccounting<I, D, BaseOrQuote::PairedCurrency>,
+    {
+        let transaction =
+            Transaction::new(USER_POSITION_MARGIN_ACCOUNT, USER_WALLET_ACCOUNT, amount);
+        accounting
+            .create_margin_transfer(transaction)
+            .expect("is an internal call and must work");
+        self.added_margin += amount;
+    }
+
+    /// Reduce the `added_margin` by `amount`, after it was moved back into the users wallet.
+    pub(crate) fn reduce_added_margin(&mut self, amount: BaseOrQuote::PairedCurrency) {
+        assert2::assert!(amount <= self.added_margin);
+        self.added_margin -= amount;
+    }
+
     /// Decrease the position.
+    /// The margin requirement and pnl settlement increment are taken from the `contract_spec`.
     pub(crate) fn decrease_contracts<Acc>(
         &mut self,
         qty: BaseOrQuote,
         liquidation_price: QuoteCurrency<I, D>,
         accounting: &mut Acc,
-        init_margin_req: Decimal<I, D>,
+        contract_spec: &ContractSpecification<I, D, BaseOrQuote>,
         direction_multiplier: i8,
         fees: BaseOrQuote::PairedCurrency,
     ) where
@@ -179,6 +237,11 @@ where
         assert2::assert!(qty <= self.quantity);
         debug_assert!(direction_multiplier == 1 || direction_multiplier == -1);
 
+        let init_margin_req = contract_spec.init_margin_req_for(if direction_multiplier == 1 {
+            Side::Buy
+        } else {
+            Side::Sell
+        });
         let entry_price = self.entry_price();
 
         self.quantity -= qty;
@@ -191,33 +254,32 @@ where
             liquidation_price,
             if direction_multiplier == 1 { qty } else { -qty },
         );
-        match pnl.cmp(&BaseOrQuote::PairedCurrency::zero()) {
-            Ordering::Greater => {
-                let transaction = Transaction::new(USER_WALLET_ACCOUNT, TREASURY_ACCOUNT, pnl);
-                accounting
-                    .create_margin_transfer(transaction)
-                    .expect("margin transfer must work");
-            }
-            Ordering::Less => {
-                let transaction =
-                    Transaction::new(TREASURY_ACCOUNT, USER_WALLET_ACCOUNT, pnl.abs());
-                accounting
-                    .create_margin_transfer(transaction)
-                    .expect("margin transfer must work");
-            }
-            Ordering::Equal => {}
-        }
-        let margin_to_free =
-            BaseOrQuote::PairedCurrency::convert_from(qty, entry_price) * init_margin_req;
-        debug_assert!(margin_to_free > BaseOrQuote::PairedCurrency::zero());
-        let transaction = Transaction::new(
-            USER_WALLET_ACCOUNT,
-            USER_POSITION_MARGIN_ACCOUNT,
-            margin_to_free,
+        settle_pnl(
+            accounting,
+            round_pnl_for_settlement(pnl, contract_spec.pnl_settlement_increment()),
         );
-        accounting
-            .create_margin_transfer(transaction)
-            .expect("margin transfer must work");
+        let margin_to_free = if self.quantity.is_zero() {
+            // Release all the margin that is actually locked,
+            // so the rounded `entry_price` does not leave any residual in the position margin account.
+            accounting
+                .margin_balance_of(USER_POSITION_MARGIN_ACCOUNT)
+                .expect("is a valid account")
+        } else {
+            let margin =
+                BaseOrQuote::PairedCurrency::convert_from(qty, entry_price) * init_margin_req;
+            debug_assert!(margin > BaseOrQuote::PairedCurrency::zero());
+            margin
+        };
+        if margin_to_free > BaseOrQuote::PairedCurrency::zero() {
+            let transaction = Transaction::new(
+                USER_WALLET_ACCOUNT,
+                USER_POSITION_MARGIN_ACCOUNT,
+                margin_to_free,
+            );
+            accounting
+                .create_margin_transfer(transaction)
+                .expect("margin transfer must work");
+        }
 
         if self.outstanding_fees > BaseOrQuote::PairedCurrency::zero() {
             let transaction = Transaction::new(
@@ -233,6 +295,50 @@ where
     }
 }
 
+/// Round the `pnl` down to a multiple of `increment`, if one is provided.
+/// Rounding down means the user receives less of a profit and pays more of a loss,
+/// so the residual always stays with the treasury.
+fn round_pnl_for_settlement<I, const D: u8, BaseOrQuote>(
+    pnl: BaseOrQuote,
+    increment: Option<BaseOrQuote>,
+) -> BaseOrQuote
+where
+    I: Mon<D>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    let Some(increment) = increment else {
+        return pnl;
+    };
+    let remainder = BaseOrQuote::from(*pnl.as_ref() % *increment.as_ref());
+    if remainder < BaseOrQuote::zero() {
+        pnl - remainder - increment
+    } else {
+        pnl - remainder
+    }
+}
+
+/// Settle the realized profit and loss between the users wallet and the treasury.
+/// A positive `pnl` is paid into the wallet, a negative one is paid from the wallet into the treasury.
+fn settle_pnl<I, const D: u8, Acc, BaseOrQuote>(accounting: &mut Acc, pnl: BaseOrQuote)
+where
+    I: Mon<D>,
+    Acc: TransactionAccounting<I, D, BaseOrQuote>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    if pnl.is_zero() {
+        return;
+    }
+    let (debit_account, credit_account) = if pnl.is_positive() {
+        (USER_WALLET_ACCOUNT, TREASURY_ACCOUNT)
+    } else {
+        (TREASURY_ACCOUNT, USER_WALLET_ACCOUNT)
+    };
+    let transaction = Transaction::new(debit_account, credit_account, pnl.abs());
+    accounting
+        .create_margin_transfer(transaction)
+        .expect("margin transfer must work");
+}
+
 #[cfg(test)]
 mod tests {
     use const_decimal::Decimal;
@@ -256,6 +362,8 @@ mod tests {
                 quantity: qty,
                 entry_price,
                 outstanding_fees: fees,
+                entry_fees: QuoteCurrency::zero(),
+                added_margin: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -286,7 +394,9 @@ mod tests {
             PositionInner {
                 quantity: BaseCurrency::one(),
                 entry_price: QuoteCurrency::new(125, 0),
-                outstanding_fees: fee_0 + fee_1
+                outstanding_fees: fee_0 + fee_1,
+                entry_fees: QuoteCurrency::zero(),
+                added_margin: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(125, 0));
@@ -303,7 +413,11 @@ mod tests {
     #[test_case::test_matrix([1, 2, 5])]
     fn position_inner_decrease_contracts_basic(leverage: u8) {
         let mut ta = InMemoryTransactionAccounting::new(QuoteCurrency::<_, DECIMALS>::new(1000, 0));
-        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+        let contract_spec = ContractSpecification::builder("")
+            .leverage(Leverage::new(leverage).unwrap())
+            .build()
+            .unwrap();
+        let init_margin_req = contract_spec.init_margin_req();
         let qty = BaseCurrency::new(5, 0);
         let entry_price = QuoteCurrency::new(100, 0);
         let fees = QuoteCurrency::convert_from(qty, entry_price) * *test_fee_maker().as_ref();
@@ -312,7 +426,7 @@ mod tests {
             qty / BaseCurrency::new(2, 0),
             entry_price,
             &mut ta,
-            init_margin_req,
+            &contract_spec,
             1,
             fees / QuoteCurrency::new(2, 0),
         );
@@ -322,6 +436,8 @@ mod tests {
                 quantity: BaseCurrency::new(25, 1),
                 entry_price: QuoteCurrency::new(100, 0),
                 outstanding_fees: QuoteCurrency::new(0, 0),
+                entry_fees: QuoteCurrency::zero(),
+                added_margin: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -339,7 +455,7 @@ mod tests {
             qty / BaseCurrency::new(2, 0),
             entry_price,
             &mut ta,
-            init_margin_req,
+            &contract_spec,
             1,
             fees / QuoteCurrency::new(2, 0),
         );
@@ -349,6 +465,8 @@ mod tests {
                 quantity: BaseCurrency::new(0, 0),
                 entry_price: QuoteCurrency::new(100, 0),
                 outstanding_fees: QuoteCurrency::new(0, 0),
+                entry_fees: QuoteCurrency::zero(),
+                added_margin: QuoteCurrency::zero(),
             }
         );
         assert_eq!(pos.entry_price(), QuoteCurrency::new(100, 0));
@@ -368,7 +486,11 @@ mod tests {
     )]
     fn position_inner_decrease_contracts_win(leverage: u8, position_side: Side) {
         let mut ta = InMemoryTransactionAccounting::new(QuoteCurrency::<_, DECIMALS>::new(1000, 0));
-        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+        let contract_spec = ContractSpecification::builder("")
+            .leverage(Leverage::new(leverage).unwrap())
+            .build()
+            .unwrap();
+        let init_margin_req = contract_spec.init_margin_req();
         let qty = BaseCurrency::new(5, 0);
         let entry_price = QuoteCurrency::new(100, 0);
         let fees = QuoteCurrency::convert_from(qty, entry_price) * *test_fee_maker().as_ref();
@@ -383,7 +505,7 @@ mod tests {
             qty / BaseCurrency::new(2, 0),
             exit_price,
             &mut ta,
-            init_margin_req,
+            &contract_spec,
             side_mult,
             fees / QuoteCurrency::new(2, 0),
         );
@@ -411,7 +533,11 @@ mod tests {
     )]
     fn position_inner_decrease_contracts_2(leverage: u8, position_side: Side) {
         let mut ta = InMemoryTransactionAccounting::new(QuoteCurrency::<_, DECIMALS>::new(1000, 0));
-        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+        let contract_spec = ContractSpecification::builder("")
+            .leverage(Leverage::new(leverage).unwrap())
+            .build()
+            .unwrap();
+        let init_margin_req = contract_spec.init_margin_req();
         let qty = BaseCurrency::new(5, 0);
         let entry_price = QuoteCurrency::new(100, 0);
         let fees = QuoteCurrency::convert_from(qty, entry_price) * *test_fee_maker().as_ref();
@@ -426,7 +552,7 @@ mod tests {
             qty / BaseCurrency::new(2, 0),
             exit_price,
             &mut ta,
-            init_margin_req,
+            &contract_spec,
             side_mult,
             fees / QuoteCurrency::new(2, 0),
         );
@@ -454,7 +580,11 @@ mod tests {
     #[ignore]
     fn position_inner_decrease_contracts_inverse(leverage: u8) {
         let mut ta = InMemoryTransactionAccounting::new(BaseCurrency::<_, DECIMALS>::new(10, 0));
-        let init_margin_req = Leverage::new(leverage).unwrap().init_margin_req();
+        let contract_spec = ContractSpecification::builder("")
+            .leverage(Leverage::new(leverage).unwrap())
+            .build()
+            .unwrap();
+        let init_margin_req = contract_spec.init_margin_req();
         let qty = QuoteCurrency::new(500, 0);
         let entry_price = QuoteCurrency::new(100, 0);
         let val = BaseCurrency::convert_from(qty, entry_price);
@@ -467,7 +597,7 @@ mod tests {
             qty / QuoteCurrency::new(2, 0),
             exit_price,
             &mut ta,
-            init_margin_req,
+            &contract_spec,
             1,
             fees / BaseCurrency::new(2, 0),
         );
@@ -486,6 +616,187 @@ mod tests {
         );
     }
 
+    #[test_case::test_case(QuoteCurrency::new(50, 0), QuoteCurrency::new(1050, 0), QuoteCurrency::new(-50, 0); "profit")]
+    #[test_case::test_case(QuoteCurrency::new(-50, 0), QuoteCurrency::new(950, 0), QuoteCurrency::new(50, 0); "loss")]
+    #[test_case::test_case(QuoteCurrency::zero(), QuoteCurrency::new(1000, 0), QuoteCurrency::zero(); "zero")]
+    fn position_inner_settle_pnl(
+        pnl: QuoteCurrency<i64, DECIMALS>,
+        expected_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+        expected_treasury_change: QuoteCurrency<i64, DECIMALS>,
+    ) {
+        let starting_balance = QuoteCurrency::new(1000, 0);
+        let mut ta = InMemoryTransactionAccounting::new(starting_balance);
+        let treasury_balance = ta.margin_balance_of(TREASURY_ACCOUNT).unwrap();
+        assert_eq!(treasury_balance, -starting_balance);
+
+        settle_pnl(&mut ta, pnl);
+        assert_eq!(
+            ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+            expected_wallet_balance
+        );
+        assert_eq!(
+            ta.margin_balance_of(TREASURY_ACCOUNT).unwrap(),
+            treasury_balance + expected_treasury_change
+        );
+    }
+
+    #[test_case::test_case(QuoteCurrency::new(37, 2), None, QuoteCurrency::new(37, 2); "no_increment")]
+    #[test_case::test_case(QuoteCurrency::new(37, 2), Some(QuoteCurrency::new(1, 1)), QuoteCurrency::new(3, 1); "profit")]
+    #[test_case::test_case(QuoteCurrency::new(-37, 2), Some(QuoteCurrency::new(1, 1)), QuoteCurrency::new(-4, 1); "loss")]
+    #[test_case::test_case(QuoteCurrency::new(-3, 1), Some(QuoteCurrency::new(1, 1)), QuoteCurrency::new(-3, 1); "exact")]
+    fn position_inner_round_pnl_for_settlement(
+        pnl: QuoteCurrency<i64, DECIMALS>,
+        increment: Option<QuoteCurrency<i64, DECIMALS>>,
+        expected: QuoteCurrency<i64, DECIMALS>,
+    ) {
+        assert_eq!(round_pnl_for_settlement(pnl, increment), expected);
+    }
+
+    #[test_case::test_case(Side::Buy, QuoteCurrency::new(3, 1); "long")]
+    #[test_case::test_case(Side::Sell, QuoteCurrency::new(-4, 1); "short")]
+    fn position_inner_decrease_contracts_pnl_settlement_increment(
+        position_side: Side,
+        expected_pnl: QuoteCurrency<i64, DECIMALS>,
+    ) {
+        let starting_balance = QuoteCurrency::new(1000, 0);
+        let mut ta = InMemoryTransactionAccounting::new(starting_balance);
+        let contract_spec = ContractSpecification::<i64, DECIMALS, BaseCurrency<_, _>>::builder("")
+            .pnl_settlement_increment(QuoteCurrency::new(1, 1))
+            .build()
+            .unwrap();
+        let qty = BaseCurrency::new(1, 0);
+        let entry_price = QuoteCurrency::new(100, 0);
+        let fees = QuoteCurrency::zero();
+        let mut pos = PositionInner::new(
+            qty,
+            entry_price,
+            &mut ta,
+            contract_spec.init_margin_req_for(position_side),
+            fees,
+        );
+
+        // The exact pnl of 0.37 is not a multiple of the increment.
+        let side_mult = match position_side {
+            Side::Buy => 1,
+            Side::Sell => -1,
+        };
+        pos.decrease_contracts(
+            qty,
+            QuoteCurrency::new(10037, 2),
+            &mut ta,
+            &contract_spec,
+            side_mult,
+            fees,
+        );
+        assert_eq!(
+            ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+            starting_balance + expected_pnl
+        );
+        // The treasury keeps the rounding residual.
+        assert_eq!(
+            ta.margin_balance_of(TREASURY_ACCOUNT).unwrap(),
+            -starting_balance - expected_pnl
+        );
+    }
+
+    #[test_case::test_matrix([3, 7])]
+    fn position_inner_margin_fractional_leverage(leverage: u8) {
+        let contract_spec = ContractSpecification::<i64, DECIMALS, BaseCurrency<_, _>>::builder("")
+            .leverage(Leverage::new(leverage).unwrap())
+            .build()
+            .unwrap();
+        let init_margin_req = contract_spec.init_margin_req();
+        let qty = BaseCurrency::new(3, 0);
+        let entry_price = QuoteCurrency::new(101, 0);
+        let expected_margin = QuoteCurrency::convert_from(qty, entry_price) * init_margin_req;
+
+        let run = || {
+            let mut ta =
+                InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+            let mut pos = PositionInner::new(
+                qty,
+                entry_price,
+                &mut ta,
+                init_margin_req,
+                QuoteCurrency::zero(),
+            );
+            let position_margin = ta.margin_balance_of(USER_POSITION_MARGIN_ACCOUNT).unwrap();
+            assert_eq!(position_margin, expected_margin);
+            assert_eq!(
+                ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap() + position_margin,
+                QuoteCurrency::new(1000, 0)
+            );
+
+            pos.decrease_contracts(
+                qty,
+                entry_price,
+                &mut ta,
+                &contract_spec,
+                1,
+                QuoteCurrency::zero(),
+            );
+            assert_eq!(
+                ta.margin_balance_of(USER_POSITION_MARGIN_ACCOUNT).unwrap(),
+                QuoteCurrency::zero()
+            );
+            assert_eq!(
+                ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+                QuoteCurrency::new(1000, 0)
+            );
+            position_margin
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn position_inner_total_cost_rounded_entry_price() {
+        let mut ta =
+            InMemoryTransactionAccounting::new(QuoteCurrency::<i64, DECIMALS>::new(1000, 0));
+        let contract_spec = ContractSpecification::default();
+        let init_margin_req = Decimal::one();
+        let fees = QuoteCurrency::zero();
+        let mut pos = PositionInner::new(
+            BaseCurrency::new(1, 0),
+            QuoteCurrency::new(100, 0),
+            &mut ta,
+            init_margin_req,
+            fees,
+        );
+        pos.increase_contracts(
+            BaseCurrency::new(2, 0),
+            QuoteCurrency::new(101, 0),
+            &mut ta,
+            init_margin_req,
+            fees,
+        );
+
+        // The exact average entry price of 100.666.. is not representable.
+        let filled_notional = QuoteCurrency::new(302, 0);
+        assert_eq!(
+            ta.margin_balance_of(USER_POSITION_MARGIN_ACCOUNT).unwrap(),
+            filled_notional
+        );
+        let smallest_increment = QuoteCurrency::new(1, DECIMALS);
+        assert!(
+            (filled_notional - pos.total_cost()).abs()
+                <= QuoteCurrency::convert_from(pos.quantity(), smallest_increment)
+        );
+
+        // Closing the position releases all the locked margin.
+        let exit_price = QuoteCurrency::new(101, 0);
+        let pnl = pos.unrealized_pnl(exit_price);
+        pos.decrease_contracts(pos.quantity(), exit_price, &mut ta, &contract_spec, 1, fees);
+        assert_eq!(pos.quantity(), BaseCurrency::zero());
+        assert_eq!(
+            ta.margin_balance_of(USER_POSITION_MARGIN_ACCOUNT).unwrap(),
+            QuoteCurrency::zero()
+        );
+        assert_eq!(
+            ta.margin_balance_of(USER_WALLET_ACCOUNT).unwrap(),
+            QuoteCurrency::new(1000, 0) + pnl
+        );
+    }
+
     #[test_case::test_matrix([1, 2, 5, 9])]
     fn position_inner_entry_price_linear(qty: i32) {
         let qty = BaseCurrency::<i32, DECIMALS>::new(qty, 0);
@@ -532,5 +843,13 @@ mod tests {
             &pos.to_string(),
             "PositionInner( quantity: 0.5 Base, outstanding_fees: 0.1 Quote)"
         );
+        assert_eq!(
+            &format!("{pos:.3}"),
+            "PositionInner( quantity: 0.500 Base, outstanding_fees: 0.100 Quote)"
+        );
+        assert_eq!(
+            &format!("{pos:.0}"),
+            "PositionInner( quantity: 0 Base, outstanding_fees: 0 Quote)"
+        );
     }
 }
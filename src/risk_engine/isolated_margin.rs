@@ -1,7 +1,10 @@
-use num::Zero;
+use std::ops::Neg;
+
+use const_decimal::Decimal;
+use num::{One, Zero};
 use tracing::trace;
 
-use super::RiskEngine;
+use super::{FlatMaintenanceMargin, MaintenanceMarginModel, RiskEngine};
 use crate::{
     contract_specification::ContractSpecification,
     market_state::MarketState,
@@ -10,13 +13,23 @@ use crate::{
     types::{LimitOrder, MarginCurrency, MarketOrder, Pending, Side, UserOrderId},
 };
 
+/// A `RiskEngine` where the margin is directly associated with the position.
+///
+/// # Generics:
+/// - `MaintenanceMarginModelT`: Computes the maintenance margin of a position,
+///   defaulting to the flat `maintenance_margin` of the `ContractSpecification`.
 #[derive(Debug, Clone)]
-pub(crate) struct IsolatedMarginRiskEngine<I, const D: u8, BaseOrQuote>
-where
+pub struct IsolatedMarginRiskEngine<
+    I,
+    const D: u8,
+    BaseOrQuote,
+    MaintenanceMarginModelT = FlatMaintenanceMargin<I, D>,
+> where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
 {
     contract_spec: ContractSpecification<I, D, BaseOrQuote>,
+    maintenance_margin_model: MaintenanceMarginModelT,
 }
 
 impl<I, const D: u8, BaseOrQuote> IsolatedMarginRiskEngine<I, D, BaseOrQuote>
@@ -24,18 +37,60 @@ where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
 {
-    pub(crate) fn new(contract_spec: ContractSpecification<I, D, BaseOrQuote>) -> Self {
-        Self { contract_spec }
+    /// Create a new instance which checks the margin requirements defined in the `contract_spec`.
+    pub fn new(contract_spec: ContractSpecification<I, D, BaseOrQuote>) -> Self {
+        let maintenance_margin_model = FlatMaintenanceMargin::from(&contract_spec);
+        Self {
+            contract_spec,
+            maintenance_margin_model,
+        }
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote, MaintenanceMarginModelT>
+    IsolatedMarginRiskEngine<I, D, BaseOrQuote, MaintenanceMarginModelT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+    MaintenanceMarginModelT: MaintenanceMarginModel<I, D, BaseOrQuote::PairedCurrency>,
+{
+    /// Create a new instance which checks the initial margin requirements defined in the `contract_spec`,
+    /// while the maintenance margin of a position is computed by the `maintenance_margin_model`.
+    /// The `maintenance_margin_buffer` of the `contract_spec` is still applied on top of it.
+    pub fn with_maintenance_margin_model(
+        contract_spec: ContractSpecification<I, D, BaseOrQuote>,
+        maintenance_margin_model: MaintenanceMarginModelT,
+    ) -> Self {
+        Self {
+            contract_spec,
+            maintenance_margin_model,
+        }
+    }
+
+    /// The maintenance margin requirement of a position on `side` with a `notional` value at its entry price,
+    /// increased by the `maintenance_margin_buffer`.
+    #[inline]
+    fn buffered_maintenance_margin(
+        &self,
+        notional: BaseOrQuote::PairedCurrency,
+        side: Side,
+    ) -> Decimal<I, D> {
+        self.maintenance_margin_model
+            .maintenance_margin_req(notional, side)
+            * (Decimal::one() + self.contract_spec.maintenance_margin_buffer())
     }
 }
 
-impl<I, const D: u8, BaseOrQuote, UserOrderIdT> RiskEngine<I, D, BaseOrQuote, UserOrderIdT>
-    for IsolatedMarginRiskEngine<I, D, BaseOrQuote>
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT, MaintenanceMarginModelT>
+    RiskEngine<I, D, BaseOrQuote, UserOrderIdT>
+    for IsolatedMarginRiskEngine<I, D, BaseOrQuote, MaintenanceMarginModelT>
 where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
     BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
     UserOrderIdT: UserOrderId,
+    MaintenanceMarginModelT: MaintenanceMarginModel<I, D, BaseOrQuote::PairedCurrency>,
 {
     fn check_market_order(
         &self,
@@ -71,10 +126,10 @@ where
         order_margin_online: &OrderMargin<I, D, BaseOrQuote, UserOrderIdT>,
     ) -> Result<(), RiskError> {
         let order_margin =
-            order_margin_online.order_margin(self.contract_spec.init_margin_req(), position);
+            order_margin_online.order_margin(self.contract_spec.order_init_margin_req(), position);
         let new_order_margin = order_margin_online.order_margin_with_order(
             order,
-            self.contract_spec.init_margin_req(),
+            self.contract_spec.order_init_margin_req(),
             position,
         );
 
@@ -93,21 +148,40 @@ where
         market_state: &MarketState<I, D>,
         position: &Position<I, D, BaseOrQuote>,
     ) -> Result<(), RiskError> {
-        let maint_margin_req = self.contract_spec.maintenance_margin();
+        // The position is liquidated once its equity falls strictly below the buffered maintenance margin,
+        // so a mark price exactly at the liquidation price does not trigger a liquidation.
+        // The outstanding fees are deducted when the position is closed, so they reduce the equity as well
+        // and move the effective liquidation price towards the entry price.
+        // Margin added to the position absorbs losses, so it moves the liquidation price away from the entry price.
         match position {
             Position::Neutral => return Ok(()),
             Position::Long(inner) => {
-                let liquidation_price =
-                    inner.entry_price().liquidation_price_long(maint_margin_req);
-                if market_state.bid() < liquidation_price {
+                let liquidation_price = inner.entry_price().liquidation_price_long(
+                    self.contract_spec.init_margin_req_for(Side::Buy),
+                    self.buffered_maintenance_margin(inner.total_cost(), Side::Buy),
+                );
+                let mark_price = self
+                    .contract_spec
+                    .mark_method()
+                    .mark_price(market_state, Side::Buy);
+                let equity_pnl = inner.unrealized_pnl(mark_price) - inner.outstanding_fees()
+                    + inner.added_margin();
+                if equity_pnl < inner.unrealized_pnl(liquidation_price) {
                     return Err(RiskError::Liquidate);
                 }
             }
             Position::Short(inner) => {
-                let liquidation_price = inner
-                    .entry_price()
-                    .liquidation_price_short(maint_margin_req);
-                if market_state.ask() > liquidation_price {
+                let liquidation_price = inner.entry_price().liquidation_price_short(
+                    self.contract_spec.init_margin_req_for(Side::Sell),
+                    self.buffered_maintenance_margin(inner.total_cost(), Side::Sell),
+                );
+                let mark_price = self
+                    .contract_spec
+                    .mark_method()
+                    .mark_price(market_state, Side::Sell);
+                let equity_pnl = inner.unrealized_pnl(mark_price).neg() - inner.outstanding_fees()
+                    + inner.added_margin();
+                if equity_pnl < inner.unrealized_pnl(liquidation_price).neg() {
                     return Err(RiskError::Liquidate);
                 }
             }
@@ -115,9 +189,57 @@ where
 
         Ok(())
     }
+
+    fn maintenance_margin(
+        &self,
+        position: &Position<I, D, BaseOrQuote>,
+    ) -> BaseOrQuote::PairedCurrency {
+        let (side, inner) = match position {
+            Position::Neutral => return BaseOrQuote::PairedCurrency::zero(),
+            Position::Long(inner) => (Side::Buy, inner),
+            Position::Short(inner) => (Side::Sell, inner),
+        };
+        inner.total_cost() * self.buffered_maintenance_margin(inner.total_cost(), side)
+    }
+
+    // The price at which the unrealized profit and loss net of the outstanding fees and the added margin
+    // equals the one at the fee free liquidation price.
+    fn liquidation_price(
+        &self,
+        position: &Position<I, D, BaseOrQuote>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        match position {
+            Position::Neutral => None,
+            Position::Long(inner) => {
+                let liquidation_price = inner.entry_price().liquidation_price_long(
+                    self.contract_spec.init_margin_req_for(Side::Buy),
+                    self.buffered_maintenance_margin(inner.total_cost(), Side::Buy),
+                );
+                BaseOrQuote::PairedCurrency::exit_price_for_pnl(
+                    inner.entry_price(),
+                    inner.unrealized_pnl(liquidation_price) + inner.outstanding_fees()
+                        - inner.added_margin(),
+                    inner.quantity(),
+                )
+            }
+            Position::Short(inner) => {
+                let liquidation_price = inner.entry_price().liquidation_price_short(
+                    self.contract_spec.init_margin_req_for(Side::Sell),
+                    self.buffered_maintenance_margin(inner.total_cost(), Side::Sell),
+                );
+                BaseOrQuote::PairedCurrency::exit_price_for_pnl(
+                    inner.entry_price(),
+                    inner.unrealized_pnl(liquidation_price).neg() + inner.outstanding_fees()
+                        - inner.added_margin(),
+                    inner.quantity().neg(),
+                )
+            }
+        }
+    }
 }
 
-impl<I, const D: u8, BaseOrQuote> IsolatedMarginRiskEngine<I, D, BaseOrQuote>
+impl<I, const D: u8, BaseOrQuote, MaintenanceMarginModelT>
+    IsolatedMarginRiskEngine<I, D, BaseOrQuote, MaintenanceMarginModelT>
 where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
@@ -141,7 +263,7 @@ where
                 // A long position increases in size.
                 let notional_value =
                     BaseOrQuote::PairedCurrency::convert_from(order.quantity(), fill_price);
-                let margin_req = notional_value * self.contract_spec.init_margin_req();
+                let margin_req = notional_value * self.contract_spec.init_margin_req_for(Side::Buy);
 
                 let fee = notional_value * *self.contract_spec.fee_taker().as_ref();
                 if margin_req + fee > available_wallet_balance {
@@ -161,7 +283,8 @@ where
                 let new_notional_value =
                     BaseOrQuote::PairedCurrency::convert_from(new_long_size, fill_price);
                 assert2::debug_assert!(new_notional_value > BaseOrQuote::PairedCurrency::zero());
-                let new_margin_req = new_notional_value * self.contract_spec.init_margin_req();
+                let new_margin_req =
+                    new_notional_value * self.contract_spec.init_margin_req_for(Side::Buy);
                 assert2::debug_assert!(new_margin_req > BaseOrQuote::PairedCurrency::zero());
 
                 let fee = new_notional_value * *self.contract_spec.fee_taker().as_ref();
@@ -197,7 +320,8 @@ where
             Position::Neutral | Position::Short(_) => {
                 let notional_value =
                     BaseOrQuote::PairedCurrency::convert_from(order.quantity(), fill_price);
-                let margin_req = notional_value * self.contract_spec.init_margin_req();
+                let margin_req =
+                    notional_value * self.contract_spec.init_margin_req_for(Side::Sell);
                 let fee = notional_value * *self.contract_spec.fee_taker().as_ref();
 
                 if margin_req + fee > available_wallet_balance {
@@ -218,7 +342,8 @@ where
                 let new_notional_value =
                     BaseOrQuote::PairedCurrency::convert_from(new_short_size, fill_price);
                 assert2::debug_assert!(new_notional_value > BaseOrQuote::PairedCurrency::zero());
-                let new_margin_req = new_notional_value * self.contract_spec.init_margin_req();
+                let new_margin_req =
+                    new_notional_value * self.contract_spec.init_margin_req_for(Side::Sell);
                 assert2::debug_assert!(new_margin_req > BaseOrQuote::PairedCurrency::zero());
 
                 let fee = new_notional_value * *self.contract_spec.fee_taker().as_ref();
@@ -257,9 +382,6 @@ where
 
 #[cfg(test)]
 mod tests {
-    use const_decimal::Decimal;
-    use num_traits::One;
-
     use super::*;
     use crate::{DECIMALS, MockTransactionAccounting, prelude::*, test_fee_maker, test_fee_taker};
 
@@ -294,7 +416,7 @@ mod tests {
     #[test]
     fn isolated_margin_quantity_minus_position() {
         assert_eq!(
-            IsolatedMarginRiskEngine::quantity_minus_position(
+            IsolatedMarginRiskEngine::<i64, 5, BaseCurrency<i64, 5>>::quantity_minus_position(
                 BaseCurrency::new(10, 0),
                 &PositionInner::from_parts(
                     BaseCurrency::<i64, 5>::new(5, 0),
@@ -389,8 +511,9 @@ mod tests {
             ),
             Err(RiskError::Liquidate)
         );
-        let ask = QuoteCurrency::new(expected_liq_price, 0) + QuoteCurrency::one();
-        let bid = ask - QuoteCurrency::one();
+        // The outstanding fees of the single contract shift the liquidation price by the same amount.
+        let bid = QuoteCurrency::new(expected_liq_price, 0) + fees;
+        let ask = bid + QuoteCurrency::one();
         let market_state = MarketState::from_components(bid, ask, ask, 0.into(), 0);
         RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
             &re,
@@ -400,6 +523,203 @@ mod tests {
         .unwrap();
     }
 
+    // With a maintenance margin fraction other than 0.5, the equity is compared against the maintenance margin,
+    // rather than the loss.
+    #[test_case::test_case(1, 5, 0, 50)]
+    #[test_case::test_case(1, 5, 2, 60)]
+    #[test_case::test_case(2, 2, 0, 60)]
+    #[test_case::test_case(2, 2, 5, 65)]
+    fn isolated_margin_check_maintenance_margin_threshold(
+        leverage: u8,
+        maintenance_margin_fraction: i64,
+        buffer: i64,
+        liq_price: i64,
+    ) {
+        let mut contract_spec =
+            ContractSpecification::<_, DECIMALS, BaseCurrency<_, DECIMALS>>::new(
+                Leverage::new(leverage).unwrap(),
+                Decimal::try_from_scaled(maintenance_margin_fraction, 1).unwrap(),
+                PriceFilter::default(),
+                QuantityFilter::default(),
+                test_fee_maker(),
+                test_fee_taker(),
+            )
+            .unwrap();
+        contract_spec
+            .set_maintenance_margin_buffer(Decimal::try_from_scaled(buffer, 1).unwrap())
+            .unwrap();
+        let re =
+            IsolatedMarginRiskEngine::<_, DECIMALS, BaseCurrency<_, DECIMALS>>::new(contract_spec);
+        let entry_price = QuoteCurrency::new(100, 0);
+        let long = Position::Long(PositionInner::from_parts(
+            BaseCurrency::one(),
+            entry_price,
+            QuoteCurrency::zero(),
+        ));
+
+        // Exactly at the threshold the position is not liquidated.
+        let bid = QuoteCurrency::new(liq_price, 0);
+        let market_state =
+            MarketState::from_components(bid, bid + QuoteCurrency::one(), bid, 0.into(), 0);
+        RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+            &re,
+            &market_state,
+            &long,
+        )
+        .unwrap();
+
+        let bid = QuoteCurrency::new(liq_price - 1, 0);
+        let market_state =
+            MarketState::from_components(bid, bid + QuoteCurrency::one(), bid, 0.into(), 0);
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+                &re,
+                &market_state,
+                &long
+            ),
+            Err(RiskError::Liquidate)
+        );
+
+        // The short position mirrors the long position around the entry price.
+        let short = Position::Short(PositionInner::from_parts(
+            BaseCurrency::one(),
+            entry_price,
+            QuoteCurrency::zero(),
+        ));
+        let ask = entry_price + entry_price - QuoteCurrency::new(liq_price, 0);
+        let market_state =
+            MarketState::from_components(ask - QuoteCurrency::one(), ask, ask, 0.into(), 0);
+        RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+            &re,
+            &market_state,
+            &short,
+        )
+        .unwrap();
+        let ask = ask + QuoteCurrency::one();
+        let market_state =
+            MarketState::from_components(ask - QuoteCurrency::one(), ask, ask, 0.into(), 0);
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+                &re,
+                &market_state,
+                &short
+            ),
+            Err(RiskError::Liquidate)
+        );
+    }
+
+    #[test]
+    fn isolated_margin_check_maintenance_margin_outstanding_fees() {
+        let contract_spec = ContractSpecification::<_, DECIMALS, BaseCurrency<_, DECIMALS>>::new(
+            Leverage::new(1).unwrap(),
+            Decimal::try_from_scaled(5, 1).unwrap(),
+            PriceFilter::default(),
+            QuantityFilter::default(),
+            test_fee_maker(),
+            test_fee_taker(),
+        )
+        .unwrap();
+        let re =
+            IsolatedMarginRiskEngine::<_, DECIMALS, BaseCurrency<_, DECIMALS>>::new(contract_spec);
+        let qty = BaseCurrency::new(5, 0);
+        let entry_price = QuoteCurrency::new(100, 0);
+        // Without fees, the long position has a liquidation price of 50.
+        let fee_free = Position::Long(PositionInner::from_parts(
+            qty,
+            entry_price,
+            QuoteCurrency::zero(),
+        ));
+        // The outstanding fees of 50 raise the liquidation price to 60.
+        let with_fees = Position::Long(PositionInner::from_parts(
+            qty,
+            entry_price,
+            QuoteCurrency::new(50, 0),
+        ));
+
+        let bid = QuoteCurrency::new(55, 0);
+        let market_state =
+            MarketState::from_components(bid, bid + QuoteCurrency::one(), bid, 0.into(), 0);
+        RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+            &re,
+            &market_state,
+            &fee_free,
+        )
+        .unwrap();
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+                &re,
+                &market_state,
+                &with_fees
+            ),
+            Err(RiskError::Liquidate)
+        );
+
+        // The same holds for a short position, with the liquidation price lowered from 150 to 140.
+        let fee_free = Position::Short(PositionInner::from_parts(
+            qty,
+            entry_price,
+            QuoteCurrency::zero(),
+        ));
+        let with_fees = Position::Short(PositionInner::from_parts(
+            qty,
+            entry_price,
+            QuoteCurrency::new(50, 0),
+        ));
+        let ask = QuoteCurrency::new(145, 0);
+        let market_state =
+            MarketState::from_components(ask - QuoteCurrency::one(), ask, ask, 0.into(), 0);
+        RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+            &re,
+            &market_state,
+            &fee_free,
+        )
+        .unwrap();
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
+                &re,
+                &market_state,
+                &with_fees
+            ),
+            Err(RiskError::Liquidate)
+        );
+    }
+
+    #[test]
+    fn isolated_margin_invalid_maintenance_margin_buffer() {
+        let mut contract_spec =
+            ContractSpecification::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::default();
+        assert_eq!(
+            contract_spec.set_maintenance_margin_buffer(Decimal::try_from_scaled(-1, 1).unwrap()),
+            Err(ConfigError::InvalidMaintenanceMarginBuffer)
+        );
+    }
+
+    #[test]
+    fn isolated_margin_maintenance_margin_buffer() {
+        let mut contract_spec =
+            ContractSpecification::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::default();
+        contract_spec
+            .set_maintenance_margin_buffer(Decimal::try_from_scaled(1, 1).unwrap())
+            .unwrap();
+        let re = IsolatedMarginRiskEngine::new(contract_spec);
+        let position = Position::Long(PositionInner::from_parts(
+            BaseCurrency::new(5, 0),
+            QuoteCurrency::new(100, 0),
+            QuoteCurrency::zero(),
+        ));
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::maintenance_margin(&re, &position),
+            QuoteCurrency::new(275, 0)
+        );
+        assert_eq!(
+            RiskEngine::<_, DECIMALS, _, NoUserOrderId>::maintenance_margin(
+                &re,
+                &Position::Neutral
+            ),
+            QuoteCurrency::zero()
+        );
+    }
+
     #[test_case::test_case(2, 126)]
     #[test_case::test_case(3, 117)]
     #[test_case::test_case(5, 111)]
@@ -453,7 +773,8 @@ mod tests {
             ),
             Err(RiskError::Liquidate)
         );
-        let ask = QuoteCurrency::new(expected_liq_price, 0) - QuoteCurrency::one();
+        // The outstanding fees of the single contract shift the liquidation price by the same amount.
+        let ask = QuoteCurrency::new(expected_liq_price, 0) - QuoteCurrency::one() - fees;
         let bid = ask - QuoteCurrency::one();
         let market_state = MarketState::from_components(bid, ask, ask, 0.into(), 0);
         RiskEngine::<_, DECIMALS, _, NoUserOrderId>::check_maintenance_margin(
@@ -0,0 +1,111 @@
+use const_decimal::Decimal;
+
+use crate::{
+    contract_specification::ContractSpecification,
+    prelude::{Currency, Mon},
+    types::{MarginCurrency, Side},
+};
+
+/// Computes the maintenance margin a position requires,
+/// which allows modelling tiered or offset based formulas used by some exchanges.
+///
+/// # Generics:
+/// - `I`: The numeric data type of `Decimal`.
+/// - `D`: The constant decimal precision.
+/// - `M`: The margin currency.
+pub trait MaintenanceMarginModel<I, const D: u8, M>: Clone + std::fmt::Debug
+where
+    I: Mon<D>,
+    M: MarginCurrency<I, D>,
+{
+    /// The maintenance margin required by a position on `side` with a `notional` value at its entry price.
+    fn required(&self, notional: M, side: Side) -> M;
+
+    /// The maintenance margin required by a position on `side` as a fraction of its `notional` value.
+    fn maintenance_margin_req(&self, notional: M, side: Side) -> Decimal<I, D> {
+        *self.required(notional, side).as_ref() / *notional.as_ref()
+    }
+}
+
+/// The default `MaintenanceMarginModel` which requires a flat fraction of the notional value,
+/// as defined by the `maintenance_margin` of the `ContractSpecification`.
+#[derive(Debug, Clone)]
+pub struct FlatMaintenanceMargin<I, const D: u8>
+where
+    I: Mon<D>,
+{
+    long: Decimal<I, D>,
+    short: Decimal<I, D>,
+}
+
+impl<I, const D: u8> FlatMaintenanceMargin<I, D>
+where
+    I: Mon<D>,
+{
+    /// Create a new instance which requires the fraction `long` of the notional value for long positions
+    /// and `short` for short positions.
+    pub fn new(long: Decimal<I, D>, short: Decimal<I, D>) -> Self {
+        Self { long, short }
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote> From<&ContractSpecification<I, D, BaseOrQuote>>
+    for FlatMaintenanceMargin<I, D>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    fn from(contract_spec: &ContractSpecification<I, D, BaseOrQuote>) -> Self {
+        Self::new(
+            contract_spec.maintenance_margin_for(Side::Buy),
+            contract_spec.maintenance_margin_for(Side::Sell),
+        )
+    }
+}
+
+impl<I, const D: u8, M> MaintenanceMarginModel<I, D, M> for FlatMaintenanceMargin<I, D>
+where
+    I: Mon<D>,
+    M: MarginCurrency<I, D>,
+{
+    #[inline]
+    fn required(&self, notional: M, side: Side) -> M {
+        notional * self.maintenance_margin_req(notional, side)
+    }
+
+    // The rate is returned as is, so no precision is lost by dividing the requirement by the notional value.
+    #[inline]
+    fn maintenance_margin_req(&self, _notional: M, side: Side) -> Decimal<I, D> {
+        match side {
+            Side::Buy => self.long,
+            Side::Sell => self.short,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::QuoteCurrency;
+
+    #[test]
+    fn flat_maintenance_margin() {
+        let model = FlatMaintenanceMargin::<i64, 5>::new(
+            Decimal::try_from_scaled(5, 2).unwrap(),
+            Decimal::try_from_scaled(1, 1).unwrap(),
+        );
+        let notional = QuoteCurrency::<i64, 5>::new(1000, 0);
+        assert_eq!(
+            model.required(notional, Side::Buy),
+            QuoteCurrency::new(50, 0)
+        );
+        assert_eq!(
+            model.required(notional, Side::Sell),
+            QuoteCurrency::new(100, 0)
+        );
+        assert_eq!(
+            model.maintenance_margin_req(notional, Side::Buy),
+            Decimal::try_from_scaled(5, 2).unwrap()
+        );
+    }
+}
@@ -13,7 +13,9 @@
 //!     Position limits are set by the exchange and restrict the maximum number of contracts that any trader can hold for a particular futures contract.
 
 mod isolated_margin;
+mod maintenance_margin_model;
 mod risk_engine_trait;
 
-pub(crate) use isolated_margin::IsolatedMarginRiskEngine;
-pub(crate) use risk_engine_trait::RiskEngine;
+pub use isolated_margin::IsolatedMarginRiskEngine;
+pub use maintenance_margin_model::{FlatMaintenanceMargin, MaintenanceMarginModel};
+pub use risk_engine_trait::RiskEngine;
@@ -1,3 +1,5 @@
+use num::Zero;
+
 use crate::{
     market_state::MarketState,
     order_margin::OrderMargin,
@@ -5,7 +7,10 @@ use crate::{
     types::{LimitOrder, MarginCurrency, MarketOrder, Pending, UserOrderId},
 };
 
-pub(crate) trait RiskEngine<I, const D: u8, BaseOrQuote, UserOrderIdT>
+/// Checks the margin requirements of orders and positions.
+/// The `Exchange` uses the `IsolatedMarginRiskEngine` unless a different implementation
+/// is provided with `Exchange::with_risk_engine`.
+pub trait RiskEngine<I, const D: u8, BaseOrQuote, UserOrderIdT>
 where
     I: Mon<D>,
     BaseOrQuote: Currency<I, D>,
@@ -59,4 +64,22 @@ where
         market_state: &MarketState<I, D>,
         position: &Position<I, D, BaseOrQuote>,
     ) -> Result<(), RiskError>;
+
+    /// The mark price at which the `position` is liquidated by `check_maintenance_margin`, if any.
+    /// Defaults to `None`, which suits risk engines which don't liquidate at a fixed price.
+    fn liquidation_price(
+        &self,
+        _position: &Position<I, D, BaseOrQuote>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        None
+    }
+
+    /// The maintenance margin the `position` requires at its entry price, which `check_maintenance_margin` enforces.
+    /// Defaults to zero, which suits risk engines without a maintenance margin requirement.
+    fn maintenance_margin(
+        &self,
+        _position: &Position<I, D, BaseOrQuote>,
+    ) -> BaseOrQuote::PairedCurrency {
+        BaseOrQuote::PairedCurrency::zero()
+    }
 }
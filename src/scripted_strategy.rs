@@ -0,0 +1,183 @@
+use const_decimal::Decimal;
+
+use crate::{
+    accounting::TransactionAccounting,
+    event_recorder::Event,
+    exchange::{CancelBy, Exchange},
+    prelude::{Currency, MarketUpdate, Mon},
+    risk_engine::RiskEngine,
+    types::{
+        Error, LimitOrder, MarginCurrency, MarketOrder, OrderId, QuoteCurrency, Result, Side,
+        TimestampNs,
+    },
+    utils::NoUserOrderId,
+};
+
+const TIMESTAMP_COL: usize = 0;
+const ACTION_COL: usize = 1;
+const SIDE_COL: usize = 2;
+const PRICE_COL: usize = 3;
+const QUANTITY_COL: usize = 4;
+const ORDER_ID_COL: usize = 5;
+
+/// A strategy defined by a list of timestamped order actions,
+/// which are issued once the market data replayed against the `Exchange` reaches their timestamp.
+/// Useful for regression tests and reproducing bug reports.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+#[derive(Debug, Clone)]
+pub struct ScriptedStrategy<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    actions: Vec<(TimestampNs, Event<I, D, BaseOrQuote, NoUserOrderId>)>,
+}
+
+impl<I, const D: u8, BaseOrQuote> ScriptedStrategy<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+{
+    /// Create a new instance from `actions`, which are sorted by their timestamp.
+    /// Actions with the same timestamp keep their order.
+    /// Only the actions of the user are issued, market data events are ignored by `run`.
+    pub fn new(mut actions: Vec<(TimestampNs, Event<I, D, BaseOrQuote, NoUserOrderId>)>) -> Self {
+        actions.sort_by_key(|(ts_ns, _)| *ts_ns);
+        Self { actions }
+    }
+
+    /// Load the actions from csv with the header `timestamp_ns,action,side,price,quantity,order_id`.
+    /// The `action` is one of `submit_market`, `submit_limit` or `cancel`
+    /// and the columns it does not use are left empty, e.g:
+    ///
+    /// ```text
+    /// timestamp_ns,action,side,price,quantity,order_id
+    /// 1,submit_limit,buy,99,2,
+    /// 2,submit_market,sell,,1,
+    /// 3,cancel,,,,0
+    /// ```
+    pub fn from_csv<R: std::io::Read>(reader: R) -> Result<Self> {
+        let mut r = csv::Reader::from_reader(reader);
+
+        // Make sure that the header matches what we are trying to parse.
+        let head = r
+            .headers()
+            .map_err(|err| Error::InvalidScript(err.to_string()))?;
+        let expected = [
+            (TIMESTAMP_COL, "timestamp_ns"),
+            (ACTION_COL, "action"),
+            (SIDE_COL, "side"),
+            (PRICE_COL, "price"),
+            (QUANTITY_COL, "quantity"),
+            (ORDER_ID_COL, "order_id"),
+        ];
+        if expected
+            .iter()
+            .any(|(col, name)| head.get(*col) != Some(*name))
+        {
+            return Err(Error::InvalidScript(format!("unexpected header {head:?}")));
+        }
+
+        let mut actions = Vec::new();
+        for record in r.records() {
+            let row = record.map_err(|err| Error::InvalidScript(err.to_string()))?;
+            let ts_ns: i64 = parse(&row[TIMESTAMP_COL], "timestamp_ns")?;
+            let event = match &row[ACTION_COL] {
+                "submit_market" => Event::SubmitMarketOrder(MarketOrder::new(
+                    parse_side(&row[SIDE_COL])?,
+                    BaseOrQuote::from(parse::<Decimal<I, D>>(&row[QUANTITY_COL], "quantity")?),
+                )?),
+                "submit_limit" => Event::SubmitLimitOrder(LimitOrder::new(
+                    parse_side(&row[SIDE_COL])?,
+                    QuoteCurrency::from(parse::<Decimal<I, D>>(&row[PRICE_COL], "price")?),
+                    BaseOrQuote::from(parse::<Decimal<I, D>>(&row[QUANTITY_COL], "quantity")?),
+                )?),
+                "cancel" => {
+                    let order_id: u64 = parse(&row[ORDER_ID_COL], "order_id")?;
+                    Event::CancelLimitOrder(CancelBy::OrderId(OrderId::from(order_id)))
+                }
+                action => {
+                    return Err(Error::InvalidScript(format!("unknown action {action}")));
+                }
+            };
+            actions.push((ts_ns.into(), event));
+        }
+
+        Ok(Self::new(actions))
+    }
+
+    /// The scripted actions, sorted by their timestamp.
+    #[inline]
+    pub fn actions(&self) -> &[(TimestampNs, Event<I, D, BaseOrQuote, NoUserOrderId>)] {
+        &self.actions
+    }
+
+    /// Drive the `exchange` with the `market_updates`, issuing each action right after the first market update
+    /// whose timestamp is at or after the timestamp of the action.
+    /// Actions after the last market update are never issued.
+    /// A market update rejected as a bad print, with `Error::PriceMoveTooLarge` or `Error::CrossedMarket`,
+    /// is skipped and the actions due wait for the next accepted one.
+    /// Stops at any other error, either of a market update or an action.
+    pub fn run<U, TransactionAccountingT, RiskEngineT>(
+        &self,
+        exchange: &mut Exchange<
+            I,
+            D,
+            BaseOrQuote,
+            NoUserOrderId,
+            TransactionAccountingT,
+            RiskEngineT,
+        >,
+        market_updates: &[U],
+    ) -> Result<()>
+    where
+        U: MarketUpdate<I, D, BaseOrQuote>,
+        TransactionAccountingT:
+            TransactionAccounting<I, D, BaseOrQuote::PairedCurrency> + std::fmt::Debug,
+        RiskEngineT: RiskEngine<I, D, BaseOrQuote, NoUserOrderId>,
+    {
+        let mut actions = self.actions.iter().peekable();
+        for market_update in market_updates {
+            match exchange.update_state(market_update) {
+                Ok(_) => {}
+                Err(Error::PriceMoveTooLarge | Error::CrossedMarket) => continue,
+                Err(err) => return Err(err),
+            }
+            while let Some((_, event)) =
+                actions.next_if(|(ts_ns, _)| *ts_ns <= market_update.timestamp_exchange_ns())
+            {
+                match event {
+                    Event::Bba(_) | Event::Trade(_) | Event::Candle(_) | Event::SmartCandle(_) => {}
+                    Event::SubmitMarketOrder(_)
+                    | Event::SubmitLimitOrder(_)
+                    | Event::CancelLimitOrder(_)
+                    | Event::AmendLimitOrder { .. }
+                    | Event::AmendOrder { .. }
+                    | Event::AddIsolatedMargin(_)
+                    | Event::RemoveIsolatedMargin(_)
+                    | Event::SetMarkPriceOverride(_) => event.apply(exchange)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str, column: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidScript(format!("can not parse {column} from {value:?}")))
+}
+
+fn parse_side(value: &str) -> Result<Side> {
+    match value {
+        "buy" => Ok(Side::Buy),
+        "sell" => Ok(Side::Sell),
+        _ => Err(Error::InvalidScript(format!("unknown side {value:?}"))),
+    }
+}
@@ -0,0 +1,778 @@
+use const_decimal::Decimal;
+use num_traits::Signed;
+
+use crate::{
+    DECIMALS, mock_config_linear, mock_exchange_inverse, mock_exchange_linear,
+    mock_exchange_linear_with_config, prelude::*, test_fee_maker, test_fee_taker,
+};
+
+#[test]
+#[tracing_test::traced_test]
+fn account_reflects_latest_state() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(1000, 0));
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    let qty = BaseCurrency::new(5, 0);
+    let order = MarketOrder::new(Side::Buy, qty).unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(495, 0));
+    assert_eq!(exchange.position().quantity(), qty);
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(101, 0)
+    );
+
+    let account = exchange.account();
+    assert_eq!(account.position, exchange.position());
+    assert!(account.active_limit_orders.is_empty());
+    assert_eq!(
+        account.balances,
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(495, 0),
+            position_margin: QuoteCurrency::new(505, 0),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+
+    // A new market update is reflected in the unrealized profit and loss.
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(110, 0),
+                ask: QuoteCurrency::new(111, 0),
+                timestamp_exchange_ns: 1.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    let market_state = exchange.market_state();
+    assert_eq!(
+        exchange
+            .position()
+            .unrealized_pnl(market_state.bid(), market_state.ask()),
+        QuoteCurrency::new(45, 0)
+    );
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(495, 0));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_exchange_fees_and_treasury() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.exchange_fees_collected(), QuoteCurrency::zero());
+    assert_eq!(exchange.treasury_balance(), QuoteCurrency::new(-1000, 0));
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+
+    let fee_0 =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(101, 0)) * *test_fee_taker().as_ref();
+    let fee_1 =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(110, 0)) * *test_fee_taker().as_ref();
+    assert_eq!(exchange.exchange_fees_collected(), fee_0 + fee_1);
+    assert_eq!(exchange.exchange_fees_collected(), exchange.fees_paid());
+
+    // The realized profit of the user is paid by the treasury.
+    let pnl = QuoteCurrency::new(45, 0);
+    assert_eq!(
+        exchange.treasury_balance(),
+        QuoteCurrency::new(-1000, 0) - pnl
+    );
+    assert_eq!(
+        exchange.wallet_balance(),
+        QuoteCurrency::new(1000, 0) + pnl - fee_0 - fee_1
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_outstanding_fees() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.outstanding_fees(), QuoteCurrency::zero());
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(2, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    let taker_fee =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(101, 0)) * *test_fee_taker().as_ref();
+    assert_eq!(exchange.outstanding_fees(), taker_fee);
+    assert_eq!(
+        exchange.outstanding_fees(),
+        exchange.position().outstanding_fees()
+    );
+
+    // A filled limit order accrues the maker fee on top.
+    let limit_price = QuoteCurrency::new(100, 0);
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, limit_price, qty).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let maker_fee = QuoteCurrency::convert_from(qty, limit_price) * *test_fee_maker().as_ref();
+    assert_eq!(exchange.outstanding_fees(), taker_fee + maker_fee);
+
+    // Closing the position settles all outstanding fees.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty + qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.outstanding_fees(), QuoteCurrency::zero());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_locked_initial_margin() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.account().locked_initial_margin(),
+        QuoteCurrency::zero()
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.account().locked_initial_margin(),
+        QuoteCurrency::new(100, 0)
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap())
+        .unwrap();
+    // The rounded entry price makes the naive margin deviate from the locked one.
+    let locked = exchange.account().locked_initial_margin();
+    assert_eq!(locked, QuoteCurrency::new(302, 0));
+    assert_ne!(exchange.position().total_cost(), locked);
+    assert!(
+        (exchange.position().total_cost() - locked).abs()
+            <= QuoteCurrency::new(3, 0) * Decimal::try_from_scaled(1, DECIMALS).unwrap()
+    );
+
+    let entry_price = exchange.position().entry_price();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(1, 0)).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.account().locked_initial_margin(),
+        QuoteCurrency::new(302, 0) - entry_price
+    );
+    assert_eq!(
+        exchange.account().locked_initial_margin(),
+        exchange.user_balances().position_margin
+    );
+
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.account().locked_initial_margin(),
+        QuoteCurrency::zero()
+    );
+}
+
+#[test_case::test_case(Side::Buy, QuoteCurrency::new(50, 0), QuoteCurrency::new(111, 0); "long_profit")]
+#[test_case::test_case(Side::Buy, QuoteCurrency::new(-50, 0), QuoteCurrency::new(91, 0); "long_loss")]
+#[test_case::test_case(Side::Sell, QuoteCurrency::new(20, 0), QuoteCurrency::new(96, 0); "short_profit")]
+#[test_case::test_case(Side::Sell, QuoteCurrency::new(-20, 0), QuoteCurrency::new(104, 0); "short_loss")]
+#[tracing_test::traced_test]
+fn account_price_for_target_pnl_linear(
+    side: Side,
+    target: QuoteCurrency<i64, DECIMALS>,
+    expected_price: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.account().price_for_target_pnl(target),
+        Err(Error::NoPosition)
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(side, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+
+    let price = exchange.account().price_for_target_pnl(target).unwrap();
+    assert_eq!(price, expected_price);
+    assert_eq!(exchange.position().unrealized_pnl(price, price), target);
+}
+
+#[test_case::test_case(Side::Buy, BaseCurrency::new(25, 1); "long_profit")]
+#[test_case::test_case(Side::Buy, BaseCurrency::new(-1, 0); "long_loss")]
+#[test_case::test_case(Side::Sell, BaseCurrency::new(1, 0); "short_profit")]
+#[test_case::test_case(Side::Sell, BaseCurrency::new(-1, 0); "short_loss")]
+#[tracing_test::traced_test]
+fn account_price_for_target_pnl_inverse(side: Side, target: BaseCurrency<i64, DECIMALS>) {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(10, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(side, QuoteCurrency::new(500, 0)).unwrap())
+        .unwrap();
+
+    let price = exchange.account().price_for_target_pnl(target).unwrap();
+    assert!(
+        (exchange.position().unrealized_pnl(price, price) - target).abs()
+            <= BaseCurrency::new(1, 4)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_price_for_target_pnl_unreachable() {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(10, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, QuoteCurrency::new(500, 0)).unwrap())
+        .unwrap();
+
+    // A long inverse position can not gain more than its value at entry, even at an infinite price.
+    assert_eq!(
+        exchange
+            .account()
+            .price_for_target_pnl(BaseCurrency::new(5, 0)),
+        Err(Error::UnreachablePnl)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_pnl_conservation_with_seeded_treasury() {
+    let mut config = mock_config_linear();
+    let starting_balance = config.starting_wallet_balance();
+    let seed = QuoteCurrency::new(500, 0);
+    config.set_initial_treasury_balance(seed).unwrap();
+    type LinearExchange = Exchange<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+    >;
+    let mut exchange = LinearExchange::new(config);
+    assert_eq!(exchange.treasury_balance(), seed - starting_balance);
+
+    let assert_zero_sum_minus_fees = |exchange: &LinearExchange| {
+        let balances = exchange.user_balances();
+        let user_change =
+            balances.available_wallet_balance + balances.order_margin + balances.position_margin
+                - starting_balance;
+        let treasury_change = exchange.treasury_balance() - (seed - starting_balance);
+        assert_eq!(user_change + treasury_change, -exchange.fees_paid());
+    };
+
+    let qty = BaseCurrency::new(2, 0);
+    // A winning trade.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_zero_sum_minus_fees(&exchange);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    assert_zero_sum_minus_fees(&exchange);
+    assert_eq!(
+        exchange.treasury_balance(),
+        seed - starting_balance - QuoteCurrency::new(18, 0)
+    );
+
+    // A losing trade.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(120, 0),
+            ask: QuoteCurrency::new(121, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_zero_sum_minus_fees(&exchange);
+    assert_eq!(
+        exchange.treasury_balance(),
+        seed - starting_balance - QuoteCurrency::new(18, 0) + QuoteCurrency::new(22, 0)
+    );
+}
+
+#[test]
+fn account_invalid_initial_treasury_balance() {
+    let mut config = mock_config_linear();
+    assert_eq!(
+        config.set_initial_treasury_balance(QuoteCurrency::new(-1, 0)),
+        Err(ConfigError::InvalidInitialTreasuryBalance)
+    );
+}
+
+#[test_case::test_case(Side::Buy, QuoteCurrency::new(550, 0); "long")]
+#[test_case::test_case(Side::Sell, QuoteCurrency::new(-550, 0); "short")]
+#[tracing_test::traced_test]
+fn account_dollar_delta_linear(side: Side, expected: QuoteCurrency<i64, DECIMALS>) {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.account().dollar_delta(QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(side, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.account().dollar_delta(QuoteCurrency::new(110, 0)),
+        expected
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_dollar_delta_inverse() {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(10, 0));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, QuoteCurrency::new(500, 0)).unwrap())
+        .unwrap();
+
+    // The exposure of the same inverse position shrinks as the price rises.
+    let account = exchange.account();
+    assert_eq!(
+        account.dollar_delta(QuoteCurrency::new(100, 0)),
+        BaseCurrency::new(-5, 0)
+    );
+    assert_eq!(
+        account.dollar_delta(QuoteCurrency::new(200, 0)),
+        BaseCurrency::new(-25, 1)
+    );
+    assert_eq!(
+        account.dollar_delta(QuoteCurrency::new(50, 0)),
+        BaseCurrency::new(-10, 0)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_open_position_count() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.open_position_count(), 0);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.open_position_count(), 1);
+    // Flipping the net position still leaves a single open position.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty + qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.open_position_count(), 1);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.open_position_count(), 0);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_realized_and_unrealized_pnl() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.realized_pnl(), QuoteCurrency::zero());
+    // The long position is marked at the bid.
+    assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(-2, 0));
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::one()).unwrap())
+        .unwrap();
+    assert_eq!(exchange.realized_pnl(), QuoteCurrency::new(9, 0));
+    assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(9, 0));
+    assert_eq!(exchange.total_pnl(), QuoteCurrency::new(18, 0));
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(105, 0),
+            ask: QuoteCurrency::new(106, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.realized_pnl(), QuoteCurrency::new(9, 0));
+    assert_eq!(exchange.unrealized_pnl(), QuoteCurrency::new(4, 0));
+    assert_eq!(exchange.total_pnl(), QuoteCurrency::new(13, 0));
+}
+
+#[test_case::test_case(1, 50)]
+#[test_case::test_case(2, 25)]
+#[test_case::test_case(5, 10)]
+fn account_distance_to_liquidation(leverage: u8, expected_distance: i64) {
+    let contract_spec = ContractSpecification::new(
+        Leverage::new(leverage).unwrap(),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::default(),
+        Fee::from(Decimal::ZERO),
+        Fee::from(Decimal::ZERO),
+    )
+    .unwrap();
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let mark_price = QuoteCurrency::new(100, 0);
+    assert_eq!(exchange.account().distance_to_liquidation(mark_price), None);
+
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::one()).unwrap())
+        .unwrap();
+    // The distance shrinks as the leverage rises.
+    assert_eq!(
+        exchange.account().distance_to_liquidation(mark_price),
+        Some((
+            QuoteCurrency::new(expected_distance, 0),
+            Decimal::try_from_scaled(expected_distance, 0).unwrap()
+        ))
+    );
+    assert_eq!(
+        exchange
+            .account()
+            .distance_to_liquidation(QuoteCurrency::new(10, 0)),
+        Some((QuoteCurrency::zero(), Decimal::ZERO))
+    );
+}
+
+#[test]
+fn account_distance_to_liquidation_short() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::one()).unwrap())
+        .unwrap();
+    // The liquidation price of 150 is lowered by the outstanding fees of 0.06.
+    assert_eq!(
+        exchange.account().liquidation_price,
+        Some(QuoteCurrency::new(14994, 2))
+    );
+    assert_eq!(
+        exchange
+            .account()
+            .distance_to_liquidation(QuoteCurrency::new(125, 0)),
+        Some((
+            QuoteCurrency::new(2494, 2),
+            Decimal::try_from_scaled(19952, 3).unwrap()
+        ))
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_margin_and_leverage_utilization() {
+    let mut exchange = mock_exchange_linear();
+    let mark_price = QuoteCurrency::new(100, 0);
+    let max_leverage = leverage!(1);
+    let account = exchange.account();
+    assert_eq!(account.margin_utilization(), Decimal::ZERO);
+    assert_eq!(
+        account.leverage_utilization(mark_price, max_leverage),
+        Decimal::ZERO
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: mark_price,
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    // Half of the wallet balance is committed to the position.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    let half = Decimal::try_from_scaled(5, 1).unwrap();
+    let account = exchange.account();
+    assert_eq!(account.margin_utilization(), half);
+    assert_eq!(account.leverage_utilization(mark_price, max_leverage), half);
+    // Only a quarter of a twice as large leverage is used.
+    assert_eq!(
+        account.leverage_utilization(mark_price, leverage!(2)),
+        Decimal::try_from_scaled(25, 2).unwrap()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_margin_breakdown() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.account().margin_breakdown(),
+        MarginBreakdown {
+            initial_margin: QuoteCurrency::zero(),
+            maintenance_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            outstanding_fees: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData,
+        }
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(90, 0),
+                BaseCurrency::new(2, 0),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let fee =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(101, 0)) * *test_fee_taker().as_ref();
+    assert_eq!(
+        exchange.account().margin_breakdown(),
+        MarginBreakdown {
+            initial_margin: QuoteCurrency::new(505, 0),
+            maintenance_margin: QuoteCurrency::new(2525, 1),
+            order_margin: QuoteCurrency::new(180, 0),
+            outstanding_fees: fee,
+            _q: std::marker::PhantomData,
+        }
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn account_entry_and_exit_fees() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.account().entry_fees_paid(), QuoteCurrency::zero());
+    assert_eq!(
+        exchange
+            .account()
+            .estimated_exit_fees(QuoteCurrency::new(100, 0)),
+        QuoteCurrency::zero()
+    );
+
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+
+    let account = exchange.account();
+    assert_eq!(
+        account.entry_fees_paid(),
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(100, 0)) * *test_fee_taker().as_ref()
+    );
+    // A short position is exited by buying at the ask.
+    assert_eq!(
+        account.estimated_exit_fees(QuoteCurrency::new(111, 0)),
+        QuoteCurrency::new(333, 3)
+    );
+}
+
+#[test_case::test_case(FeeMode::Deferred; "deferred")]
+#[test_case::test_case(FeeMode::Upfront; "upfront")]
+#[tracing_test::traced_test]
+fn account_entry_fees_paid(fee_mode: FeeMode) {
+    let mut config = mock_config_linear();
+    config.set_fee_mode(fee_mode);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let fee = |qty: i64, price: i64| {
+        QuoteCurrency::convert_from(BaseCurrency::new(qty, 0), QuoteCurrency::new(price, 0))
+            * *test_fee_taker().as_ref()
+    };
+
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.account().entry_fees_paid(), fee(5, 101));
+
+    // Reducing the position keeps its entry fees, while increasing it adds to them.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.account().entry_fees_paid(), fee(5, 101));
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.account().entry_fees_paid(),
+        fee(5, 101) + fee(1, 101)
+    );
+
+    // Flipping the position only attributes the fee of the new quantity.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(6, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.account().entry_fees_paid(), fee(2, 100));
+}
@@ -15,6 +15,7 @@ fn amend_limit_order_qty(new_qty: BaseCurrency<i64, DECIMALS>) {
                 timestamp_exchange_ns: 1.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let order = LimitOrder::new(
@@ -54,6 +55,7 @@ fn amend_limit_order_qty_with_partial_fill_leading_to_cancel(new_qty: BaseCurren
                 timestamp_exchange_ns: 1.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let order = LimitOrder::new(
@@ -104,6 +106,7 @@ fn amend_limit_order_qty_with_partial_fill(new_qty: BaseCurrency<i64, DECIMALS>)
                 timestamp_exchange_ns: 1.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let order = LimitOrder::new(
@@ -148,3 +151,76 @@ fn amend_limit_order_qty_with_partial_fill(new_qty: BaseCurrency<i64, DECIMALS>)
         BaseCurrency::new(2, 0) + delta
     );
 }
+
+#[tracing_test::traced_test]
+#[test]
+fn amend_order_priority() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    for price in [99, 98] {
+        exchange
+            .submit_limit_order(
+                LimitOrder::new(Side::Buy, QuoteCurrency::new(price, 0), qty).unwrap(),
+            )
+            .unwrap();
+    }
+    let ids =
+        |orders: &ActiveLimitOrders<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>| {
+            orders.values().map(|order| order.id()).collect::<Vec<_>>()
+        };
+    assert_eq!(
+        ids(exchange.active_limit_orders()),
+        vec![0.into(), 1.into()]
+    );
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        QuoteCurrency::new(985, 0)
+    );
+
+    // Only reducing the quantity keeps the `OrderId` and the queue priority.
+    exchange
+        .amend_order(0.into(), QuoteCurrency::new(99, 0), BaseCurrency::new(3, 0))
+        .unwrap();
+    assert_eq!(
+        ids(exchange.active_limit_orders()),
+        vec![0.into(), 1.into()]
+    );
+    let order = exchange.active_limit_orders().get_by_id(0.into()).unwrap();
+    assert_eq!(order.remaining_quantity(), BaseCurrency::new(3, 0));
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        QuoteCurrency::new(787, 0)
+    );
+
+    // Changing the price resets the queue priority with a new `OrderId`.
+    exchange
+        .amend_order(0.into(), QuoteCurrency::new(97, 0), BaseCurrency::new(3, 0))
+        .unwrap();
+    assert_eq!(
+        ids(exchange.active_limit_orders()),
+        vec![1.into(), 2.into()]
+    );
+    let order = exchange.active_limit_orders().get_by_id(2.into()).unwrap();
+    assert_eq!(order.limit_price(), QuoteCurrency::new(97, 0));
+    assert_eq!(order.remaining_quantity(), BaseCurrency::new(3, 0));
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        QuoteCurrency::new(781, 0)
+    );
+
+    assert_eq!(
+        exchange.amend_order(0.into(), QuoteCurrency::new(97, 0), qty),
+        Err(Error::OrderNoLongerActive)
+    );
+    assert_eq!(
+        exchange.amend_order(5.into(), QuoteCurrency::new(97, 0), qty),
+        Err(Error::OrderIdNotFound { order_id: 5.into() })
+    );
+}
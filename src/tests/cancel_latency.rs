@@ -0,0 +1,102 @@
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_bba};
+
+fn config_with_cancel_latency() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config.set_cancel_latency(Some(10.into())).unwrap();
+    config
+}
+
+fn sell_trade(timestamp_exchange_ns: i64) -> Trade<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> {
+    Trade {
+        price: QuoteCurrency::new(99, 0),
+        quantity: BaseCurrency::one(),
+        side: Side::Sell,
+        timestamp_exchange_ns: timestamp_exchange_ns.into(),
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cancel_latency_order_fills_before_cancel() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_cancel_latency());
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+    exchange.update_state(&test_bba(100, 101, 2)).unwrap();
+    assert_eq!(
+        exchange
+            .cancel_limit_order(CancelBy::OrderId(order.id()))
+            .unwrap(),
+        order
+    );
+    // The order remains active until the cancel reaches the exchange.
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // A fill within the latency window is not prevented by the late cancel.
+    let updates = exchange
+        .update_state(&sell_trade(5))
+        .unwrap()
+        .limit_order_updates;
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert_eq!(exchange.position().quantity(), BaseCurrency::one());
+
+    assert!(
+        exchange
+            .update_state(&test_bba(100, 101, 12))
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    assert_eq!(exchange.position().quantity(), BaseCurrency::one());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cancel_latency_cancel_takes_effect_after_latency() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_cancel_latency());
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+    exchange
+        .cancel_limit_order(CancelBy::OrderId(order.id()))
+        .unwrap();
+    assert!(
+        exchange
+            .update_state(&test_bba(100, 101, 9))
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // Once the latency elapsed, the order is cancelled before it can be filled.
+    let updates = exchange
+        .update_state(&sell_trade(10))
+        .unwrap()
+        .limit_order_updates;
+    assert_eq!(updates, &vec![LimitOrderUpdate::Cancelled(order)]);
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.user_balances().order_margin, QuoteCurrency::zero());
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(1000, 0));
+}
+
+#[test]
+fn cancel_latency_invalid() {
+    let mut config = mock_config_linear();
+    assert_eq!(
+        config.set_cancel_latency(Some(0.into())),
+        Err(ConfigError::InvalidCancelLatency)
+    );
+}
@@ -0,0 +1,279 @@
+use const_decimal::Decimal;
+
+use crate::{
+    DECIMALS, mock_exchange_linear, mock_exchange_linear_with_config, prelude::*, test_bba,
+    test_fee_taker,
+};
+
+fn taker_fees(fills: &[(i64, i64)]) -> QuoteCurrency<i64, DECIMALS> {
+    fills
+        .iter()
+        .map(|(qty, price)| {
+            QuoteCurrency::convert_from(BaseCurrency::new(*qty, 0), QuoteCurrency::new(*price, 0))
+                * *test_fee_taker().as_ref()
+        })
+        .fold(QuoteCurrency::zero(), |acc, fee| acc + fee)
+}
+
+fn market_order(
+    side: Side,
+    qty: i64,
+) -> MarketOrder<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId, NewOrder> {
+    MarketOrder::new(side, BaseCurrency::new(qty, 0)).unwrap()
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_long_round_trip() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    assert!(exchange.closed_trades().is_empty());
+
+    exchange.update_state(&test_bba(105, 106, 10)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.side(), Side::Buy);
+    assert_eq!(trade.quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(trade.entry_price(), QuoteCurrency::new(101, 0));
+    assert_eq!(trade.exit_price(), QuoteCurrency::new(105, 0));
+    assert_eq!(trade.gross_pnl(), QuoteCurrency::new(4, 0));
+    let fees = taker_fees(&[(1, 101), (1, 105)]);
+    assert_eq!(trade.fees(), fees);
+    assert_eq!(trade.net_pnl(), QuoteCurrency::new(4, 0) - fees);
+    assert_eq!(trade.entry_ts_ns(), 0.into());
+    assert_eq!(trade.exit_ts_ns(), 10.into());
+    assert_eq!(trade.holding_period_ns(), 10.into());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_scaled_in_then_out() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(102, 103, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(110, 111, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    assert!(exchange.closed_trades().is_empty());
+    exchange.update_state(&test_bba(108, 109, 3)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.side(), Side::Buy);
+    assert_eq!(trade.quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(trade.entry_price(), QuoteCurrency::new(102, 0));
+    assert_eq!(trade.exit_price(), QuoteCurrency::new(109, 0));
+    assert_eq!(trade.gross_pnl(), QuoteCurrency::new(14, 0));
+    assert_eq!(
+        trade.fees(),
+        taker_fees(&[(1, 101), (1, 103), (1, 110), (1, 108)])
+    );
+    assert_eq!(trade.holding_period_ns(), 3.into());
+}
+
+// Increasing the position again after reducing it is part of the same net trade.
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_reduced_then_increased() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 2))
+        .unwrap();
+    exchange.update_state(&test_bba(110, 111, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(112, 113, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(116, 117, 3)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 2))
+        .unwrap();
+
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.quantity(), BaseCurrency::new(3, 0));
+    assert_eq!(trade.entry_price(), QuoteCurrency::new(105, 0));
+    assert_eq!(trade.exit_price(), QuoteCurrency::new(114, 0));
+    // 9 on the first reduction and 2 * 9 relative to the raised entry price of 107.
+    assert_eq!(trade.gross_pnl(), QuoteCurrency::new(27, 0));
+    assert_eq!(trade.holding_period_ns(), 3.into());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_flip() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(100, 101, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 3))
+        .unwrap();
+
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].side(), Side::Buy);
+    assert_eq!(trades[0].quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(trades[0].gross_pnl(), QuoteCurrency::new(-1, 0));
+    // The fee of the flipping fill is part of the trade it closes.
+    assert_eq!(trades[0].fees(), taker_fees(&[(1, 101), (3, 100)]));
+
+    exchange.update_state(&test_bba(95, 96, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 2))
+        .unwrap();
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[1].side(), Side::Sell);
+    assert_eq!(trades[1].quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(trades[1].entry_price(), QuoteCurrency::new(100, 0));
+    assert_eq!(trades[1].exit_price(), QuoteCurrency::new(96, 0));
+    assert_eq!(trades[1].gross_pnl(), QuoteCurrency::new(8, 0));
+    assert_eq!(trades[1].fees(), taker_fees(&[(2, 96)]));
+    assert_eq!(trades[1].entry_ts_ns(), 1.into());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_weighted_exit_price() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 3))
+        .unwrap();
+    exchange.update_state(&test_bba(105, 106, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(108, 109, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 2))
+        .unwrap();
+
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 1);
+    // (1 * 105 + 2 * 108) / 3
+    assert_eq!(trades[0].exit_price(), QuoteCurrency::new(107, 0));
+
+    // The next position starts with a fresh exit price.
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(110, 111, 3)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    let trades = exchange.closed_trades();
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[1].entry_price(), QuoteCurrency::new(109, 0));
+    assert_eq!(trades[1].exit_price(), QuoteCurrency::new(110, 0));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_long_and_short_realized_pnl() {
+    let mut exchange = mock_exchange_linear();
+    assert_eq!(exchange.long_realized_pnl(), QuoteCurrency::zero());
+    assert_eq!(exchange.short_realized_pnl(), QuoteCurrency::zero());
+
+    // A winning long.
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 2))
+        .unwrap();
+    exchange.update_state(&test_bba(105, 106, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 2))
+        .unwrap();
+    // A losing short.
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(110, 111, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+
+    assert_eq!(exchange.long_realized_pnl(), QuoteCurrency::new(8, 0));
+    assert_eq!(exchange.short_realized_pnl(), QuoteCurrency::new(-6, 0));
+    assert_eq!(
+        exchange.long_realized_pnl() + exchange.short_realized_pnl(),
+        exchange.realized_pnl()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn closed_trades_expectancy() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        Fee::from(Decimal::zero()),
+        Fee::from(Decimal::zero()),
+    )
+    .expect("works");
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    assert_eq!(exchange.expectancy(), None);
+
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(105, 106, 1)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(108, 109, 2)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Sell, 1))
+        .unwrap();
+    exchange.update_state(&test_bba(110, 111, 3)).unwrap();
+    exchange
+        .submit_market_order(market_order(Side::Buy, 1))
+        .unwrap();
+
+    // Two wins of 4 and 2 and a loss of 3: 2/3 * 3 - 1/3 * 3 = 1.
+    assert_eq!(exchange.closed_trades().len(), 3);
+    assert_eq!(exchange.expectancy(), Some(QuoteCurrency::new(1, 0)));
+}
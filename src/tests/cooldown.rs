@@ -0,0 +1,85 @@
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_bba};
+
+fn config_with_cooldown() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config.set_cooldown(Some(10.into())).unwrap();
+    config
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cooldown_rejects_entry_until_elapsed() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_cooldown());
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    // Without a prior close, the first entry is not affected.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    exchange.update_state(&test_bba(100, 101, 5)).unwrap();
+    assert_eq!(
+        exchange
+            .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+            .unwrap_err(),
+        Error::CooldownActive
+    );
+    assert_eq!(
+        exchange
+            .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(99, 0), qty).unwrap())
+            .unwrap_err(),
+        Error::CooldownActive
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    exchange.update_state(&test_bba(100, 101, 10)).unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cooldown_exempts_closing_orders() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_cooldown());
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    // Flipping the position closes the long one, which starts the cooldown.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty + qty).unwrap())
+        .unwrap();
+    assert!(matches!(exchange.position(), Position::Short(_)));
+
+    exchange.update_state(&test_bba(100, 101, 1)).unwrap();
+    assert_eq!(
+        exchange
+            .submit_market_order(MarketOrder::new(Side::Buy, qty + qty).unwrap())
+            .unwrap_err(),
+        Error::CooldownActive
+    );
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(99, 0), qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+fn cooldown_invalid() {
+    let mut config = mock_config_linear();
+    assert_eq!(
+        config.set_cooldown(Some(0.into())),
+        Err(ConfigError::InvalidCooldown)
+    );
+    assert_eq!(config.cooldown(), None);
+}
@@ -0,0 +1,56 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_bba};
+
+fn config_with_policy(
+    policy: CrossedMarketPolicy,
+) -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config.set_crossed_market_policy(policy);
+    config
+}
+
+#[tracing_test::traced_test]
+#[test_case(102, 100; "crossed")]
+#[test_case(101, 101; "locked")]
+fn crossed_market_reject(bid: i64, ask: i64) {
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_policy(CrossedMarketPolicy::Reject));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    assert_eq!(
+        exchange.update_state(&Bba {
+            bid: QuoteCurrency::new(bid, 0),
+            ask: QuoteCurrency::new(ask, 0),
+            timestamp_exchange_ns: 1.into(),
+        }),
+        Err(Error::CrossedMarket)
+    );
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(100, 0));
+    assert_eq!(exchange.market_state().ask(), QuoteCurrency::new(101, 0));
+    assert_eq!(exchange.market_state().current_timestamp_ns(), 0.into());
+}
+
+#[tracing_test::traced_test]
+#[test_case(102, 100, 101, 102; "crossed")]
+#[test_case(105, 100, 102, 103; "crossed rounds mid down")]
+#[test_case(101, 101, 101, 102; "locked")]
+fn crossed_market_clamp(bid: i64, ask: i64, expected_bid: i64, expected_ask: i64) {
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_policy(CrossedMarketPolicy::Clamp));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(bid, 0),
+            ask: QuoteCurrency::new(ask, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.market_state().bid(),
+        QuoteCurrency::new(expected_bid, 0)
+    );
+    assert_eq!(
+        exchange.market_state().ask(),
+        QuoteCurrency::new(expected_ask, 0)
+    );
+}
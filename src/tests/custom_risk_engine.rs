@@ -0,0 +1,140 @@
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear, prelude::*};
+
+/// A `RiskEngine` which checks orders like the isolated margin one, but never liquidates.
+#[derive(Debug, Clone)]
+struct NeverLiquidate {
+    inner: IsolatedMarginRiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+}
+
+impl RiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId> for NeverLiquidate {
+    fn check_market_order(
+        &self,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        position_margin: QuoteCurrency<i64, DECIMALS>,
+        order: &MarketOrder<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+            Pending<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        >,
+        fill_price: QuoteCurrency<i64, DECIMALS>,
+        available_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+    ) -> std::result::Result<(), RiskError> {
+        self.inner.check_market_order(
+            position,
+            position_margin,
+            order,
+            fill_price,
+            available_wallet_balance,
+        )
+    }
+
+    fn check_limit_order(
+        &self,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        order: &LimitOrder<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+            Pending<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        >,
+        available_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+        order_margin: &OrderMargin<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>,
+    ) -> std::result::Result<(), RiskError> {
+        self.inner
+            .check_limit_order(position, order, available_wallet_balance, order_margin)
+    }
+
+    fn check_maintenance_margin(
+        &self,
+        _market_state: &MarketState<i64, DECIMALS>,
+        _position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+    ) -> std::result::Result<(), RiskError> {
+        Ok(())
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn custom_risk_engine_never_liquidates() {
+    let config = mock_config_linear();
+    let risk_engine = NeverLiquidate {
+        inner: IsolatedMarginRiskEngine::new(config.contract_spec().clone()),
+    };
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+        NeverLiquidate,
+    >::with_risk_engine(config, risk_engine);
+    let mut default_exchange = mock_exchange_linear();
+
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    };
+    exchange.update_state(&bba).unwrap();
+    default_exchange.update_state(&bba).unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    default_exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+
+    // The long position has a liquidation price of 50.5.
+    let bba = Bba {
+        bid: QuoteCurrency::new(40, 0),
+        ask: QuoteCurrency::new(41, 0),
+        timestamp_exchange_ns: 1.into(),
+    };
+    assert!(default_exchange.update_state(&bba).unwrap().liquidated);
+    assert_eq!(default_exchange.position(), &Position::Neutral);
+
+    exchange.update_state(&bba).unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(101, 0)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn custom_risk_engine_replay() {
+    let config = mock_config_linear();
+    let risk_engine = NeverLiquidate {
+        inner: IsolatedMarginRiskEngine::new(config.contract_spec().clone()),
+    };
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+        NeverLiquidate,
+    >::with_risk_engine(config, risk_engine);
+
+    // The long position has a liquidation price of 50.5, which the `RiskEngine` ignores.
+    let events = [
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        }),
+        Event::SubmitMarketOrder(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap()),
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 1.into(),
+        }),
+    ];
+    replay(&mut exchange, &events);
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(5, 0));
+}
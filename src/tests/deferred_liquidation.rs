@@ -0,0 +1,119 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn deferred_liquidation_fill_cures_breach() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    // The long position has a liquidation price of 50.5.
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Sell, QuoteCurrency::new(105, 0), qty).unwrap())
+        .unwrap();
+
+    // The candle closes below the liquidation price, but its high fills the closing order first.
+    let updates = exchange
+        .update_state(
+            &Candle::new(
+                QuoteCurrency::new(50, 0),
+                QuoteCurrency::new(51, 0),
+                QuoteCurrency::new(50, 0),
+                QuoteCurrency::new(106, 0),
+                1.into(),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .limit_order_updates;
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(updates[0], LimitOrderUpdate::FullyFilled(_)));
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn deferred_liquidation_without_cure() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Sell, QuoteCurrency::new(105, 0), qty).unwrap())
+        .unwrap();
+
+    // The high of the candle does not reach the limit price, so the position is liquidated.
+    let res = exchange.update_state(
+        &Candle::new(
+            QuoteCurrency::new(50, 0),
+            QuoteCurrency::new(51, 0),
+            QuoteCurrency::new(50, 0),
+            QuoteCurrency::new(104, 0),
+            1.into(),
+        )
+        .unwrap(),
+    );
+    assert!(res.unwrap().liquidated);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn deferred_liquidation_reports_fills() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(60, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+
+    // The low of the candle fills the order, which does not cure the breach at the close.
+    let outcome = exchange
+        .update_state(
+            &Candle::new(
+                QuoteCurrency::new(40, 0),
+                QuoteCurrency::new(41, 0),
+                QuoteCurrency::new(40, 0),
+                QuoteCurrency::new(101, 0),
+                1.into(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    assert!(outcome.liquidated);
+    let fills: Vec<_> = outcome.fills().collect();
+    assert_eq!(fills.len(), 1);
+    let LimitOrderUpdate::FullyFilled(filled) = fills[0] else {
+        panic!("Expected a full fill");
+    };
+    assert_eq!(filled.id(), order.id());
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
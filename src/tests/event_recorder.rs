@@ -0,0 +1,198 @@
+use crate::{DECIMALS, mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn event_recorder_replay() {
+    let mut exchange = mock_exchange_linear();
+    let mut recorder = EventRecorder::default();
+
+    let events: Vec<Event<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>> = vec![
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        }),
+        Event::SubmitMarketOrder(MarketOrder::new(Side::Buy, BaseCurrency::new(3, 0)).unwrap()),
+        Event::SubmitLimitOrder(
+            LimitOrder::new(
+                Side::Sell,
+                QuoteCurrency::new(105, 0),
+                BaseCurrency::new(2, 0),
+            )
+            .unwrap(),
+        ),
+        Event::SubmitLimitOrder(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(95, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        ),
+        // Rejected for insufficient balance, which is part of the recorded session.
+        Event::SubmitMarketOrder(MarketOrder::new(Side::Buy, BaseCurrency::new(100, 0)).unwrap()),
+        Event::Trade(Trade {
+            price: QuoteCurrency::new(106, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 1.into(),
+        }),
+        Event::CancelLimitOrder(CancelBy::OrderId(2.into())),
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(107, 0),
+            ask: QuoteCurrency::new(108, 0),
+            timestamp_exchange_ns: 2.into(),
+        }),
+    ];
+    let mut num_errors = 0;
+    for event in events {
+        if recorder.apply(&mut exchange, event).is_err() {
+            num_errors += 1;
+        }
+    }
+    assert_eq!(num_errors, 1);
+    assert_eq!(recorder.events().len(), 8);
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    let mut replayed = mock_exchange_linear();
+    replay(&mut replayed, recorder.events());
+    assert_eq!(replayed.user_balances(), exchange.user_balances());
+    assert_eq!(replayed.position(), exchange.position());
+    assert_eq!(
+        replayed.active_limit_orders(),
+        exchange.active_limit_orders()
+    );
+    assert_eq!(replayed.fees_paid(), exchange.fees_paid());
+    assert_eq!(replayed.treasury_balance(), exchange.treasury_balance());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn event_recorder_replay_amends_and_margin() {
+    let mut exchange = mock_exchange_linear();
+    let mut recorder = EventRecorder::default();
+
+    let smart_candle = SmartCandle::new(
+        &[Trade {
+            price: QuoteCurrency::new(106, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 1.into(),
+        }],
+        Bba {
+            bid: QuoteCurrency::new(105, 0),
+            ask: QuoteCurrency::new(106, 0),
+            timestamp_exchange_ns: 1.into(),
+        },
+        exchange.config().contract_spec().price_filter(),
+    );
+    let events: Vec<Event<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>> = vec![
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        }),
+        Event::SubmitMarketOrder(MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap()),
+        Event::SubmitLimitOrder(
+            LimitOrder::new(
+                Side::Sell,
+                QuoteCurrency::new(105, 0),
+                BaseCurrency::new(2, 0),
+            )
+            .unwrap(),
+        ),
+        Event::AmendOrder {
+            existing_order_id: 1.into(),
+            new_price: QuoteCurrency::new(105, 0),
+            new_quantity: BaseCurrency::new(1, 0),
+        },
+        Event::SubmitLimitOrder(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(95, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        ),
+        Event::AmendLimitOrder {
+            existing_order_id: 2.into(),
+            new_order: LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(96, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        },
+        Event::AddIsolatedMargin(QuoteCurrency::new(10, 0)),
+        Event::RemoveIsolatedMargin(QuoteCurrency::new(5, 0)),
+        Event::SetMarkPriceOverride(Some(QuoteCurrency::new(99, 0))),
+        Event::SmartCandle(smart_candle),
+    ];
+    for event in events {
+        recorder.apply(&mut exchange, event).unwrap();
+    }
+    assert_eq!(recorder.events().len(), 10);
+
+    let mut replayed = mock_exchange_linear();
+    replay(&mut replayed, recorder.events());
+    assert_eq!(replayed.user_balances(), exchange.user_balances());
+    assert_eq!(replayed.position(), exchange.position());
+    assert_eq!(
+        replayed.active_limit_orders(),
+        exchange.active_limit_orders()
+    );
+    assert_eq!(
+        replayed.market_state().mark_price_override(),
+        exchange.market_state().mark_price_override()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn event_recorder_serde_round_trip() {
+    let mut exchange = mock_exchange_linear();
+    let mut recorder = EventRecorder::default();
+    let events: Vec<Event<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>> = vec![
+        Event::Bba(Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        }),
+        Event::SubmitMarketOrder(MarketOrder::new(Side::Buy, BaseCurrency::new(2, 0)).unwrap()),
+        Event::SubmitLimitOrder(
+            LimitOrder::new(
+                Side::Sell,
+                QuoteCurrency::new(105, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        ),
+        Event::AddIsolatedMargin(QuoteCurrency::new(10, 0)),
+        Event::Trade(Trade {
+            price: QuoteCurrency::new(106, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 1.into(),
+        }),
+        Event::CancelLimitOrder(CancelBy::OrderId(1.into())),
+    ];
+    for event in events {
+        let _ = recorder.apply(&mut exchange, event);
+    }
+
+    // The recorded session can be stored and loaded again to replay it.
+    let serialized = ron::to_string(recorder.events()).unwrap();
+    let deserialized: Vec<Event<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>> =
+        ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.len(), recorder.events().len());
+
+    let mut replayed = mock_exchange_linear();
+    replay(&mut replayed, &deserialized);
+    assert_eq!(replayed.user_balances(), exchange.user_balances());
+    assert_eq!(replayed.position(), exchange.position());
+    assert_eq!(
+        replayed.active_limit_orders(),
+        exchange.active_limit_orders()
+    );
+}
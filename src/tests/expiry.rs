@@ -0,0 +1,119 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_contract_spec_linear, mock_exchange_linear_with_config, prelude::*};
+
+fn config_with_expiry() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut contract_spec = mock_contract_spec_linear();
+    contract_spec
+        .set_expiry(10.into(), QuoteCurrency::new(110, 0))
+        .unwrap();
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    config
+}
+
+#[test]
+fn expiry_invalid_settlement_price() {
+    let mut contract_spec =
+        ContractSpecification::<i64, DECIMALS, BaseCurrency<_, DECIMALS>>::default();
+    assert_eq!(contract_spec.expiry(), None);
+    assert_eq!(
+        contract_spec.set_expiry(10.into(), QuoteCurrency::zero()),
+        Err(ConfigError::InvalidSettlementPrice)
+    );
+    assert_eq!(contract_spec.settlement_price(), None);
+}
+
+#[tracing_test::traced_test]
+#[test_case(Side::Buy, QuoteCurrency::new(45, 0), QuoteCurrency::new(303, 3); "long")]
+#[test_case(Side::Sell, QuoteCurrency::new(-50, 0), QuoteCurrency::new(3, 1); "short")]
+fn expiry_settles_position(
+    side: Side,
+    expected_pnl: QuoteCurrency<i64, DECIMALS>,
+    expected_fees: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_expiry());
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(side, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(90, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    // Before the expiry the contract trades as usual.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(105, 0),
+            ask: QuoteCurrency::new(106, 0),
+            timestamp_exchange_ns: 9.into(),
+        })
+        .unwrap();
+    assert!(!exchange.is_expired());
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // The position is settled at the settlement price rather than the market price.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(120, 0),
+            ask: QuoteCurrency::new(121, 0),
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    assert!(exchange.is_expired());
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.realized_pnl(), expected_pnl);
+    assert_eq!(exchange.fees_paid(), expected_fees);
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(1000, 0) + expected_pnl - expected_fees,
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData,
+        }
+    );
+
+    assert_eq!(
+        exchange.submit_market_order(MarketOrder::new(side, BaseCurrency::new(1, 0)).unwrap()),
+        Err(Error::ContractExpired)
+    );
+    assert_eq!(
+        exchange.submit_limit_order(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(100, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap()
+        ),
+        Err(Error::ContractExpired)
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(130, 0),
+            ask: QuoteCurrency::new(131, 0),
+            timestamp_exchange_ns: 11.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.realized_pnl(), expected_pnl);
+}
@@ -0,0 +1,189 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_config_linear, prelude::*, test_fee_maker};
+
+type TestExchange = Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    NoUserOrderId,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+>;
+
+fn mock_exchange_linear_with_fee_mode(fee_mode: FeeMode) -> TestExchange {
+    let mut config = mock_config_linear();
+    config.set_fee_mode(fee_mode);
+    Exchange::new(config)
+}
+
+#[test]
+fn fee_mode_default() {
+    let config = Config::<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        ContractSpecification::default(),
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    assert_eq!(config.fee_mode(), FeeMode::Deferred);
+}
+
+#[tracing_test::traced_test]
+#[test_case(FeeMode::Deferred, QuoteCurrency::new(495, 0), QuoteCurrency::new(303, 3); "deferred")]
+#[test_case(FeeMode::Upfront, QuoteCurrency::new(494697, 3), QuoteCurrency::zero(); "upfront")]
+fn fee_mode_market_orders(
+    fee_mode: FeeMode,
+    expected_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+    expected_outstanding_fees: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_fee_mode(fee_mode);
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+
+    let qty = BaseCurrency::new(5, 0);
+    let order = MarketOrder::new(Side::Buy, qty).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let fee0 = QuoteCurrency::new(303, 3);
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: expected_wallet_balance,
+            position_margin: QuoteCurrency::new(505, 0),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+    assert_eq!(
+        exchange.position().outstanding_fees(),
+        expected_outstanding_fees
+    );
+    assert_eq!(exchange.fees_paid(), fee0 - expected_outstanding_fees);
+
+    // Closing the position leaves both modes with the same balances.
+    let order = MarketOrder::new(Side::Sell, qty).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    let fee1 = QuoteCurrency::new(3, 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(994397, 3),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+    assert_eq!(exchange.fees_paid(), fee0 + fee1);
+}
+
+#[tracing_test::traced_test]
+#[test_case(FeeMode::Deferred, QuoteCurrency::new(500, 0), QuoteCurrency::new(1, 1); "deferred")]
+#[test_case(FeeMode::Upfront, QuoteCurrency::new(4999, 1), QuoteCurrency::zero(); "upfront")]
+fn fee_mode_limit_order(
+    fee_mode: FeeMode,
+    expected_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+    expected_outstanding_fees: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_fee_mode(fee_mode);
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+
+    let qty = BaseCurrency::new(5, 0);
+    let order = LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(exchange.fees_paid(), QuoteCurrency::zero());
+
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap()
+        .limit_order_updates;
+    assert_eq!(updates.len(), 1);
+
+    let fee =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(100, 0)) * *test_fee_maker().as_ref();
+    assert_eq!(fee, QuoteCurrency::new(1, 1));
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: expected_wallet_balance,
+            position_margin: QuoteCurrency::new(500, 0),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+    assert_eq!(
+        exchange.position().outstanding_fees(),
+        expected_outstanding_fees
+    );
+    assert_eq!(exchange.fees_paid(), fee - expected_outstanding_fees);
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn fee_mode_equity_trajectory() {
+    let mut deferred = mock_exchange_linear_with_fee_mode(FeeMode::Deferred);
+    let mut upfront = mock_exchange_linear_with_fee_mode(FeeMode::Upfront);
+
+    let steps = [
+        (100, 101, Side::Buy, 5),
+        (105, 106, Side::Sell, 2),
+        (98, 99, Side::Buy, 1),
+        (110, 111, Side::Sell, 4),
+    ];
+    for (i, (bid, ask, side, qty)) in steps.into_iter().enumerate() {
+        let market_update = Bba {
+            bid: QuoteCurrency::new(bid, 0),
+            ask: QuoteCurrency::new(ask, 0),
+            timestamp_exchange_ns: (i as i64).into(),
+        };
+        for exchange in [&mut deferred, &mut upfront] {
+            exchange.update_state(&market_update).unwrap();
+            let order = MarketOrder::new(side, BaseCurrency::new(qty, 0)).unwrap();
+            exchange.submit_market_order(order).unwrap();
+        }
+        assert_eq!(upfront.position().outstanding_fees(), QuoteCurrency::zero());
+        assert_eq!(
+            deferred.position().quantity(),
+            upfront.position().quantity()
+        );
+
+        // The fees are expensed at different times, but the equity net of outstanding fees is the same.
+        let equity = |exchange: &TestExchange| {
+            exchange.user_balances().sum() + exchange.unrealized_pnl() - exchange.outstanding_fees()
+        };
+        assert_eq!(equity(&deferred), equity(&upfront));
+        assert_eq!(
+            deferred.fees_paid() + deferred.outstanding_fees(),
+            upfront.fees_paid()
+        );
+    }
+
+    assert_eq!(deferred.position(), &Position::Neutral);
+    assert_eq!(upfront.position(), &Position::Neutral);
+    assert_eq!(deferred.user_balances(), upfront.user_balances());
+    assert_eq!(deferred.fees_paid(), upfront.fees_paid());
+    assert_eq!(deferred.realized_pnl(), upfront.realized_pnl());
+}
@@ -0,0 +1,129 @@
+use test_case::test_case;
+
+use crate::{
+    DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_fee_maker,
+    test_fee_taker,
+};
+
+fn config_with_fee_model(
+    fee_model: FeeModel<QuoteCurrency<i64, DECIMALS>>,
+) -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config.set_fee_model(fee_model).unwrap();
+    config
+}
+
+#[test_case(FeeModel::Percentage, QuoteCurrency::convert_from(BaseCurrency::new(2, 0), QuoteCurrency::new(101, 0)) * *test_fee_taker().as_ref(); "percentage")]
+#[test_case(FeeModel::PerContract(QuoteCurrency::new(5, 1)), QuoteCurrency::new(1, 0); "per_contract")]
+#[tracing_test::traced_test]
+fn fee_model_market_order(
+    fee_model: FeeModel<QuoteCurrency<i64, DECIMALS>>,
+    expected_fee: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_fee_model(fee_model));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(2, 0);
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(fill.fee(), expected_fee);
+    assert_eq!(exchange.outstanding_fees(), expected_fee);
+
+    // Closing at the same price charges the same fee again, which is transferred to the `EXCHANGE_FEE_ACCOUNT`.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(101, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.fees_paid(), expected_fee + expected_fee);
+    assert_eq!(
+        exchange.wallet_balance(),
+        QuoteCurrency::new(1000, 0) - expected_fee - expected_fee
+    );
+}
+
+#[test_case(FeeModel::Percentage, QuoteCurrency::convert_from(BaseCurrency::new(2, 0), QuoteCurrency::new(100, 0)) * *test_fee_maker().as_ref(); "percentage")]
+#[test_case(FeeModel::PerContract(QuoteCurrency::new(5, 1)), QuoteCurrency::new(1, 0); "per_contract")]
+#[tracing_test::traced_test]
+fn fee_model_limit_order(
+    fee_model: FeeModel<QuoteCurrency<i64, DECIMALS>>,
+    expected_fee: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_fee_model(fee_model));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(2, 0);
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+    assert_eq!(exchange.outstanding_fees(), expected_fee);
+}
+
+#[test]
+fn fee_model_invalid_per_contract_fee() {
+    let mut config = mock_config_linear();
+    assert_eq!(
+        config.set_fee_model(FeeModel::PerContract(QuoteCurrency::new(-1, 0))),
+        Err(ConfigError::InvalidPerContractFee)
+    );
+    assert_eq!(config.fee_model(), FeeModel::Percentage);
+}
+
+#[test_case(FeeModel::Percentage, QuoteCurrency::new(4, 2), QuoteCurrency::new(12, 2); "percentage")]
+#[test_case(FeeModel::PerContract(QuoteCurrency::new(5, 1)), QuoteCurrency::new(1, 0), QuoteCurrency::new(1, 0); "per_contract")]
+#[tracing_test::traced_test]
+fn fee_model_estimated_fee(
+    fee_model: FeeModel<QuoteCurrency<i64, DECIMALS>>,
+    expected_maker_fee: QuoteCurrency<i64, DECIMALS>,
+    expected_taker_fee: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_fee_model(fee_model));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(2, 0);
+    let price = QuoteCurrency::new(100, 0);
+    assert_eq!(
+        exchange.estimated_fee(qty, price, LiquidityRole::Maker),
+        expected_maker_fee
+    );
+    assert_eq!(
+        exchange.estimated_fee(qty, price, LiquidityRole::Taker),
+        expected_taker_fee
+    );
+
+    // The estimate matches the fee charged when the order is filled.
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(fill.fee(), expected_taker_fee);
+}
@@ -0,0 +1,82 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn flat_account_update_state_matches_full_path() {
+    // The flat exchange takes the fast path, while the resting order forces the full one.
+    let mut flat = mock_exchange_linear();
+    let mut full = mock_exchange_linear();
+    full.update_state(&Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    })
+    .unwrap();
+    full.submit_limit_order(
+        LimitOrder::new(
+            Side::Buy,
+            QuoteCurrency::new(60, 0),
+            BaseCurrency::new(1, 0),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    flat.update_state(&Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    })
+    .unwrap();
+
+    for i in 1..50 {
+        let price = QuoteCurrency::new(100 + (i % 7), 0);
+        let trade = Trade {
+            price,
+            quantity: BaseCurrency::new(1, 0),
+            side: if i % 2 == 0 { Side::Buy } else { Side::Sell },
+            timestamp_exchange_ns: i.into(),
+        };
+        assert!(
+            flat.update_state(&trade)
+                .unwrap()
+                .limit_order_updates
+                .is_empty()
+        );
+        assert!(
+            full.update_state(&trade)
+                .unwrap()
+                .limit_order_updates
+                .is_empty()
+        );
+        let bba = Bba {
+            bid: price,
+            ask: price + QuoteCurrency::one(),
+            timestamp_exchange_ns: i.into(),
+        };
+        assert!(
+            flat.update_state(&bba)
+                .unwrap()
+                .limit_order_updates
+                .is_empty()
+        );
+        assert!(
+            full.update_state(&bba)
+                .unwrap()
+                .limit_order_updates
+                .is_empty()
+        );
+
+        let (flat_state, full_state) = (flat.market_state(), full.market_state());
+        assert_eq!(flat_state.bid(), full_state.bid());
+        assert_eq!(flat_state.ask(), full_state.ask());
+        assert_eq!(flat_state.last_trade_price(), full_state.last_trade_price());
+        assert_eq!(
+            flat_state.current_timestamp_ns(),
+            full_state.current_timestamp_ns()
+        );
+        assert_eq!(flat_state.step(), full_state.step());
+    }
+    assert_eq!(flat.position(), &Position::Neutral);
+    assert_eq!(flat.wallet_balance(), QuoteCurrency::new(1000, 0));
+    assert!(flat.active_limit_orders().is_empty());
+}
@@ -0,0 +1,166 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[tracing_test::traced_test]
+#[test]
+fn add_isolated_margin_moves_liquidation_price() {
+    let mut exchange = mock_exchange_linear();
+    let mut reference = mock_exchange_linear();
+    for exchange in [&mut exchange, &mut reference] {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into(),
+            })
+            .unwrap();
+        exchange
+            .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+            .unwrap();
+        // Close to the liquidation price, but not yet below it.
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(55, 0),
+                ask: QuoteCurrency::new(56, 0),
+                timestamp_exchange_ns: 1.into(),
+            })
+            .unwrap();
+    }
+    let liquidation_price = exchange.account().liquidation_price.unwrap();
+    let balances = exchange.user_balances();
+
+    let added_margin = QuoteCurrency::new(200, 0);
+    exchange.add_isolated_margin(added_margin).unwrap();
+    let Position::Long(inner) = exchange.position() else {
+        panic!("Expected a long position");
+    };
+    assert_eq!(inner.quantity(), BaseCurrency::new(5, 0));
+    assert_eq!(inner.added_margin(), added_margin);
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        balances.available_wallet_balance - added_margin
+    );
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        balances.position_margin + added_margin
+    );
+    assert_eq!(
+        exchange.account().liquidation_price,
+        Some(liquidation_price - QuoteCurrency::new(40, 0))
+    );
+
+    // Below the initial liquidation price, which only the position with the added margin survives.
+    let market_update = Bba {
+        bid: QuoteCurrency::new(40, 0),
+        ask: QuoteCurrency::new(41, 0),
+        timestamp_exchange_ns: 2.into(),
+    };
+    exchange.update_state(&market_update).unwrap();
+    assert!(matches!(exchange.position(), Position::Long(_)));
+    assert!(reference.update_state(&market_update).unwrap().liquidated);
+    assert_eq!(reference.position(), &Position::Neutral);
+}
+
+#[test]
+fn add_isolated_margin_insufficient_balance() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.add_isolated_margin(QuoteCurrency::new(100, 0)),
+        Err(Error::NoPosition)
+    );
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    let balances = exchange.user_balances();
+
+    assert_eq!(
+        exchange.add_isolated_margin(QuoteCurrency::zero()),
+        Err(Error::InvalidMarginAmount)
+    );
+    assert_eq!(
+        exchange.add_isolated_margin(balances.available_wallet_balance + QuoteCurrency::new(1, 0)),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    assert_eq!(exchange.user_balances(), balances);
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn remove_isolated_margin() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange.remove_isolated_margin(QuoteCurrency::new(100, 0)),
+        Err(Error::NoPosition)
+    );
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    exchange
+        .add_isolated_margin(QuoteCurrency::new(200, 0))
+        .unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let liquidation_price = exchange.account().liquidation_price.unwrap();
+    let balances = exchange.user_balances();
+
+    // Only margin which was added can be removed.
+    assert_eq!(
+        exchange.remove_isolated_margin(QuoteCurrency::new(201, 0)),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+    // Would move the liquidation price above the current mark price.
+    assert_eq!(
+        exchange.remove_isolated_margin(QuoteCurrency::new(150, 0)),
+        Err(Error::RiskError(RiskError::Liquidate))
+    );
+    assert_eq!(exchange.user_balances(), balances);
+    assert_eq!(
+        exchange.account().liquidation_price,
+        Some(liquidation_price)
+    );
+
+    let removed_margin = QuoteCurrency::new(100, 0);
+    exchange.remove_isolated_margin(removed_margin).unwrap();
+    let Position::Long(inner) = exchange.position() else {
+        panic!("Expected a long position");
+    };
+    assert_eq!(inner.added_margin(), QuoteCurrency::new(100, 0));
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        balances.available_wallet_balance + removed_margin
+    );
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        balances.position_margin - removed_margin
+    );
+    assert_eq!(
+        exchange.account().liquidation_price,
+        Some(liquidation_price + QuoteCurrency::new(20, 0))
+    );
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    assert!(matches!(exchange.position(), Position::Long(_)));
+}
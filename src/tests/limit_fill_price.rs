@@ -0,0 +1,102 @@
+use test_case::test_case;
+
+use crate::{
+    DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_fee_maker,
+};
+
+fn config_with_limit_fill_price(
+    limit_fill_price: LimitFillPrice,
+) -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config.set_limit_fill_price(limit_fill_price);
+    config
+}
+
+#[tracing_test::traced_test]
+#[test_case(LimitFillPrice::LimitPrice, Side::Buy, 95; "limit_price_buy")]
+#[test_case(LimitFillPrice::FavorableTouch, Side::Buy, 88; "favorable_touch_buy")]
+#[test_case(LimitFillPrice::LimitPrice, Side::Sell, 106; "limit_price_sell")]
+#[test_case(LimitFillPrice::FavorableTouch, Side::Sell, 113; "favorable_touch_sell")]
+fn limit_fill_price_gap_through(limit_fill_price: LimitFillPrice, side: Side, expected: i64) {
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_limit_fill_price(limit_fill_price));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::one();
+    let (limit_price, candle) = match side {
+        Side::Buy => (
+            QuoteCurrency::new(95, 0),
+            Candle::new(
+                QuoteCurrency::new(89, 0),
+                QuoteCurrency::new(90, 0),
+                QuoteCurrency::new(88, 0),
+                QuoteCurrency::new(91, 0),
+                1.into(),
+            )
+            .unwrap(),
+        ),
+        Side::Sell => (
+            QuoteCurrency::new(106, 0),
+            Candle::new(
+                QuoteCurrency::new(111, 0),
+                QuoteCurrency::new(112, 0),
+                QuoteCurrency::new(110, 0),
+                QuoteCurrency::new(113, 0),
+                1.into(),
+            )
+            .unwrap(),
+        ),
+    };
+    exchange
+        .submit_limit_order(LimitOrder::new(side, limit_price, qty).unwrap())
+        .unwrap();
+
+    // The market gaps through the limit price without ever trading at it.
+    let updates = exchange.update_state(&candle).unwrap().limit_order_updates;
+    assert_eq!(updates.len(), 1);
+    let LimitOrderUpdate::FullyFilled(order) = &updates[0] else {
+        panic!("Expected a full fill");
+    };
+    let expected = QuoteCurrency::new(expected, 0);
+    assert_eq!(order.state().avg_fill_price(), expected);
+    assert_eq!(exchange.position().entry_price(), expected);
+    assert_eq!(
+        exchange.outstanding_fees(),
+        QuoteCurrency::convert_from(qty, expected) * *test_fee_maker().as_ref()
+    );
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn limit_fill_price_trade() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_limit_fill_price(
+        LimitFillPrice::FavorableTouch,
+    ));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::one();
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap())
+        .unwrap();
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(97, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap()
+        .limit_order_updates;
+    assert_eq!(updates.len(), 1);
+    assert_eq!(exchange.position().entry_price(), QuoteCurrency::new(97, 0));
+}
@@ -0,0 +1,34 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_exchange_linear, prelude::*};
+
+#[test_case(Side::Buy, QuoteCurrency::new(100, 0), LiquidityRole::Maker; "buy at the bid")]
+#[test_case(Side::Buy, QuoteCurrency::new(101, 0), LiquidityRole::Maker; "buy inside the spread")]
+#[test_case(Side::Buy, QuoteCurrency::new(102, 0), LiquidityRole::Taker; "buy at the ask")]
+#[test_case(Side::Buy, QuoteCurrency::new(103, 0), LiquidityRole::Taker; "buy crossing")]
+#[test_case(Side::Sell, QuoteCurrency::new(102, 0), LiquidityRole::Maker; "sell at the ask")]
+#[test_case(Side::Sell, QuoteCurrency::new(101, 0), LiquidityRole::Maker; "sell inside the spread")]
+#[test_case(Side::Sell, QuoteCurrency::new(100, 0), LiquidityRole::Taker; "sell at the bid")]
+#[test_case(Side::Sell, QuoteCurrency::new(99, 0), LiquidityRole::Taker; "sell crossing")]
+fn liquidity_role(side: Side, limit_price: QuoteCurrency<i64, DECIMALS>, expected: LiquidityRole) {
+    let mut exchange = mock_exchange_linear();
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(102, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    assert_eq!(exchange.liquidity_role(side, limit_price), expected);
+
+    // A taker order would be rejected as `GoodTilCrossing`, a maker order rests in the book.
+    let order = LimitOrder::new(side, limit_price, BaseCurrency::new(1, 0)).unwrap();
+    match expected {
+        LiquidityRole::Maker => assert!(exchange.submit_limit_order(order).is_ok()),
+        LiquidityRole::Taker => assert!(exchange.submit_limit_order(order).is_err()),
+    }
+}
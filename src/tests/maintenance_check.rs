@@ -0,0 +1,139 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{DECIMALS, mock_config_linear, prelude::*, test_bba};
+
+/// A `RiskEngine` which counts how often the maintenance margin is checked.
+#[derive(Debug, Clone)]
+struct CountingRiskEngine {
+    inner: IsolatedMarginRiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+    maintenance_checks: Rc<Cell<usize>>,
+}
+
+impl RiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId> for CountingRiskEngine {
+    fn check_market_order(
+        &self,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        position_margin: QuoteCurrency<i64, DECIMALS>,
+        order: &MarketOrder<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+            Pending<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        >,
+        fill_price: QuoteCurrency<i64, DECIMALS>,
+        available_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+    ) -> std::result::Result<(), RiskError> {
+        self.inner.check_market_order(
+            position,
+            position_margin,
+            order,
+            fill_price,
+            available_wallet_balance,
+        )
+    }
+
+    fn check_limit_order(
+        &self,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        order: &LimitOrder<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+            Pending<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+        >,
+        available_wallet_balance: QuoteCurrency<i64, DECIMALS>,
+        order_margin: &OrderMargin<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId>,
+    ) -> std::result::Result<(), RiskError> {
+        self.inner
+            .check_limit_order(position, order, available_wallet_balance, order_margin)
+    }
+
+    fn check_maintenance_margin(
+        &self,
+        market_state: &MarketState<i64, DECIMALS>,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+    ) -> std::result::Result<(), RiskError> {
+        self.maintenance_checks
+            .set(self.maintenance_checks.get() + 1);
+        <IsolatedMarginRiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> as RiskEngine<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+        >>::check_maintenance_margin(&self.inner, market_state, position)
+    }
+
+    fn maintenance_margin(
+        &self,
+        position: &Position<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+    ) -> QuoteCurrency<i64, DECIMALS> {
+        <IsolatedMarginRiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> as RiskEngine<
+            i64,
+            DECIMALS,
+            BaseCurrency<i64, DECIMALS>,
+            NoUserOrderId,
+        >>::maintenance_margin(&self.inner, position)
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn maintenance_check_only_on_mark_price_change() {
+    let config = mock_config_linear();
+    let maintenance_checks = Rc::new(Cell::new(0));
+    let risk_engine = CountingRiskEngine {
+        inner: IsolatedMarginRiskEngine::new(config.contract_spec().clone()),
+        maintenance_checks: Rc::clone(&maintenance_checks),
+    };
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+        CountingRiskEngine,
+    >::with_risk_engine(config, risk_engine);
+
+    // A flat account is never checked.
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    assert_eq!(maintenance_checks.get(), 0);
+
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(maintenance_checks.get(), 0);
+    exchange.update_state(&test_bba(100, 101, 1)).unwrap();
+    assert_eq!(maintenance_checks.get(), 1);
+
+    // Order events between two identical mark prices don't trigger a check.
+    let order = exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(90, 0), qty).unwrap())
+        .unwrap()
+        .resting()
+        .unwrap();
+    exchange
+        .cancel_limit_order(CancelBy::OrderId(order.id()))
+        .unwrap();
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(91, 0), qty).unwrap())
+        .unwrap();
+    exchange.update_state(&test_bba(100, 101, 2)).unwrap();
+    // A long position is marked at the bid, which did not change.
+    exchange.update_state(&test_bba(100, 102, 3)).unwrap();
+    assert_eq!(maintenance_checks.get(), 1);
+
+    exchange.update_state(&test_bba(99, 100, 4)).unwrap();
+    assert_eq!(maintenance_checks.get(), 2);
+
+    // A changed position is checked again, even at the same mark price.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange.update_state(&test_bba(99, 100, 5)).unwrap();
+    assert_eq!(maintenance_checks.get(), 3);
+    exchange.update_state(&test_bba(99, 100, 6)).unwrap();
+    assert_eq!(maintenance_checks.get(), 3);
+}
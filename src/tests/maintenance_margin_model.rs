@@ -0,0 +1,87 @@
+use const_decimal::Decimal;
+
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear, prelude::*};
+
+/// Requires a fraction of the notional value minus a fixed amount,
+/// like the tiered maintenance margin of some exchanges.
+#[derive(Debug, Clone)]
+struct RateMinusAmount {
+    rate: Decimal<i64, DECIMALS>,
+    amount: QuoteCurrency<i64, DECIMALS>,
+}
+
+impl MaintenanceMarginModel<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> for RateMinusAmount {
+    fn required(
+        &self,
+        notional: QuoteCurrency<i64, DECIMALS>,
+        _side: Side,
+    ) -> QuoteCurrency<i64, DECIMALS> {
+        notional * self.rate - self.amount
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn maintenance_margin_model_rate_minus_amount() {
+    let config = mock_config_linear();
+    let risk_engine = IsolatedMarginRiskEngine::with_maintenance_margin_model(
+        config.contract_spec().clone(),
+        RateMinusAmount {
+            rate: Decimal::try_from_scaled(5, 1).unwrap(),
+            amount: QuoteCurrency::new(50, 0),
+        },
+    );
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+        IsolatedMarginRiskEngine<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, RateMinusAmount>,
+    >::with_risk_engine(config, risk_engine);
+    let mut default_exchange = mock_exchange_linear();
+
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    };
+    exchange.update_state(&bba).unwrap();
+    default_exchange.update_state(&bba).unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    default_exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.account().maintenance_margin,
+        QuoteCurrency::new(2025, 1)
+    );
+    assert_eq!(
+        default_exchange.account().maintenance_margin,
+        QuoteCurrency::new(2525, 1)
+    );
+
+    // With the flat model the long position has a liquidation price of 50.5,
+    // while the custom one requires a maintenance margin of 202.5 on the notional of 505,
+    // moving the liquidation price down to 40.5.
+    let bba = Bba {
+        bid: QuoteCurrency::new(45, 0),
+        ask: QuoteCurrency::new(46, 0),
+        timestamp_exchange_ns: 1.into(),
+    };
+    assert!(default_exchange.update_state(&bba).unwrap().liquidated);
+    assert_eq!(default_exchange.position(), &Position::Neutral);
+    exchange.update_state(&bba).unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+
+    let bba = Bba {
+        bid: QuoteCurrency::new(40, 0),
+        ask: QuoteCurrency::new(41, 0),
+        timestamp_exchange_ns: 2.into(),
+    };
+    assert!(exchange.update_state(&bba).unwrap().liquidated);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,82 @@
+use crate::{DECIMALS, mock_exchange_linear, prelude::*, test_bba};
+
+fn resting_buy() -> LimitOrder<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId, NewOrder>
+{
+    LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(99, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap()
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn maker_fill_quote_touch_does_not_fill() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange.submit_limit_order(resting_buy()).unwrap();
+
+    // The ask reaches the limit price, but no trade prints.
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(98, 0),
+                ask: QuoteCurrency::new(99, 0),
+                timestamp_exchange_ns: 1.into(),
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn maker_fill_trade_at_limit_does_not_fill() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange.submit_limit_order(resting_buy()).unwrap();
+
+    // The order is assumed to have the worst queue position, so a trade at the limit price is not enough.
+    assert!(
+        exchange
+            .update_state(&Trade {
+                price: QuoteCurrency::new(99, 0),
+                quantity: BaseCurrency::new(1, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: 1.into(),
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn maker_fill_trade_through_limit_fills() {
+    let mut exchange = mock_exchange_linear();
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange.submit_limit_order(resting_buy()).unwrap();
+
+    let outcome = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(98, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(outcome.fills().count(), 1);
+    assert!(exchange.active_limit_orders().is_empty());
+    let Position::Long(inner) = exchange.position() else {
+        panic!("Expected a long position");
+    };
+    assert_eq!(inner.quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(inner.entry_price(), QuoteCurrency::new(99, 0));
+}
@@ -0,0 +1,184 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_contract_spec_linear, mock_exchange_linear_with_config, prelude::*};
+
+fn config_with_mark_method(
+    mark_method: MarkMethod,
+) -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut contract_spec = mock_contract_spec_linear();
+    contract_spec.set_mark_method(mark_method);
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    config
+}
+
+#[test]
+fn mark_method_mark_price() {
+    let mut market_state = MarketState::<i64, DECIMALS>::default();
+    market_state.set_bid(QuoteCurrency::new(100, 0));
+    market_state.set_ask(QuoteCurrency::new(102, 0));
+
+    for mark_method in [MarkMethod::MidPrice, MarkMethod::FairPrice] {
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(
+                mark_method.mark_price(&market_state, side),
+                QuoteCurrency::new(101, 0)
+            );
+        }
+    }
+    assert_eq!(
+        MarkMethod::BidAsk.mark_price(&market_state, Side::Buy),
+        QuoteCurrency::new(100, 0)
+    );
+    assert_eq!(
+        MarkMethod::BidAsk.mark_price(&market_state, Side::Sell),
+        QuoteCurrency::new(102, 0)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn mark_method_fallback() {
+    let mark_method = MarkMethod::Fallback {
+        sources: PriceSources::new(&[PriceSource::LastTrade, PriceSource::MidPrice]).unwrap(),
+        max_age: Some(TimestampNs::from(10)),
+    };
+    let mut exchange = mock_exchange_linear_with_config(config_with_mark_method(mark_method));
+    for side in [Side::Buy, Side::Sell] {
+        assert_eq!(
+            mark_method.mark_price(exchange.market_state(), side),
+            QuoteCurrency::new(0, 0)
+        );
+    }
+
+    // No trade printed yet, so the mid price is used.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        mark_method.mark_price(exchange.market_state(), Side::Buy),
+        QuoteCurrency::new(101, 0)
+    );
+
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(103, 0),
+            quantity: BaseCurrency::new(1, 0),
+            side: Side::Buy,
+            timestamp_exchange_ns: 5.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        mark_method.mark_price(exchange.market_state(), Side::Buy),
+        QuoteCurrency::new(103, 0)
+    );
+
+    // The last trade becomes stale.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(104, 0),
+            ask: QuoteCurrency::new(106, 0),
+            timestamp_exchange_ns: 16.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        mark_method.mark_price(exchange.market_state(), Side::Sell),
+        QuoteCurrency::new(105, 0)
+    );
+}
+
+// The long position has a liquidation price of 50.5.
+#[tracing_test::traced_test]
+#[test_case(MarkMethod::BidAsk, true; "bid_ask")]
+#[test_case(MarkMethod::MidPrice, false; "mid_price")]
+fn mark_method_liquidation_long(mark_method: MarkMethod, liquidated: bool) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_mark_method(mark_method));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+
+    let res = exchange.update_state(&Bba {
+        bid: QuoteCurrency::new(50, 0),
+        ask: QuoteCurrency::new(52, 0),
+        timestamp_exchange_ns: 1.into(),
+    });
+    let outcome = res.unwrap();
+    assert_eq!(outcome.liquidated, liquidated);
+    assert!(outcome.limit_order_updates.is_empty());
+    if liquidated {
+        assert_eq!(exchange.position(), &Position::Neutral);
+    } else {
+        assert_eq!(exchange.position().quantity(), qty);
+    }
+}
+
+// The short position has a liquidation price of 150.
+#[tracing_test::traced_test]
+#[test_case(MarkMethod::BidAsk, true; "bid_ask")]
+#[test_case(MarkMethod::MidPrice, false; "mid_price")]
+fn mark_method_liquidation_short(mark_method: MarkMethod, liquidated: bool) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_mark_method(mark_method));
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+
+    let res = exchange.update_state(&Bba {
+        bid: QuoteCurrency::new(148, 0),
+        ask: QuoteCurrency::new(151, 0),
+        timestamp_exchange_ns: 1.into(),
+    });
+    let outcome = res.unwrap();
+    assert_eq!(outcome.liquidated, liquidated);
+    assert!(outcome.limit_order_updates.is_empty());
+    if liquidated {
+        assert_eq!(exchange.position(), &Position::Neutral);
+    } else {
+        assert_eq!(exchange.position().quantity(), qty);
+    }
+}
+
+// Without any trade, the last trade price is never observed, so the position is marked at the bid or ask.
+#[test]
+fn mark_method_fallback_never_observed() {
+    let mut market_state = MarketState::<i64, DECIMALS>::default();
+    market_state.set_bid(QuoteCurrency::new(100, 0));
+    market_state.set_ask(QuoteCurrency::new(102, 0));
+
+    let mark_method = MarkMethod::Fallback {
+        sources: PriceSources::new(&[PriceSource::LastTrade]).unwrap(),
+        max_age: None,
+    };
+    assert_eq!(
+        mark_method.mark_price(&market_state, Side::Buy),
+        QuoteCurrency::new(100, 0)
+    );
+    assert_eq!(
+        mark_method.mark_price(&market_state, Side::Sell),
+        QuoteCurrency::new(102, 0)
+    );
+}
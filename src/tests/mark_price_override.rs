@@ -0,0 +1,37 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn mark_price_override_liquidates() {
+    let mut exchange = mock_exchange_linear();
+    let bba = Bba {
+        bid: QuoteCurrency::new(100, 0),
+        ask: QuoteCurrency::new(101, 0),
+        timestamp_exchange_ns: 0.into(),
+    };
+    exchange.update_state(&bba).unwrap();
+
+    // The mark price is far below the liquidation price of 50.5, but market orders still fill at the book.
+    exchange.set_mark_price_override(Some(QuoteCurrency::new(40, 0)));
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(101, 0)
+    );
+
+    assert!(exchange.update_state(&bba).unwrap().liquidated);
+    assert_eq!(exchange.position(), &Position::Neutral);
+    // The position is closed at the bid, not at the overridden mark price.
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(994397, 3));
+
+    // Clearing the override reverts to the configured `MarkMethod`.
+    exchange.set_mark_price_override(None);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange.update_state(&bba).unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+}
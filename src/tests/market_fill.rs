@@ -0,0 +1,44 @@
+use crate::{mock_exchange_linear, prelude::*, test_fee_taker};
+
+#[test]
+#[tracing_test::traced_test]
+fn market_fill_details() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let qty = BaseCurrency::new(5, 0);
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    let fee0 =
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(101, 0)) * *test_fee_taker().as_ref();
+    assert_eq!(fill.quantity(), qty);
+    assert_eq!(fill.avg_price(), QuoteCurrency::new(101, 0));
+    assert_eq!(fill.fee(), fee0);
+    assert_eq!(fill.liquidity_role(), LiquidityRole::Taker);
+    assert_eq!(fill.order().side(), Side::Buy);
+    assert_eq!(exchange.position().quantity(), fill.quantity());
+    assert_eq!(exchange.position().entry_price(), fill.avg_price());
+    assert_eq!(exchange.position().outstanding_fees(), fill.fee());
+
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(2, 0)).unwrap())
+        .unwrap();
+    let fee1 = QuoteCurrency::convert_from(BaseCurrency::new(2, 0), QuoteCurrency::new(100, 0))
+        * *test_fee_taker().as_ref();
+    assert_eq!(fill.quantity(), BaseCurrency::new(2, 0));
+    assert_eq!(fill.avg_price(), QuoteCurrency::new(100, 0));
+    assert_eq!(fill.fee(), fee1);
+    assert_eq!(fill.liquidity_role(), LiquidityRole::Taker);
+    assert_eq!(exchange.position().quantity(), qty - fill.quantity());
+    assert_eq!(
+        exchange.fees_paid() + exchange.position().outstanding_fees(),
+        fee0 + fee1
+    );
+}
@@ -0,0 +1,54 @@
+use test_case::test_case;
+
+use crate::{mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_fee_taker};
+
+#[tracing_test::traced_test]
+#[test_case(MarketFillPrice::BidAsk, 102, 100, 0; "bid_ask")]
+#[test_case(MarketFillPrice::MidPrice, 101, 101, 5; "mid_price")]
+fn market_fill_price_round_trip(
+    market_fill_price: MarketFillPrice,
+    entry_price: i64,
+    exit_price: i64,
+    spread_cost: i64,
+) {
+    let mut config = mock_config_linear();
+    config.set_market_fill_price(market_fill_price);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let qty = BaseCurrency::new(5, 0);
+    let fee = |price: i64| {
+        QuoteCurrency::convert_from(qty, QuoteCurrency::new(price, 0)) * *test_fee_taker().as_ref()
+    };
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(fill.avg_price(), QuoteCurrency::new(entry_price, 0));
+    assert_eq!(
+        fill.fee(),
+        fee(entry_price) + QuoteCurrency::new(spread_cost, 0)
+    );
+    let fill = exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, qty).unwrap())
+        .unwrap();
+    assert_eq!(fill.avg_price(), QuoteCurrency::new(exit_price, 0));
+    assert_eq!(
+        fill.fee(),
+        fee(exit_price) + QuoteCurrency::new(spread_cost, 0)
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    // Both pay the spread of 2 on a quantity of 5, either through the fill price or as an explicit cost,
+    // while the percentage fees are the same, as the entry and exit notional only shift by half the spread.
+    let total_spread_cost = QuoteCurrency::convert_from(qty, QuoteCurrency::new(2, 0));
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(1000, 0) - total_spread_cost - fee(entry_price) - fee(exit_price)
+    );
+}
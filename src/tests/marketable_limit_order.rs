@@ -0,0 +1,74 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_exchange_linear, prelude::*, test_fee_taker};
+
+fn marketable_order(
+    side: Side,
+    limit_price: QuoteCurrency<i64, DECIMALS>,
+) -> LimitOrder<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId, NewOrder> {
+    let mut order = LimitOrder::new(side, limit_price, BaseCurrency::one()).unwrap();
+    order.set_re_pricing(RePricing::Marketable);
+    order
+}
+
+#[tracing_test::traced_test]
+#[test_case(Side::Buy, 105, 101; "buy_above_ask")]
+#[test_case(Side::Buy, 101, 101; "buy_at_ask")]
+#[test_case(Side::Sell, 95, 100; "sell_below_bid")]
+#[test_case(Side::Sell, 100, 100; "sell_at_bid")]
+fn marketable_limit_order_price_improvement(side: Side, limit_price: i64, expected_price: i64) {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // The order takes liquidity at the away quote, which is never worse than its limit price.
+    let fill = exchange
+        .submit_limit_order(marketable_order(side, QuoteCurrency::new(limit_price, 0)))
+        .unwrap()
+        .fill()
+        .unwrap();
+    assert!(exchange.active_limit_orders().is_empty());
+
+    let expected_price = QuoteCurrency::new(expected_price, 0);
+    assert_eq!(exchange.position().entry_price(), expected_price);
+    assert_eq!(
+        exchange.outstanding_fees(),
+        QuoteCurrency::convert_from(BaseCurrency::one(), expected_price)
+            * *test_fee_taker().as_ref()
+    );
+
+    assert_eq!(fill.order().state().meta().id(), 0.into());
+    assert_eq!(fill.order().side(), side);
+    assert_eq!(fill.quantity(), BaseCurrency::one());
+    assert_eq!(fill.avg_price(), expected_price);
+    assert_eq!(fill.fee(), exchange.outstanding_fees());
+    assert_eq!(fill.liquidity_role(), LiquidityRole::Taker);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn marketable_limit_order_rests_when_passive() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // A limit price below the ask caps the fill price, so the order can't take liquidity and rests in the book.
+    let order = exchange
+        .submit_limit_order(marketable_order(Side::Buy, QuoteCurrency::new(100, 0)))
+        .unwrap()
+        .resting()
+        .unwrap();
+    assert_eq!(order.remaining_quantity(), BaseCurrency::one());
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,66 @@
+use const_decimal::Decimal;
+
+use crate::{mock_config_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+fn max_fill_ratio_per_tick_config() {
+    let mut config = mock_config_linear();
+    assert_eq!(config.max_fill_ratio_per_tick(), None);
+    for ratio in [Decimal::ZERO, Decimal::try_from_scaled(11, 1).unwrap()] {
+        assert_eq!(
+            config.set_max_fill_ratio_per_tick(Some(ratio)),
+            Err(ConfigError::InvalidMaxFillRatioPerTick)
+        );
+    }
+    config
+        .set_max_fill_ratio_per_tick(Some(Decimal::ONE))
+        .unwrap();
+    assert_eq!(config.max_fill_ratio_per_tick(), Some(Decimal::ONE));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_fill_ratio_per_tick_spreads_fill() {
+    let mut config = mock_config_linear();
+    config
+        .set_max_fill_ratio_per_tick(Some(Decimal::try_from_scaled(25, 2).unwrap()))
+        .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(4, 0);
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap())
+        .unwrap();
+
+    // Each trade could fill the whole order, but only a quarter of it is filled per market update.
+    for i in 1..=4 {
+        let updates = exchange
+            .update_state(&Trade {
+                price: QuoteCurrency::new(99, 0),
+                quantity: BaseCurrency::new(10, 0),
+                side: Side::Sell,
+                timestamp_exchange_ns: i.into(),
+            })
+            .unwrap()
+            .limit_order_updates;
+        assert_eq!(updates.len(), 1);
+        if i < 4 {
+            assert!(matches!(updates[0], LimitOrderUpdate::PartiallyFilled(_)));
+            assert_eq!(exchange.active_limit_orders().len(), 1);
+        } else {
+            assert!(matches!(updates[0], LimitOrderUpdate::FullyFilled(_)));
+            assert!(exchange.active_limit_orders().is_empty());
+        }
+        assert_eq!(exchange.position().quantity(), BaseCurrency::new(i, 0));
+    }
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(100, 0)
+    );
+}
@@ -0,0 +1,97 @@
+use crate::{mock_config_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+fn max_trades_per_tick_config() {
+    let mut config = mock_config_linear();
+    assert_eq!(config.max_trades_per_tick(), None);
+    assert_eq!(
+        config.set_max_trades_per_tick(Some(0)),
+        Err(ConfigError::InvalidMaxTradesPerTick)
+    );
+    config.set_max_trades_per_tick(Some(2)).unwrap();
+    assert_eq!(config.max_trades_per_tick(), Some(2));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_trades_per_tick_rejects_and_resets() {
+    let mut config = mock_config_linear();
+    config.set_max_trades_per_tick(Some(2)).unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(99, 0), qty).unwrap())
+        .unwrap();
+    assert_eq!(
+        exchange.submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap()),
+        Err(Error::MaxTradesPerTickReached)
+    );
+    assert_eq!(
+        exchange.submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(99, 0), qty).unwrap()
+        ),
+        Err(Error::MaxTradesPerTickReached)
+    );
+    assert_eq!(exchange.position().quantity(), qty);
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    // The counter resets with the next market update.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), qty + qty);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn max_trades_per_tick_ignores_liquidations() {
+    let mut config = mock_config_linear();
+    config.set_max_trades_per_tick(Some(1)).unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+
+    // The long position has a liquidation price of 50.5.
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(outcome.liquidated);
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    // The liquidation did not use up the single submission of this market update.
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+}
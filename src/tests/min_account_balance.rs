@@ -0,0 +1,84 @@
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*};
+
+fn config_with_min_account_balance() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut config = mock_config_linear();
+    config
+        .set_min_account_balance(Some(QuoteCurrency::new(900, 0)))
+        .unwrap();
+    config
+}
+
+#[test]
+fn min_account_balance_invalid() {
+    let mut config = mock_config_linear();
+    assert_eq!(
+        config.set_min_account_balance(Some(QuoteCurrency::zero())),
+        Err(ConfigError::InvalidMinAccountBalance)
+    );
+    assert_eq!(
+        config.set_min_account_balance(Some(QuoteCurrency::new(1000, 0))),
+        Err(ConfigError::InvalidMinAccountBalance)
+    );
+    assert_eq!(config.min_account_balance(), None);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn min_account_balance_blows_up_account() {
+    let mut exchange = mock_exchange_linear_with_config(config_with_min_account_balance());
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(70, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap();
+
+    // The equity of about 894.7 is above the liquidation threshold, but below the `min_account_balance`.
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(80, 0),
+            ask: QuoteCurrency::new(81, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(outcome.blown_up);
+    assert!(!outcome.liquidated);
+    // The active limit order is reported as cancelled.
+    assert_eq!(outcome.cancellations().count(), 1);
+    assert!(exchange.is_blown_up());
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: QuoteCurrency::new(894457, 3),
+            position_margin: QuoteCurrency::zero(),
+            order_margin: QuoteCurrency::zero(),
+            _q: std::marker::PhantomData,
+        }
+    );
+
+    // Subsequent orders are rejected.
+    assert_eq!(
+        exchange
+            .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+            .unwrap_err(),
+        Error::AccountBlownUp
+    );
+    assert_eq!(
+        exchange
+            .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(79, 0), qty).unwrap())
+            .unwrap_err(),
+        Error::AccountBlownUp
+    );
+}
@@ -1,7 +1,47 @@
+mod account;
 mod amend;
+mod cancel_latency;
 mod cancel_limit_order;
+mod closed_trades;
+mod cooldown;
+mod crossed_market;
+mod custom_risk_engine;
+mod deferred_liquidation;
+mod event_recorder;
+mod expiry;
+mod fee_mode;
+mod fee_model;
+mod flat_account;
+mod isolated_margin;
+mod limit_fill_price;
+mod liquidity_role;
+mod maintenance_check;
+mod maintenance_margin_model;
+mod maker_fill;
+mod mark_method;
+mod mark_price_override;
+mod market_fill;
+mod market_fill_price;
+mod marketable_limit_order;
+mod max_fill_ratio_per_tick;
+mod max_trades_per_tick;
+mod min_account_balance;
+mod no_market_data;
+mod order_distance_to_fill;
+mod order_rate_limit;
 mod partial_order_fill;
+mod position_sizing;
+mod price_move_cap;
+mod scripted_strategy;
+mod self_cross;
+mod short_margin;
 mod submit_limit_buy_order;
 mod submit_limit_sell_order;
 mod submit_market_buy_order;
 mod submit_market_sell_order;
+mod transaction_history;
+mod update_outcome;
+mod user_order_id;
+mod validation_mode;
+mod would_fill;
+mod zero_quantity;
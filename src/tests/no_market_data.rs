@@ -0,0 +1,43 @@
+use crate::{mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn no_market_data_rejects_orders() {
+    let mut exchange = mock_exchange_linear();
+    assert!(!exchange.market_state().has_market_data());
+
+    let market_order = MarketOrder::new(Side::Buy, BaseCurrency::new(1, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(market_order.clone()),
+        Err(Error::NoMarketData)
+    );
+    let limit_order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(100, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange.submit_limit_order(limit_order.clone()),
+        Err(Error::NoMarketData)
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(
+        exchange.user_balances().available_wallet_balance,
+        QuoteCurrency::new(1000, 0)
+    );
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert!(exchange.market_state().has_market_data());
+    exchange.submit_limit_order(limit_order).unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    exchange.submit_market_order(market_order).unwrap();
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+}
@@ -0,0 +1,65 @@
+use test_case::test_case;
+
+use crate::{DECIMALS, mock_exchange_linear, prelude::*};
+
+#[tracing_test::traced_test]
+#[test_case(Side::Buy, QuoteCurrency::new(100, 0), QuoteCurrency::new(1, 0); "buy at the bid")]
+#[test_case(Side::Buy, QuoteCurrency::new(98, 0), QuoteCurrency::new(3, 0); "buy below the bid")]
+#[test_case(Side::Buy, QuoteCurrency::new(90, 0), QuoteCurrency::new(11, 0); "buy far below the bid")]
+#[test_case(Side::Sell, QuoteCurrency::new(101, 0), QuoteCurrency::new(1, 0); "sell at the ask")]
+#[test_case(Side::Sell, QuoteCurrency::new(105, 0), QuoteCurrency::new(5, 0); "sell above the ask")]
+fn order_distance_to_fill(
+    side: Side,
+    limit_price: QuoteCurrency<i64, DECIMALS>,
+    expected_distance: QuoteCurrency<i64, DECIMALS>,
+) {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = LimitOrder::new(side, limit_price, BaseCurrency::new(1, 0)).unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    assert_eq!(
+        exchange.order_distance_to_fill(0.into()),
+        Ok(expected_distance)
+    );
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn order_distance_to_fill_inactive_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    let order = LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), qty).unwrap();
+    exchange.submit_limit_order(order).unwrap();
+    assert_eq!(
+        exchange.order_distance_to_fill(1.into()),
+        Err(Error::OrderIdNotFound { order_id: 1.into() })
+    );
+
+    exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: qty,
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(
+        exchange.order_distance_to_fill(0.into()),
+        Err(Error::OrderNoLongerActive)
+    );
+}
@@ -0,0 +1,64 @@
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_bba};
+
+fn buy_order() -> LimitOrder<i64, DECIMALS, BaseCurrency<i64, DECIMALS>, NoUserOrderId, NewOrder> {
+    LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap()
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn order_rate_limit_burst_and_refill() {
+    let mock_config = mock_config_linear();
+    let config = Config::new(
+        mock_config.starting_wallet_balance(),
+        mock_config.max_num_open_orders(),
+        mock_config.contract_spec().clone(),
+        OrderRateLimits::per_window(3, 1_000_000_000.into()).unwrap(),
+    )
+    .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+
+    // A burst beyond the bucket is throttled.
+    let results: Vec<_> = (0..5)
+        .map(|_| exchange.submit_limit_order(buy_order()).map(|_| ()))
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            Ok(()),
+            Ok(()),
+            Ok(()),
+            Err(Error::RateLimitReached),
+            Err(Error::RateLimitReached)
+        ]
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 3);
+
+    // One permit refills every third of a second.
+    exchange
+        .update_state(&test_bba(100, 101, 500_000_000))
+        .unwrap();
+    assert!(exchange.submit_limit_order(buy_order()).is_ok());
+    assert_eq!(
+        exchange.submit_limit_order(buy_order()).map(|_| ()),
+        Err(Error::RateLimitReached)
+    );
+
+    // After the window elapsed the bucket is full again, but holds no more than its capacity.
+    exchange
+        .update_state(&test_bba(100, 101, 5_000_000_000))
+        .unwrap();
+    for _ in 0..3 {
+        assert!(exchange.submit_limit_order(buy_order()).is_ok());
+    }
+    assert_eq!(
+        exchange.submit_limit_order(buy_order()).map(|_| ()),
+        Err(Error::RateLimitReached)
+    );
+    assert_eq!(exchange.active_limit_orders().len(), 7);
+}
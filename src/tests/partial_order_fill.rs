@@ -21,6 +21,7 @@ fn partial_limit_order_fill(
                 timestamp_exchange_ns: 1.into()
             },)
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let order = LimitOrder::new(side, limit_price, qty).unwrap();
@@ -34,7 +35,8 @@ fn partial_limit_order_fill(
             side: side.inverted(),
             timestamp_exchange_ns: 1.into(),
         })
-        .unwrap();
+        .unwrap()
+        .limit_order_updates;
     // Half of the limit order should be executed
     assert_eq!(exec_orders.len(), 1);
 
@@ -0,0 +1,123 @@
+use const_decimal::Decimal;
+
+use crate::{DECIMALS, mock_exchange_linear, mock_exchange_linear_with_config, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn order_by_balance_fraction() {
+    let exchange = {
+        let mut exchange = mock_exchange_linear();
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(99, 0),
+                ask: QuoteCurrency::new(100, 0),
+                timestamp_exchange_ns: 0.into(),
+            })
+            .unwrap();
+        exchange
+    };
+    let fraction = Decimal::try_from_scaled(5, 1).unwrap();
+
+    for sizing in [PositionSizing::Compounding, PositionSizing::Fixed] {
+        let order = exchange
+            .order_by_balance_fraction(Side::Buy, fraction, sizing)
+            .unwrap();
+        assert_eq!(order.side(), Side::Buy);
+        assert_eq!(order.quantity(), BaseCurrency::new(5, 0));
+    }
+
+    // Rounded towards zero to the quantity `tick_size`.
+    let order = exchange
+        .order_by_balance_fraction(
+            Side::Sell,
+            Decimal::try_from_scaled(1, 1).unwrap(),
+            PositionSizing::Fixed,
+        )
+        .unwrap();
+    assert_eq!(order.quantity(), BaseCurrency::new(101, 2));
+
+    assert_eq!(
+        exchange.order_by_balance_fraction(Side::Buy, Decimal::ZERO, PositionSizing::Fixed),
+        Err(Error::OrderError(OrderError::OrderQuantityLTEZero))
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn order_by_balance_fraction_compounding_vs_fixed() {
+    let mut exchange = mock_exchange_linear();
+    let fraction = Decimal::try_from_scaled(5, 1).unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(99, 0),
+            ask: QuoteCurrency::new(100, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = exchange
+        .order_by_balance_fraction(Side::Buy, fraction, PositionSizing::Compounding)
+        .unwrap();
+    exchange.submit_market_order(order).unwrap();
+
+    // A winning trade.
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(120, 0),
+            ask: QuoteCurrency::new(121, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    let order = MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(109934, 2));
+
+    let compounding = exchange
+        .order_by_balance_fraction(Side::Buy, fraction, PositionSizing::Compounding)
+        .unwrap();
+    let fixed = exchange
+        .order_by_balance_fraction(Side::Buy, fraction, PositionSizing::Fixed)
+        .unwrap();
+    assert_eq!(compounding.quantity(), BaseCurrency::new(454, 2));
+    assert_eq!(fixed.quantity(), BaseCurrency::new(413, 2));
+    assert!(compounding.quantity() > fixed.quantity());
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn required_balance_to_open() {
+    let exchange_with_balance = |balance: QuoteCurrency<i64, DECIMALS>| {
+        let contract_spec = mock_exchange_linear().config().contract_spec().clone();
+        let config = Config::new(balance, 10, contract_spec, OrderRateLimits::default()).unwrap();
+        let mut exchange = mock_exchange_linear_with_config(config);
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(99, 0),
+                ask: QuoteCurrency::new(100, 0),
+                timestamp_exchange_ns: 0.into(),
+            })
+            .unwrap();
+        exchange
+    };
+    let exchange = exchange_with_balance(QuoteCurrency::new(1000, 0));
+    let qty = BaseCurrency::new(5, 0);
+    // A passive order pays the maker fee.
+    assert_eq!(
+        exchange.required_balance_to_open(Side::Buy, qty, QuoteCurrency::new(99, 0)),
+        QuoteCurrency::new(495099, 3)
+    );
+    let required = exchange.required_balance_to_open(Side::Buy, qty, QuoteCurrency::new(100, 0));
+    assert_eq!(required, QuoteCurrency::new(5003, 1));
+
+    let mut exchange = exchange_with_balance(required);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position().quantity(), qty);
+
+    let mut exchange = exchange_with_balance(required - QuoteCurrency::new(1, DECIMALS));
+    assert_eq!(
+        exchange.submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap()),
+        Err(Error::RiskError(RiskError::NotEnoughAvailableBalance))
+    );
+}
@@ -0,0 +1,171 @@
+use const_decimal::Decimal;
+
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear_with_config, prelude::*, test_bba};
+
+#[test]
+fn price_move_cap_config() {
+    let mut config = mock_config_linear();
+    assert_eq!(config.max_price_move_pct(), None);
+    assert_eq!(
+        config.set_max_price_move_pct(Some(Decimal::ZERO)),
+        Err(ConfigError::InvalidMaxPriceMove)
+    );
+    config
+        .set_max_price_move_pct(Some(Decimal::try_from_scaled(10, 0).unwrap()))
+        .unwrap();
+    assert_eq!(
+        config.max_price_move_pct(),
+        Some(Decimal::try_from_scaled(10, 0).unwrap())
+    );
+}
+
+#[test_case::test_case(None; "without cap")]
+#[test_case::test_case(Some(Decimal::try_from_scaled(10, 0).unwrap()); "with cap")]
+#[tracing_test::traced_test]
+fn price_move_cap_spike(max_price_move_pct: Option<Decimal<i64, DECIMALS>>) {
+    let mut config = mock_config_linear();
+    config.set_max_price_move_pct(max_price_move_pct).unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+    let qty = BaseCurrency::new(5, 0);
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+        .unwrap();
+
+    // A 50% single tick spike.
+    let res = exchange.update_state(&Bba {
+        bid: QuoteCurrency::new(50, 0),
+        ask: QuoteCurrency::new(51, 0),
+        timestamp_exchange_ns: 1.into(),
+    });
+    match max_price_move_pct {
+        Some(_) => {
+            assert_eq!(res, Err(Error::PriceMoveTooLarge));
+            assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(100, 0));
+            assert_eq!(exchange.market_state().ask(), QuoteCurrency::new(101, 0));
+            assert_eq!(exchange.position().quantity(), qty);
+        }
+        None => {
+            assert!(res.unwrap().liquidated);
+            assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(50, 0));
+            assert_eq!(exchange.position(), &Position::Neutral);
+        }
+    }
+
+    // Moves within the cap are processed normally.
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(105, 0),
+                ask: QuoteCurrency::new(106, 0),
+                timestamp_exchange_ns: 2.into()
+            })
+            .is_ok()
+    );
+}
+
+// A genuine gap to a new price level is only rejected once, not forever.
+#[test]
+#[tracing_test::traced_test]
+fn price_move_cap_new_level() {
+    let mut config = mock_config_linear();
+    config
+        .set_max_price_move_pct(Some(Decimal::try_from_scaled(10, 0).unwrap()))
+        .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+
+    assert_eq!(
+        exchange.update_state(&test_bba(150, 151, 1)),
+        Err(Error::PriceMoveTooLarge)
+    );
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(100, 0));
+    assert_eq!(exchange.market_state().current_timestamp_ns(), 0.into());
+
+    // The price stays at the new level, which confirms the move.
+    exchange.update_state(&test_bba(150, 151, 2)).unwrap();
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(150, 0));
+    exchange.update_state(&test_bba(152, 153, 3)).unwrap();
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(152, 0));
+
+    // A single spike which reverts is still rejected.
+    assert_eq!(
+        exchange.update_state(&test_bba(200, 201, 4)),
+        Err(Error::PriceMoveTooLarge)
+    );
+    exchange.update_state(&test_bba(151, 152, 5)).unwrap();
+    assert_eq!(exchange.market_state().bid(), QuoteCurrency::new(151, 0));
+}
+
+// A bad `Trade` print is rejected like a bad quote, so it neither fills resting orders nor moves the last trade price.
+#[test_case::test_case(None; "without cap")]
+#[test_case::test_case(Some(Decimal::try_from_scaled(10, 0).unwrap()); "with cap")]
+#[tracing_test::traced_test]
+fn price_move_cap_trade_spike(max_price_move_pct: Option<Decimal<i64, DECIMALS>>) {
+    let mut config = mock_config_linear();
+    config.set_max_price_move_pct(max_price_move_pct).unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    let trade = |price: i64, ts: i64| Trade {
+        price: QuoteCurrency::new(price, 0),
+        quantity: BaseCurrency::new(1, 0),
+        side: Side::Sell,
+        timestamp_exchange_ns: ts.into(),
+    };
+
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange.update_state(&trade(100, 1)).unwrap();
+    let order = LimitOrder::new(
+        Side::Buy,
+        QuoteCurrency::new(90, 0),
+        BaseCurrency::new(1, 0),
+    )
+    .unwrap();
+    exchange.submit_limit_order(order).unwrap();
+
+    // A 50% single tick spike.
+    let res = exchange.update_state(&trade(50, 2));
+    match max_price_move_pct {
+        Some(_) => {
+            assert_eq!(res, Err(Error::PriceMoveTooLarge));
+            assert_eq!(
+                exchange.market_state().last_trade_price(),
+                QuoteCurrency::new(100, 0)
+            );
+            assert_eq!(exchange.active_limit_orders().len(), 1);
+            assert_eq!(exchange.position(), &Position::Neutral);
+        }
+        None => {
+            assert_eq!(res.unwrap().limit_order_updates.len(), 1);
+            assert_eq!(
+                exchange.market_state().last_trade_price(),
+                QuoteCurrency::new(50, 0)
+            );
+            assert!(exchange.active_limit_orders().is_empty());
+            assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+        }
+    }
+
+    // Trades within the cap are processed normally.
+    exchange.update_state(&trade(99, 3)).unwrap();
+    assert_eq!(
+        exchange.market_state().last_trade_price(),
+        QuoteCurrency::new(99, 0)
+    );
+}
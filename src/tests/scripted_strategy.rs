@@ -0,0 +1,94 @@
+use const_decimal::Decimal;
+
+use crate::{
+    DECIMALS, mock_config_linear, mock_exchange_linear, mock_exchange_linear_with_config,
+    prelude::*, test_bba,
+};
+
+const SCRIPT: &str = "timestamp_ns,action,side,price,quantity,order_id
+1,submit_limit,buy,99,2,
+1,submit_limit,buy,95,1,
+3,cancel,,,,1
+3,submit_market,sell,,1,
+";
+
+#[test]
+#[tracing_test::traced_test]
+fn scripted_strategy_run() {
+    let mut exchange = mock_exchange_linear();
+    let strategy =
+        ScriptedStrategy::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::from_csv(SCRIPT.as_bytes())
+            .unwrap();
+    assert_eq!(strategy.actions().len(), 4);
+
+    let candle = |bid, ask, low, high, ts_ns: i64| {
+        Candle::new(
+            QuoteCurrency::new(bid, 0),
+            QuoteCurrency::new(ask, 0),
+            QuoteCurrency::new(low, 0),
+            QuoteCurrency::new(high, 0),
+            ts_ns.into(),
+        )
+        .unwrap()
+    };
+    let market_updates = [
+        candle(100, 101, 100, 101, 0),
+        candle(100, 101, 100, 101, 1),
+        candle(98, 99, 98, 101, 2),
+        candle(100, 101, 99, 101, 3),
+    ];
+    strategy.run(&mut exchange, &market_updates).unwrap();
+
+    // The buy at 99 was filled, the one at 95 cancelled and half of the position sold at the bid.
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(exchange.position().entry_price(), QuoteCurrency::new(99, 0));
+    assert_eq!(exchange.realized_pnl(), QuoteCurrency::new(1, 0));
+}
+
+#[test]
+fn scripted_strategy_invalid() {
+    assert_eq!(
+        ScriptedStrategy::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::from_csv(
+            "timestamp_ns,action,side,price,quantity,order_id\n1,submit_market,long,,1,\n"
+                .as_bytes()
+        )
+        .unwrap_err(),
+        Error::InvalidScript("unknown side \"long\"".to_string())
+    );
+    assert!(
+        ScriptedStrategy::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::from_csv(
+            "ts,action\n1,cancel\n".as_bytes()
+        )
+        .is_err()
+    );
+}
+
+// A rejected bad print does not abort the run, the actions due are issued with the next accepted market update.
+#[test]
+#[tracing_test::traced_test]
+fn scripted_strategy_run_skips_rejected_market_updates() {
+    let mut config = mock_config_linear();
+    config
+        .set_max_price_move_pct(Some(Decimal::try_from_scaled(10, 0).unwrap()))
+        .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    let strategy = ScriptedStrategy::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::from_csv(
+        "timestamp_ns,action,side,price,quantity,order_id\n1,submit_market,buy,,1,\n".as_bytes(),
+    )
+    .unwrap();
+
+    let market_updates = [
+        test_bba(100, 101, 0),
+        test_bba(50, 51, 1),
+        test_bba(102, 103, 2),
+    ];
+    strategy.run(&mut exchange, &market_updates).unwrap();
+
+    assert_eq!(exchange.market_state().current_timestamp_ns(), 2.into());
+    assert_eq!(exchange.position().quantity(), BaseCurrency::new(1, 0));
+    assert_eq!(
+        exchange.position().entry_price(),
+        QuoteCurrency::new(103, 0)
+    );
+}
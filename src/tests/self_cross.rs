@@ -0,0 +1,119 @@
+use test_case::test_case;
+
+use crate::{
+    mock_config_linear, mock_exchange_linear, mock_exchange_linear_with_config, prelude::*,
+};
+
+#[tracing_test::traced_test]
+#[test_case(SelfCrossPolicy::Reject, Err(Error::SelfCross), &[0]; "reject")]
+#[test_case(SelfCrossPolicy::CancelResting, Ok(()), &[1]; "cancel resting")]
+#[test_case(SelfCrossPolicy::Allow, Ok(()), &[0, 1]; "allow")]
+fn self_cross_policy(
+    policy: SelfCrossPolicy,
+    expected_result: Result<()>,
+    expected_active_ids: &[u64],
+) {
+    let mut config = mock_config_linear();
+    config.set_self_cross_policy(policy);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(101, 0), qty).unwrap())
+        .unwrap();
+
+    // A sell below the ask does not cross the market, but it crosses the resting buy.
+    let result = exchange
+        .submit_limit_order(LimitOrder::new(Side::Sell, QuoteCurrency::new(101, 0), qty).unwrap())
+        .map(|_| ());
+    assert_eq!(result, expected_result);
+
+    let mut active_ids: Vec<OrderId> = exchange
+        .active_limit_orders()
+        .values()
+        .map(|order| order.id())
+        .collect();
+    active_ids.sort();
+    assert_eq!(
+        active_ids,
+        expected_active_ids
+            .iter()
+            .map(|id| OrderId::from(*id))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn self_cross_not_crossing() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(103, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(101, 0), qty).unwrap())
+        .unwrap();
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Sell, QuoteCurrency::new(102, 0), qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 2);
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn self_cross_cancel_resting_reported() {
+    let mut config = mock_config_linear();
+    config.set_self_cross_policy(SelfCrossPolicy::CancelResting);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let qty = BaseCurrency::new(1, 0);
+    let resting = exchange
+        .submit_limit_order(LimitOrder::new(Side::Buy, QuoteCurrency::new(101, 0), qty).unwrap())
+        .unwrap()
+        .resting()
+        .unwrap();
+
+    // A `GoodTilCrossing` sell at the bid is rejected, so the resting buy it crosses is kept.
+    assert!(matches!(
+        exchange.submit_limit_order(
+            LimitOrder::new(Side::Sell, QuoteCurrency::new(100, 0), qty).unwrap()
+        ),
+        Err(Error::OrderError(
+            OrderError::GoodTillCrossingRejectedOrder { .. }
+        ))
+    ));
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+
+    exchange
+        .submit_limit_order(LimitOrder::new(Side::Sell, QuoteCurrency::new(101, 0), qty).unwrap())
+        .unwrap();
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(102, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        outcome.limit_order_updates,
+        &vec![LimitOrderUpdate::Cancelled(resting)]
+    );
+}
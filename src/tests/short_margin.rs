@@ -0,0 +1,95 @@
+use const_decimal::Decimal;
+use test_case::test_case;
+
+use crate::{
+    DECIMALS, mock_exchange_linear_with_config, prelude::*, test_fee_maker, test_fee_taker,
+};
+
+fn config_with_short_margin() -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let mut contract_spec = ContractSpecification::new(
+        leverage!(2),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .expect("works");
+    contract_spec
+        .set_short_margin(leverage!(1), Decimal::try_from_scaled(5, 1).unwrap())
+        .unwrap();
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    config
+}
+
+#[test]
+fn short_margin_defaults_to_long_margin() {
+    let mut contract_spec =
+        ContractSpecification::<i64, DECIMALS, BaseCurrency<_, DECIMALS>>::default();
+    assert_eq!(
+        contract_spec.init_margin_req_short(),
+        contract_spec.init_margin_req()
+    );
+    assert_eq!(
+        contract_spec.maintenance_margin_short(),
+        contract_spec.maintenance_margin()
+    );
+    assert_eq!(
+        contract_spec.set_short_margin(leverage!(1), Decimal::ZERO),
+        Err(ConfigError::InvalidMaintenanceMarginFraction)
+    );
+
+    contract_spec
+        .set_short_margin(leverage!(4), Decimal::try_from_scaled(5, 1).unwrap())
+        .unwrap();
+    assert_eq!(
+        contract_spec.init_margin_req_for(Side::Sell),
+        Decimal::try_from_scaled(25, 2).unwrap()
+    );
+    assert_eq!(
+        contract_spec.maintenance_margin_for(Side::Sell),
+        Decimal::try_from_scaled(125, 3).unwrap()
+    );
+    assert_eq!(contract_spec.init_margin_req_for(Side::Buy), Decimal::ONE);
+    assert_eq!(contract_spec.order_init_margin_req(), Decimal::ONE);
+}
+
+#[tracing_test::traced_test]
+#[test_case(Side::Buy, QuoteCurrency::new(2525, 1); "long")]
+#[test_case(Side::Sell, QuoteCurrency::new(500, 0); "short")]
+fn short_margin_reservation(side: Side, expected_position_margin: QuoteCurrency<i64, DECIMALS>) {
+    let mut exchange = mock_exchange_linear_with_config(config_with_short_margin());
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: 0.into()
+            })
+            .unwrap()
+            .limit_order_updates
+            .is_empty()
+    );
+
+    let order = MarketOrder::new(side, BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        expected_position_margin
+    );
+
+    // Closing the position releases all of the margin.
+    let order = MarketOrder::new(side.inverted(), BaseCurrency::new(5, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        QuoteCurrency::zero()
+    );
+}
@@ -17,6 +17,7 @@ fn submit_limit_buy_order_no_position() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -52,7 +53,8 @@ fn submit_limit_buy_order_no_position() {
                 side: Side::Sell,
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
     let bid = QuoteCurrency::new(96, 0);
@@ -65,6 +67,7 @@ fn submit_limit_buy_order_no_position() {
                 timestamp_exchange_ns: 1.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let mut accounting = InMemoryTransactionAccounting::new(QuoteCurrency::new(1000, 0));
@@ -110,6 +113,7 @@ fn submit_limit_buy_order_no_position() {
                 timestamp_exchange_ns: 2.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -128,7 +132,8 @@ fn submit_limit_buy_order_no_position() {
                 side: Side::Buy,
                 timestamp_exchange_ns: 3.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
     assert_eq!(exchange.position(), &Position::Neutral);
@@ -147,6 +152,7 @@ fn submit_limit_buy_order_no_position_max() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -214,6 +220,7 @@ fn submit_limit_buy_order_with_long() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let qty = BaseCurrency::new(9, 0);
@@ -252,7 +259,8 @@ fn submit_limit_buy_order_with_long() {
                 ask: QuoteCurrency::new(101, 0),
                 timestamp_exchange_ns: 1.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -291,7 +299,8 @@ fn submit_limit_buy_order_with_long() {
                 side: Side::Buy,
                 timestamp_exchange_ns: 2.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
 
@@ -311,6 +320,7 @@ fn submit_limit_buy_order_with_short() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
     let order = MarketOrder::new(Side::Sell, BaseCurrency::new(9, 0)).unwrap();
@@ -378,7 +388,8 @@ fn submit_limit_buy_order_with_short() {
                 side: Side::Sell,
                 timestamp_exchange_ns: 1.into(),
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
 
@@ -396,7 +407,8 @@ fn submit_limit_buy_order_above_ask() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
     let order = LimitOrder::new(
@@ -16,6 +16,7 @@ fn submit_limit_sell_order_no_position() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -40,7 +41,8 @@ fn submit_limit_sell_order_no_position() {
                 side: Side::Buy,
                 timestamp_exchange_ns: 1.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
     exchange
@@ -100,7 +102,8 @@ fn submit_limit_sell_order_no_position() {
                 side: Side::Sell,
                 timestamp_exchange_ns: 3.into(),
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &vec![expected_order_update]
     );
     assert_eq!(exchange.position(), &Position::Neutral);
@@ -128,6 +131,7 @@ fn submit_limit_sell_order_no_position_max() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -190,7 +194,8 @@ fn submit_limit_sell_order_below_bid() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
     let order = LimitOrder::new(
@@ -13,7 +13,8 @@ fn submit_market_buy_order_reject() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -37,6 +38,7 @@ fn submit_market_buy_order_no_position() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
@@ -85,7 +87,8 @@ fn submit_market_buy_order_with_long_position() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -157,7 +160,8 @@ fn submit_market_buy_order_with_short_position() {
                 ask: QuoteCurrency::new(101, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -217,7 +221,8 @@ fn submit_market_buy_order_turnaround_short() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
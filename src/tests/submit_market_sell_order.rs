@@ -12,7 +12,8 @@ fn submit_market_sell_order_reject() {
                 ask: QuoteCurrency::new(101, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -35,7 +36,8 @@ fn submit_market_sell_order() {
                 ask: QuoteCurrency::new(101, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -82,7 +84,8 @@ fn submit_market_sell_order_with_short_position() {
                 ask: QuoteCurrency::new(101, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -147,7 +150,8 @@ fn submit_market_sell_order_with_long_position() {
                 ask: QuoteCurrency::new(100, 0),
                 timestamp_exchange_ns: 0.into()
             })
-            .unwrap(),
+            .unwrap()
+            .limit_order_updates,
         &Vec::new()
     );
 
@@ -186,6 +190,7 @@ fn submit_market_sell_order_turnaround_long() {
                 timestamp_exchange_ns: 0.into()
             })
             .unwrap()
+            .limit_order_updates
             .is_empty()
     );
 
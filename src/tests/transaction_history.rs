@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use const_decimal::Decimal;
+
+use crate::{DECIMALS, mock_config_linear, mock_exchange_linear, prelude::*};
+
+#[tracing_test::traced_test]
+#[test]
+fn transaction_history_export_csv_round_trip() {
+    let config = mock_config_linear();
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        RecordingTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+    >::new(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(110, 0),
+            ask: QuoteCurrency::new(111, 0),
+            timestamp_exchange_ns: 2.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Sell, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let mut buf = Vec::new();
+    exchange
+        .transaction_accounting()
+        .export_csv(&mut buf)
+        .unwrap();
+
+    let mut r = csv::Reader::from_reader(buf.as_slice());
+    assert_eq!(
+        r.headers().unwrap().iter().collect::<Vec<_>>(),
+        vec!["timestamp_ns", "debit_account", "credit_account", "amount"]
+    );
+    let rows: Vec<csv::StringRecord> = r.records().map(|row| row.unwrap()).collect();
+    assert_eq!(
+        rows.len(),
+        exchange.transaction_accounting().history().len()
+    );
+    assert_eq!(&rows[0][0], "0");
+    assert_eq!(&rows[0][1], "USER_WALLET_ACCOUNT");
+    assert_eq!(&rows[0][2], "TREASURY_ACCOUNT");
+    assert!(rows[1..].iter().any(|row| &row[0] == "1"));
+    assert!(rows[1..].iter().any(|row| &row[0] == "2"));
+
+    let mut net_balances = BTreeMap::<String, Decimal<i64, DECIMALS>>::new();
+    for row in rows.iter() {
+        let amount: Decimal<i64, DECIMALS> = row[3].parse().unwrap();
+        assert!(amount > Decimal::ZERO);
+        let debit = net_balances
+            .entry(row[1].to_string())
+            .or_insert(Decimal::ZERO);
+        *debit = *debit + amount;
+        let credit = net_balances
+            .entry(row[2].to_string())
+            .or_insert(Decimal::ZERO);
+        *credit = *credit - amount;
+    }
+    assert_eq!(
+        net_balances
+            .values()
+            .fold(Decimal::ZERO, |acc, balance| acc + *balance),
+        Decimal::ZERO
+    );
+    assert_eq!(
+        QuoteCurrency::from(net_balances["USER_WALLET_ACCOUNT"]),
+        exchange
+            .transaction_accounting()
+            .margin_balance_of(USER_WALLET_ACCOUNT)
+            .unwrap()
+    );
+    assert_eq!(
+        QuoteCurrency::from(net_balances["USER_WALLET_ACCOUNT"]),
+        exchange.user_balances().available_wallet_balance
+    );
+}
+
+#[test]
+fn transaction_history_not_retained() {
+    let exchange = mock_exchange_linear();
+    assert_eq!(
+        exchange.transaction_accounting().export_csv(Vec::new()),
+        Err(Error::TransactionHistoryNotRetained)
+    );
+}
+
+#[tracing_test::traced_test]
+#[test]
+fn transaction_history_chronological() {
+    let config = mock_config_linear();
+    let mut exchange = Exchange::<
+        i64,
+        DECIMALS,
+        BaseCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        RecordingTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+    >::new(config);
+    for (ts, side) in [(1, Side::Buy), (2, Side::Buy), (3, Side::Sell)] {
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(100, 0),
+                ask: QuoteCurrency::new(101, 0),
+                timestamp_exchange_ns: ts.into(),
+            })
+            .unwrap();
+        exchange
+            .submit_market_order(MarketOrder::new(side, BaseCurrency::new(2, 0)).unwrap())
+            .unwrap();
+    }
+
+    let timestamps: Vec<TimestampNs> = exchange
+        .transaction_accounting()
+        .history()
+        .iter()
+        .map(|t| t.timestamp_ns())
+        .collect();
+    assert_eq!(timestamps[0], TimestampNs::from(0));
+    assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+    for ts in 1..=3 {
+        assert!(timestamps.contains(&TimestampNs::from(ts)));
+    }
+}
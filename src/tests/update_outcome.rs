@@ -0,0 +1,136 @@
+use crate::{
+    mock_config_linear, mock_contract_spec_linear, mock_exchange_linear,
+    mock_exchange_linear_with_config, prelude::*,
+};
+
+#[test]
+#[tracing_test::traced_test]
+fn update_outcome_fill_and_cancellation() {
+    let mut config = mock_config_linear();
+    config.set_cancel_latency(Some(10.into())).unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    assert!(outcome.limit_order_updates.is_empty());
+    assert!(!outcome.liquidated);
+    assert!(!outcome.blown_up);
+    assert!(!outcome.expired);
+
+    let filled = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(100, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+    let cancelled = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(98, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+    exchange
+        .cancel_limit_order(CancelBy::OrderId(cancelled.id()))
+        .unwrap();
+
+    let outcome = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(99, 0),
+            quantity: BaseCurrency::one(),
+            side: Side::Sell,
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    assert!(!outcome.expired);
+    let fills: Vec<_> = outcome.fills().collect();
+    assert_eq!(fills.len(), 1);
+    let LimitOrderUpdate::FullyFilled(order) = fills[0] else {
+        panic!("Expected a full fill");
+    };
+    assert_eq!(order.id(), filled.id());
+    assert_eq!(
+        outcome.cancellations().collect::<Vec<_>>(),
+        vec![&LimitOrderUpdate::Cancelled(cancelled)]
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn update_outcome_expiry() {
+    let mut contract_spec = mock_contract_spec_linear();
+    contract_spec
+        .set_expiry(10.into(), QuoteCurrency::new(110, 0))
+        .unwrap();
+    let config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, QuoteCurrency::new(90, 0), BaseCurrency::one()).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 10.into(),
+        })
+        .unwrap();
+    assert!(outcome.expired);
+    assert_eq!(outcome.fills().count(), 0);
+    assert_eq!(
+        outcome.limit_order_updates,
+        &vec![LimitOrderUpdate::Cancelled(order)]
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn update_outcome_liquidation() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    exchange
+        .submit_market_order(MarketOrder::new(Side::Buy, BaseCurrency::new(5, 0)).unwrap())
+        .unwrap();
+
+    // The long position has a liquidation price of 50.5.
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(outcome.liquidated);
+    assert!(!outcome.blown_up);
+    assert!(!outcome.expired);
+    assert!(outcome.limit_order_updates.is_empty());
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
@@ -0,0 +1,73 @@
+use crate::{DECIMALS, mock_config_linear, prelude::*};
+
+type TaggedExchange = Exchange<
+    i64,
+    DECIMALS,
+    BaseCurrency<i64, DECIMALS>,
+    u64,
+    InMemoryTransactionAccounting<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>>,
+>;
+
+#[test]
+#[tracing_test::traced_test]
+fn user_order_id_is_preserved_in_fills() {
+    let mut exchange: TaggedExchange = Exchange::new(mock_config_linear());
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    let qty = BaseCurrency::one();
+    let fill = exchange
+        .submit_market_order(MarketOrder::new_with_user_order_id(Side::Buy, qty, 1).unwrap())
+        .unwrap();
+    assert_eq!(fill.order().user_order_id(), 1);
+
+    for (limit_price, user_order_id) in [(100, 2), (99, 3)] {
+        let order = exchange
+            .submit_limit_order(
+                LimitOrder::new_with_user_order_id(
+                    Side::Buy,
+                    QuoteCurrency::new(limit_price, 0),
+                    qty,
+                    user_order_id,
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .resting()
+            .unwrap();
+        assert_eq!(order.user_order_id(), user_order_id);
+    }
+    let mut resting_ids: Vec<u64> = exchange
+        .active_limit_orders()
+        .values()
+        .map(|order| order.user_order_id())
+        .collect();
+    resting_ids.sort();
+    assert_eq!(resting_ids, vec![2, 3]);
+
+    let updates = exchange
+        .update_state(&Trade {
+            price: QuoteCurrency::new(98, 0),
+            quantity: BaseCurrency::new(5, 0),
+            side: Side::Sell,
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap()
+        .limit_order_updates;
+    let mut filled_ids: Vec<u64> = updates
+        .iter()
+        .map(|update| match update {
+            LimitOrderUpdate::FullyFilled(order) => order.user_order_id(),
+            LimitOrderUpdate::PartiallyFilled(_) | LimitOrderUpdate::Cancelled(_) => {
+                panic!("Expected a full fill")
+            }
+        })
+        .collect();
+    filled_ids.sort();
+    assert_eq!(filled_ids, vec![2, 3]);
+}
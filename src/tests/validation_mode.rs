@@ -0,0 +1,147 @@
+use const_decimal::Decimal;
+use test_case::test_case;
+
+use crate::{
+    DECIMALS, mock_exchange_linear_with_config, prelude::*, test_bba, test_fee_maker,
+    test_fee_taker,
+};
+
+fn config_with_validation_mode(
+    validation_mode: ValidationMode,
+) -> Config<i64, DECIMALS, QuoteCurrency<i64, DECIMALS>> {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(Some(BaseCurrency::new(1, 1)), None, BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .expect("works");
+    let mut config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    config.set_validation_mode(validation_mode);
+    config
+}
+
+#[test]
+fn validation_mode_default() {
+    let exchange =
+        mock_exchange_linear_with_config(config_with_validation_mode(ValidationMode::default()));
+    assert_eq!(exchange.config().validation_mode(), ValidationMode::Strict);
+}
+
+#[tracing_test::traced_test]
+#[test_case(Side::Buy, 995, 99)]
+#[test_case(Side::Sell, 1015, 102)]
+fn validation_mode_limit_price_off_tick(side: Side, limit_price: i64, rounded_price: i64) {
+    let order = LimitOrder::new(
+        side,
+        QuoteCurrency::new(limit_price, 1),
+        BaseCurrency::one(),
+    )
+    .unwrap();
+
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_validation_mode(ValidationMode::Strict));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    assert!(matches!(
+        exchange.submit_limit_order(order.clone()),
+        Err(Error::OrderError(OrderError::Filter(
+            FilterError::PriceStepSize { .. }
+        )))
+    ));
+    assert!(exchange.active_limit_orders().is_empty());
+
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_validation_mode(ValidationMode::Lenient));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let order = exchange
+        .submit_limit_order(order)
+        .unwrap()
+        .resting()
+        .unwrap();
+    assert_eq!(order.limit_price(), QuoteCurrency::new(rounded_price, 0));
+    assert_eq!(order.remaining_quantity(), BaseCurrency::one());
+}
+
+#[tracing_test::traced_test]
+#[test_case(5, 2, OrderError::QuantityTooLow, 1, 1; "below_min")]
+#[test_case(1055, 3, OrderError::InvalidQuantityStepSize, 105, 2; "off_step")]
+fn validation_mode_market_order_quantity(
+    qty: i64,
+    qty_scale: u8,
+    strict_err: OrderError,
+    rounded_qty: i64,
+    rounded_qty_scale: u8,
+) {
+    let order = MarketOrder::new(Side::Buy, BaseCurrency::new(qty, qty_scale)).unwrap();
+
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_validation_mode(ValidationMode::Strict));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    assert_eq!(
+        exchange.submit_market_order(order.clone()),
+        Err(Error::OrderError(strict_err))
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+
+    let mut exchange =
+        mock_exchange_linear_with_config(config_with_validation_mode(ValidationMode::Lenient));
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    exchange.submit_market_order(order).unwrap();
+    assert_eq!(
+        exchange.position().quantity(),
+        BaseCurrency::new(rounded_qty, rounded_qty_scale)
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn validation_mode_lenient_liquidation_above_max_quantity() {
+    let contract_spec = ContractSpecification::new(
+        leverage!(1),
+        Decimal::try_from_scaled(5, 1).unwrap(),
+        PriceFilter::default(),
+        QuantityFilter::new(None, Some(BaseCurrency::new(3, 0)), BaseCurrency::new(1, 2)).unwrap(),
+        test_fee_maker(),
+        test_fee_taker(),
+    )
+    .expect("works");
+    let mut config = Config::new(
+        QuoteCurrency::new(1000, 0),
+        10,
+        contract_spec,
+        OrderRateLimits::default(),
+    )
+    .unwrap();
+    config.set_validation_mode(ValidationMode::Lenient);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange.update_state(&test_bba(100, 101, 0)).unwrap();
+    let qty = BaseCurrency::new(3, 0);
+    for _ in 0..2 {
+        exchange
+            .submit_market_order(MarketOrder::new(Side::Buy, qty).unwrap())
+            .unwrap();
+    }
+    assert_eq!(exchange.position().quantity(), qty + qty);
+
+    // The long position has a liquidation price of 50.5,
+    // and is closed entirely even though it exceeds the `max_quantity` of an order.
+    let outcome = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(40, 0),
+            ask: QuoteCurrency::new(41, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert!(outcome.liquidated);
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.closed_trades().len(), 1);
+    assert_eq!(exchange.closed_trades()[0].quantity(), qty + qty);
+}
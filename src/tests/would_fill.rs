@@ -0,0 +1,105 @@
+use crate::{
+    DECIMALS, mock_config_linear, mock_exchange_linear, mock_exchange_linear_with_config,
+    prelude::*,
+};
+
+fn trade(price: i64, side: Side, ts: i64) -> Trade<i64, DECIMALS, BaseCurrency<i64, DECIMALS>> {
+    Trade {
+        price: QuoteCurrency::new(price, 0),
+        quantity: BaseCurrency::new(1, 0),
+        side,
+        timestamp_exchange_ns: ts.into(),
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn would_fill_limit_order() {
+    let mut exchange = mock_exchange_linear();
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let limit_price = QuoteCurrency::new(100, 0);
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(Side::Buy, limit_price, BaseCurrency::new(2, 0)).unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+
+    // A trade at the limit price does not fill the order, as it has the worst queue position.
+    let crossing_path = [
+        trade(101, Side::Buy, 1),
+        trade(100, Side::Sell, 2),
+        trade(99, Side::Sell, 3),
+        trade(98, Side::Sell, 4),
+    ];
+    assert_eq!(
+        exchange.would_fill(&order, &crossing_path),
+        Some((2, limit_price))
+    );
+
+    let path_never_reached = [
+        trade(100, Side::Sell, 1),
+        trade(102, Side::Buy, 2),
+        trade(101, Side::Sell, 3),
+    ];
+    assert_eq!(exchange.would_fill(&order, &path_never_reached), None);
+
+    let bba_path = [Bba {
+        bid: QuoteCurrency::new(98, 0),
+        ask: QuoteCurrency::new(99, 0),
+        timestamp_exchange_ns: 1.into(),
+    }];
+    assert_eq!(exchange.would_fill(&order, &bba_path), None);
+
+    // The simulation does not change the state of the exchange.
+    assert_eq!(exchange.active_limit_orders().len(), 1);
+    assert_eq!(exchange.position(), &Position::Neutral);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn would_fill_favorable_touch() {
+    let mut config = mock_config_linear();
+    config.set_limit_fill_price(LimitFillPrice::FavorableTouch);
+    let mut exchange = mock_exchange_linear_with_config(config);
+    exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(100, 0),
+            ask: QuoteCurrency::new(101, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+    let order = exchange
+        .submit_limit_order(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(100, 0),
+                BaseCurrency::new(1, 0),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+
+    // The candle gaps through the limit price, so the order fills at its low.
+    let candle_path = [Candle::new(
+        QuoteCurrency::new(96, 0),
+        QuoteCurrency::new(97, 0),
+        QuoteCurrency::new(95, 0),
+        QuoteCurrency::new(98, 0),
+        1.into(),
+    )
+    .unwrap()];
+    assert_eq!(
+        exchange.would_fill(&order, &candle_path),
+        Some((0, QuoteCurrency::new(95, 0)))
+    );
+}
@@ -0,0 +1,57 @@
+use crate::{DECIMALS, mock_exchange_linear, prelude::*};
+
+#[test]
+#[tracing_test::traced_test]
+fn zero_quantity_market_order() {
+    let exchange = mock_exchange_linear();
+
+    // The order is rejected at construction, so it never reaches the exchange.
+    assert_eq!(
+        MarketOrder::<_, DECIMALS, _, NoUserOrderId, _>::new(
+            Side::Buy,
+            BaseCurrency::<i64, DECIMALS>::zero()
+        ),
+        Err(OrderError::OrderQuantityLTEZero)
+    );
+    assert_eq!(
+        MarketOrder::new_with_user_order_id(Side::Sell, BaseCurrency::<i64, DECIMALS>::zero(), 0),
+        Err(OrderError::OrderQuantityLTEZero)
+    );
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(1000, 0));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn zero_quantity_limit_order() {
+    let exchange = mock_exchange_linear();
+
+    assert_eq!(
+        LimitOrder::<_, DECIMALS, _, NoUserOrderId, _>::new(
+            Side::Buy,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::<i64, DECIMALS>::zero()
+        ),
+        Err(OrderError::OrderQuantityLTEZero)
+    );
+    assert_eq!(
+        LimitOrder::new_with_user_order_id(
+            Side::Sell,
+            QuoteCurrency::new(100, 0),
+            BaseCurrency::<i64, DECIMALS>::zero(),
+            0
+        ),
+        Err(OrderError::OrderQuantityLTEZero)
+    );
+    assert!(exchange.active_limit_orders().is_empty());
+    assert_eq!(exchange.wallet_balance(), QuoteCurrency::new(1000, 0));
+}
+
+#[test]
+fn zero_quantity_filter() {
+    let filter = QuantityFilter::<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>::default();
+    assert_eq!(
+        filter.validate_order_quantity(BaseCurrency::zero()),
+        Err(OrderError::QuantityTooLow)
+    );
+}
@@ -0,0 +1,236 @@
+use getset::CopyGetters;
+
+use super::{Currency, MarginCurrency, Mon, QuoteCurrency, Side, TimestampNs};
+use crate::position::Position;
+
+/// A completed round-trip trade, from the fill which opened a position until the fill which returned it to flat.
+///
+/// Increasing the position again after reducing it is part of the same trade,
+/// so the `entry_price` and `exit_price` are averages weighted by the quantity of all entering and exiting fills.
+/// A fill which flips the position closes the trade and opens a new one with the remaining quantity.
+///
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct ClosedTrade<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// `Side::Buy` for a long and `Side::Sell` for a short trade.
+    #[getset(get_copy = "pub")]
+    side: Side,
+
+    /// The total quantity entered, which equals the total quantity exited.
+    #[getset(get_copy = "pub")]
+    quantity: BaseOrQuote,
+
+    /// The average price of the entering fills.
+    #[getset(get_copy = "pub")]
+    entry_price: QuoteCurrency<I, D>,
+
+    /// The average price of the exiting fills.
+    #[getset(get_copy = "pub")]
+    exit_price: QuoteCurrency<I, D>,
+
+    /// The profit and loss of the exiting fills before fees,
+    /// each relative to the entry price of the position when it was filled.
+    #[getset(get_copy = "pub")]
+    gross_pnl: BaseOrQuote::PairedCurrency,
+
+    /// The total fees of all fills of the trade, regardless of whether they were paid upfront or deferred.
+    /// The fee of a fill which flips the position is part of the trade it closes.
+    #[getset(get_copy = "pub")]
+    fees: BaseOrQuote::PairedCurrency,
+
+    /// When the first entering fill happened.
+    #[getset(get_copy = "pub")]
+    entry_ts_ns: TimestampNs,
+
+    /// When the position returned to flat.
+    #[getset(get_copy = "pub")]
+    exit_ts_ns: TimestampNs,
+}
+
+impl<I, const D: u8, BaseOrQuote> ClosedTrade<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    /// The profit and loss after fees.
+    #[inline]
+    pub fn net_pnl(&self) -> BaseOrQuote::PairedCurrency {
+        self.gross_pnl - self.fees
+    }
+
+    /// How long the position was held.
+    #[inline]
+    pub fn holding_period_ns(&self) -> TimestampNs {
+        self.exit_ts_ns - self.entry_ts_ns
+    }
+}
+
+// The trade of the currently open position, which becomes a `ClosedTrade` once it returns to flat.
+#[derive(Debug, Clone)]
+struct OpenTrade<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    side: Side,
+    quantity: BaseOrQuote,
+    entry_price: QuoteCurrency<I, D>,
+    exit_quantity: BaseOrQuote,
+    exit_price: QuoteCurrency<I, D>,
+    gross_pnl: BaseOrQuote::PairedCurrency,
+    fees: BaseOrQuote::PairedCurrency,
+    entry_ts_ns: TimestampNs,
+}
+
+/// Accumulates the fills of the position into `ClosedTrade`s.
+#[derive(Debug, Clone)]
+pub(crate) struct TradeBlotter<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    open_trade: Option<OpenTrade<I, D, BaseOrQuote>>,
+    closed_trades: Vec<ClosedTrade<I, D, BaseOrQuote>>,
+}
+
+impl<I, const D: u8, BaseOrQuote> Default for TradeBlotter<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+{
+    fn default() -> Self {
+        Self {
+            open_trade: None,
+            closed_trades: Vec::new(),
+        }
+    }
+}
+
+impl<I, const D: u8, BaseOrQuote> TradeBlotter<I, D, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
+{
+    /// The completed round-trip trades, oldest first.
+    #[inline]
+    pub(crate) fn closed_trades(&self) -> &[ClosedTrade<I, D, BaseOrQuote>] {
+        &self.closed_trades
+    }
+
+    /// Record a fill of `filled_qty` at `fill_price` with its total `fees`, before it is applied to the `position`.
+    pub(crate) fn record_fill(
+        &mut self,
+        position: &Position<I, D, BaseOrQuote>,
+        side: Side,
+        filled_qty: BaseOrQuote,
+        fill_price: QuoteCurrency<I, D>,
+        fees: BaseOrQuote::PairedCurrency,
+        ts_ns: TimestampNs,
+    ) {
+        let (position_side, position) = match position {
+            Position::Neutral => {
+                self.open(side, filled_qty, fill_price, fees, ts_ns);
+                return;
+            }
+            Position::Long(inner) => (Side::Buy, inner),
+            Position::Short(inner) => (Side::Sell, inner),
+        };
+        let position_qty = position.quantity();
+        // A position which was not opened by a recorded fill starts its trade now.
+        let open_trade = self.open_trade.get_or_insert_with(|| OpenTrade {
+            side: position_side,
+            quantity: position_qty,
+            entry_price: position.entry_price(),
+            exit_quantity: BaseOrQuote::zero(),
+            exit_price: QuoteCurrency::zero(),
+            gross_pnl: BaseOrQuote::PairedCurrency::zero(),
+            fees: BaseOrQuote::PairedCurrency::zero(),
+            entry_ts_ns: ts_ns,
+        });
+        open_trade.fees += fees;
+        if side == position_side {
+            open_trade.entry_price = QuoteCurrency::new_weighted_price(
+                open_trade.entry_price,
+                *open_trade.quantity.as_ref(),
+                fill_price,
+                *filled_qty.as_ref(),
+            );
+            open_trade.quantity += filled_qty;
+            return;
+        }
+
+        let closing_qty = if filled_qty < position_qty {
+            filled_qty
+        } else {
+            position_qty
+        };
+        open_trade.gross_pnl += BaseOrQuote::PairedCurrency::pnl(
+            position.entry_price(),
+            fill_price,
+            match position_side {
+                Side::Buy => closing_qty,
+                Side::Sell => -closing_qty,
+            },
+        );
+        open_trade.exit_price = QuoteCurrency::new_weighted_price(
+            open_trade.exit_price,
+            *open_trade.exit_quantity.as_ref(),
+            fill_price,
+            *closing_qty.as_ref(),
+        );
+        open_trade.exit_quantity += closing_qty;
+        if filled_qty < position_qty {
+            return;
+        }
+
+        let open_trade = self.open_trade.take().expect("Is inserted above");
+        debug_assert_eq!(open_trade.quantity, open_trade.exit_quantity);
+        self.closed_trades.push(ClosedTrade {
+            side: open_trade.side,
+            quantity: open_trade.quantity,
+            entry_price: open_trade.entry_price,
+            exit_price: open_trade.exit_price,
+            gross_pnl: open_trade.gross_pnl,
+            fees: open_trade.fees,
+            entry_ts_ns: open_trade.entry_ts_ns,
+            exit_ts_ns: ts_ns,
+        });
+        if filled_qty > position_qty {
+            self.open(
+                side,
+                filled_qty - position_qty,
+                fill_price,
+                BaseOrQuote::PairedCurrency::zero(),
+                ts_ns,
+            );
+        }
+    }
+
+    fn open(
+        &mut self,
+        side: Side,
+        quantity: BaseOrQuote,
+        entry_price: QuoteCurrency<I, D>,
+        fees: BaseOrQuote::PairedCurrency,
+        ts_ns: TimestampNs,
+    ) {
+        self.open_trade = Some(OpenTrade {
+            side,
+            quantity,
+            entry_price,
+            exit_quantity: BaseOrQuote::zero(),
+            exit_price: QuoteCurrency::zero(),
+            gross_pnl: BaseOrQuote::PairedCurrency::zero(),
+            fees,
+            entry_ts_ns: ts_ns,
+        });
+    }
+}
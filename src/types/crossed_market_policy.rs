@@ -0,0 +1,10 @@
+/// How a market update is handled whose bid is at or above its ask, e.g due to bad data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CrossedMarketPolicy {
+    /// The market update is rejected with `Error::CrossedMarket`, leaving the bid and ask unchanged.
+    #[default]
+    Reject,
+    /// The bid is set to the mid price rounded down to the `tick_size` of the `PriceFilter`
+    /// and the ask one tick above it.
+    Clamp,
+}
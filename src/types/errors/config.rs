@@ -31,6 +31,42 @@ pub enum ConfigError {
     #[error("The maintenance margin fraction is invalid")]
     InvalidMaintenanceMarginFraction,
 
+    #[error("The maintenance margin buffer must be >= 0")]
+    InvalidMaintenanceMarginBuffer,
+
+    #[error("The pnl settlement increment must be > 0")]
+    InvalidPnlSettlementIncrement,
+
+    #[error("The settlement price must be > 0")]
+    InvalidSettlementPrice,
+
     #[error("Invalid order limits")]
     InvalidOrderLimits,
+
+    #[error("The `max_fill_ratio_per_tick` must be > 0 and <= 1")]
+    InvalidMaxFillRatioPerTick,
+
+    #[error("The `max_price_move_pct` must be > 0")]
+    InvalidMaxPriceMove,
+
+    #[error("The `max_trades_per_tick` must be > 0")]
+    InvalidMaxTradesPerTick,
+
+    #[error("The `cooldown` must be > 0")]
+    InvalidCooldown,
+
+    #[error("The `cancel_latency` must be > 0")]
+    InvalidCancelLatency,
+
+    #[error("The initial treasury balance must be >= 0")]
+    InvalidInitialTreasuryBalance,
+
+    #[error("The per contract fee must be >= 0")]
+    InvalidPerContractFee,
+
+    #[error("The `min_account_balance` must be > 0 and < the starting wallet balance")]
+    InvalidMinAccountBalance,
+
+    #[error("The price sources must not be empty and each source must only occur once")]
+    InvalidPriceSources,
 }
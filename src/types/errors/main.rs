@@ -49,9 +49,56 @@ pub enum Error {
     #[error("Unable to create `Decimal`")]
     UnableToCreateDecimal,
 
+    #[error("The float value is NaN or infinite.")]
+    NonFiniteFloat,
+
     #[error("The order rate limit was reached for this period.")]
     RateLimitReached,
 
     #[error("The provided prices for `Candle` don't make sense.")]
     InvalidCandlePrices,
+
+    #[error("The market update moved the price by more than `max_price_move_pct`.")]
+    PriceMoveTooLarge,
+
+    #[error("The maximum number of trades within this market update is reached.")]
+    MaxTradesPerTickReached,
+
+    #[error("The `cooldown` since the last closed position has not elapsed yet.")]
+    CooldownActive,
+
+    #[error(
+        "The account equity fell below the `min_account_balance`, so no further orders are accepted."
+    )]
+    AccountBlownUp,
+
+    #[error("No market data has been received yet, so orders can not be priced.")]
+    NoMarketData,
+
+    #[error("The bid of the market update is at or above its ask.")]
+    CrossedMarket,
+
+    #[error("Invalid strategy script: {0}")]
+    InvalidScript(String),
+
+    #[error("The limit order would cross a resting limit order of the opposite side.")]
+    SelfCross,
+
+    #[error("The contract has expired and was settled, so no further orders are accepted.")]
+    ContractExpired,
+
+    #[error("The transaction accounting does not retain its history, so it can not be exported.")]
+    TransactionHistoryNotRetained,
+
+    #[error("Unable to export the transaction history: {0}")]
+    TransactionHistoryExport(String),
+
+    #[error("The margin amount must be greater than zero.")]
+    InvalidMarginAmount,
+
+    #[error("There is no open position.")]
+    NoPosition,
+
+    #[error("No positive price realizes the targeted profit and loss.")]
+    UnreachablePnl,
 }
@@ -1,6 +1,6 @@
 use const_decimal::Decimal;
 
-use super::Mon;
+use super::{MarginCurrency, Mon, QuoteCurrency};
 
 /// Fee as a part per one hundred thousand.
 /// The generic `MarkerTaker` marker indicates to the type system if its a maker or taker fee.
@@ -29,6 +29,48 @@ impl<I, const D: u8, MakerTaker> AsRef<Decimal<I, D>> for Fee<I, D, MakerTaker>
     }
 }
 
+/// Determines when the fees of a fill are deducted from the users wallet.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeMode {
+    /// The fees accrue as `outstanding_fees` of the position
+    /// and are settled once the position is reduced.
+    #[default]
+    Deferred,
+    /// The fees are deducted from the wallet immediately when the order is filled.
+    Upfront,
+}
+
+/// Determines how the fees of a fill are computed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeModel<M> {
+    /// The `fee_maker` or `fee_taker` of the `ContractSpecification` is charged as a fraction of the notional value.
+    #[default]
+    Percentage,
+    /// A flat fee is charged for each filled contract, denoted in the margin currency.
+    PerContract(M),
+}
+
+impl<M> FeeModel<M> {
+    /// Compute the fee of filling `quantity` contracts at `price`.
+    /// The `fee` is the fraction of the notional value charged by `FeeModel::Percentage`.
+    #[inline]
+    pub fn fee_of<I, const D: u8>(
+        &self,
+        quantity: M::PairedCurrency,
+        price: QuoteCurrency<I, D>,
+        fee: Decimal<I, D>,
+    ) -> M
+    where
+        I: Mon<D>,
+        M: MarginCurrency<I, D>,
+    {
+        match self {
+            FeeModel::Percentage => M::convert_from(quantity, price) * fee,
+            FeeModel::PerContract(per_contract) => *per_contract * *quantity.as_ref(),
+        }
+    }
+}
+
 /// The fee limit orders pay.
 #[derive(Debug, Clone, Copy)]
 pub struct Maker;
@@ -40,6 +82,7 @@ pub struct Taker;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prelude::BaseCurrency;
 
     #[test]
     fn size_of_fee() {
@@ -47,4 +90,19 @@ mod tests {
         assert_eq!(std::mem::size_of::<Fee<i32, 5, Taker>>(), 4);
         assert_eq!(std::mem::size_of::<Fee<i64, 5, Maker>>(), 8);
     }
+
+    #[test]
+    fn fee_model_fee_of() {
+        let qty = BaseCurrency::<i64, 5>::new(3, 0);
+        let price = QuoteCurrency::new(100, 0);
+        let fee = Decimal::try_from_scaled(1, 3).unwrap();
+        assert_eq!(
+            FeeModel::Percentage.fee_of(qty, price, fee),
+            QuoteCurrency::new(3, 1)
+        );
+        assert_eq!(
+            FeeModel::PerContract(QuoteCurrency::new(25, 2)).fee_of(qty, price, fee),
+            QuoteCurrency::new(75, 2)
+        );
+    }
 }
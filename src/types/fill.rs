@@ -0,0 +1,62 @@
+use getset::{CopyGetters, Getters};
+
+use super::{Currency, Filled, LiquidityRole, MarketOrder, Mon, QuoteCurrency, UserOrderId};
+
+/// The details of an executed market order, allowing a strategy to judge its execution quality.
+/// A limit order with `RePricing::Marketable` which is filled on submission is reported as a market order with its id.
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct Fill<I, const D: u8, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// The filled order.
+    #[getset(get = "pub")]
+    order: MarketOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>,
+
+    /// The total fee charged for the fill, regardless of whether it is paid upfront or deferred.
+    #[getset(get_copy = "pub")]
+    fee: BaseOrQuote::PairedCurrency,
+
+    /// Whether the fill added or took liquidity.
+    #[getset(get_copy = "pub")]
+    liquidity_role: LiquidityRole,
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> Fill<I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// Create a new instance of `Self`.
+    pub(crate) fn new(
+        order: MarketOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>,
+        fee: BaseOrQuote::PairedCurrency,
+        liquidity_role: LiquidityRole,
+    ) -> Self {
+        Self {
+            order,
+            fee,
+            liquidity_role,
+        }
+    }
+
+    /// The filled quantity.
+    #[inline]
+    pub fn quantity(&self) -> BaseOrQuote {
+        self.order.state().filled_qty()
+    }
+
+    /// The average price of the fill.
+    #[inline]
+    pub fn avg_price(&self) -> QuoteCurrency<I, D> {
+        self.order.state().avg_fill_price()
+    }
+}
@@ -36,6 +36,8 @@ where
     }
 
     /// Compute the initial margin requirement from leverage.
+    /// This is computed in exact decimal arithmetic with the precision `D`,
+    /// so a repeating decimal like `1 / 3` is truncated towards zero and yields the same value on every run.
     #[inline]
     pub fn init_margin_req(&self) -> Decimal<I, D> {
         Decimal::one() / self.0
@@ -72,5 +74,13 @@ mod tests {
             Leverage::<i32, 0>::new(2).unwrap().init_margin_req(),
             Decimal::one() / Decimal::TWO
         );
+        assert_eq!(
+            Leverage::<i64, 5>::new(3).unwrap().init_margin_req(),
+            Decimal::try_from_scaled(33333, 5).unwrap()
+        );
+        assert_eq!(
+            Leverage::<i64, 5>::new(7).unwrap().init_margin_req(),
+            Decimal::try_from_scaled(14285, 5).unwrap()
+        );
     }
 }
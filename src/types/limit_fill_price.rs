@@ -0,0 +1,69 @@
+use super::{Mon, QuoteCurrency, Side};
+
+/// Determines at which price a limit order is filled when the market trades through its limit price.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LimitFillPrice {
+    /// The order is always filled at its limit price, granting no price improvement.
+    #[default]
+    LimitPrice,
+    /// The order is filled at the most favorable price touched by the market update,
+    /// e.g. the trade price of a `Trade` or the low of a `Candle` for a buy order.
+    /// Market updates which don't provide such a price fill at the limit price.
+    FavorableTouch,
+}
+
+impl LimitFillPrice {
+    /// The fill price of a limit order on `side` at `limit_price`,
+    /// given the most favorable `touch_price` of the market update, if any.
+    #[inline]
+    pub fn fill_price<I, const D: u8>(
+        &self,
+        side: Side,
+        limit_price: QuoteCurrency<I, D>,
+        touch_price: Option<QuoteCurrency<I, D>>,
+    ) -> QuoteCurrency<I, D>
+    where
+        I: Mon<D>,
+    {
+        match (self, touch_price) {
+            (LimitFillPrice::LimitPrice, _) | (LimitFillPrice::FavorableTouch, None) => limit_price,
+            (LimitFillPrice::FavorableTouch, Some(touch_price)) => match side {
+                Side::Buy => limit_price.min(touch_price),
+                Side::Sell => limit_price.max(touch_price),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_fill_price() {
+        let limit_price = QuoteCurrency::<i64, 5>::new(100, 0);
+        let touch = Some(QuoteCurrency::new(95, 0));
+        assert_eq!(
+            LimitFillPrice::LimitPrice.fill_price(Side::Buy, limit_price, touch),
+            limit_price
+        );
+        assert_eq!(
+            LimitFillPrice::FavorableTouch.fill_price(Side::Buy, limit_price, touch),
+            QuoteCurrency::new(95, 0)
+        );
+        assert_eq!(
+            LimitFillPrice::FavorableTouch.fill_price(Side::Buy, limit_price, None),
+            limit_price
+        );
+        let touch = Some(QuoteCurrency::new(105, 0));
+        assert_eq!(
+            LimitFillPrice::FavorableTouch.fill_price(Side::Sell, limit_price, touch),
+            QuoteCurrency::new(105, 0)
+        );
+        // A touch price worse than the limit price never worsens the fill.
+        assert_eq!(
+            LimitFillPrice::FavorableTouch.fill_price(Side::Buy, limit_price, touch),
+            limit_price
+        );
+    }
+}
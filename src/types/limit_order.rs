@@ -18,7 +18,7 @@ use crate::{
 /// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
 /// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
 /// - `OrderStatus`: The status of the order for each stage, contains different information based on the stage.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters, Setters)]
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters, Setters, Serialize, Deserialize)]
 pub struct LimitOrder<I, const D: u8, BaseOrQuote, UserOrderIdT, OrderStatus>
 where
     I: Mon<D>,
@@ -178,6 +178,12 @@ where
         assert!(new_qty > BaseOrQuote::zero());
         self.remaining_quantity = new_qty;
     }
+
+    /// Modify the `limit_price`, which must be greater than zero.
+    pub(crate) fn set_limit_price(&mut self, limit_price: QuoteCurrency<I, D>) {
+        assert!(limit_price > QuoteCurrency::zero());
+        self.limit_price = limit_price;
+    }
 }
 
 impl<I, const D: u8, BaseOrQuote, UserOrderIdT>
@@ -188,14 +194,35 @@ where
     BaseOrQuote::PairedCurrency: MarginCurrency<I, D>,
     UserOrderIdT: UserOrderId,
 {
-    /// Used when an order gets some `quantity` filled at a `price`.
+    /// Modify the `remaining_quantity` of a resting order, which is amended in place.
+    /// The `new_qty` must be GT than zero.
+    pub(crate) fn set_remaining_quantity(&mut self, new_qty: BaseOrQuote) {
+        assert!(new_qty > BaseOrQuote::zero());
+        self.remaining_quantity = new_qty;
+    }
+
+    /// Used when an order gets some `quantity` filled at its limit price.
     ///
     /// # Returns:
     /// Some(filled_order), if the order is fully filled.
+    #[cfg(test)]
     pub(crate) fn fill(
         &mut self,
         filled_quantity: BaseOrQuote,
         ts_ns: TimestampNs,
+    ) -> Option<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>> {
+        self.fill_at(filled_quantity, self.limit_price(), ts_ns)
+    }
+
+    /// Used when an order gets some `quantity` filled at a `price`.
+    ///
+    /// # Returns:
+    /// Some(filled_order), if the order is fully filled.
+    pub(crate) fn fill_at(
+        &mut self,
+        filled_quantity: BaseOrQuote,
+        price: QuoteCurrency<I, D>,
+        ts_ns: TimestampNs,
     ) -> Option<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>> {
         assert!(
             filled_quantity <= self.remaining_quantity,
@@ -205,7 +232,6 @@ where
             filled_quantity > BaseOrQuote::zero(),
             "Filled quantity must be greater than zero."
         );
-        let price = self.limit_price();
         let meta = self.state.meta().clone();
 
         match &mut self.state.filled_quantity {
@@ -0,0 +1,55 @@
+use super::{Currency, Fill, LimitOrder, Mon, OrderId, Pending, UserOrderId};
+
+/// The result of submitting a limit order with `Exchange::submit_limit_order`.
+/// Generics:
+/// - `I`: The numeric data type of currencies.
+/// - `D`: The constant decimal precision of the currencies.
+/// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
+/// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LimitOrderSubmission<I, const D: u8, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// The order rests in the book with its timestamp and id filled in, until it is filled or cancelled.
+    Resting(LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>),
+    /// The marketable order with `RePricing::Marketable` was filled entirely on submission,
+    /// taking liquidity at the away quote and paying the taker fee.
+    Filled(Fill<I, D, BaseOrQuote, UserOrderIdT>),
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT>
+    LimitOrderSubmission<I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D>,
+    BaseOrQuote: Currency<I, D>,
+    UserOrderIdT: UserOrderId,
+{
+    /// The id the exchange assigned to the order.
+    pub fn id(&self) -> OrderId {
+        match self {
+            Self::Resting(order) => order.id(),
+            Self::Filled(fill) => fill.order().state().meta().id(),
+        }
+    }
+
+    /// The resting order, or `None` if it was filled on submission.
+    pub fn resting(
+        self,
+    ) -> Option<LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>> {
+        match self {
+            Self::Resting(order) => Some(order),
+            Self::Filled(_) => None,
+        }
+    }
+
+    /// The fill of the order, or `None` if it rests in the book.
+    pub fn fill(self) -> Option<Fill<I, D, BaseOrQuote, UserOrderIdT>> {
+        match self {
+            Self::Resting(_) => None,
+            Self::Filled(fill) => Some(fill),
+        }
+    }
+}
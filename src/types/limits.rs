@@ -1,29 +1,55 @@
 use getset::CopyGetters;
 
-use super::{ConfigError, Error};
+use super::{ConfigError, Error, NANOS_PER_SECOND, TimestampNs};
 
 /// Defines the maximum order message rates, e.g.: limits order submission to 10 per second.
+/// The limit is enforced with a token bucket, which allows a burst of `orders_per_window` actions
+/// and then refills evenly over the `window_ns`.
 #[derive(Debug, Clone, Copy, CopyGetters, Serialize, Deserialize)]
 pub struct OrderRateLimits {
-    /// How many orders can be submitted per second.
+    /// How many orders can be submitted within the window.
     #[getset(get_copy = "pub")]
-    orders_per_second: u16,
+    #[serde(alias = "orders_per_second")]
+    orders_per_window: u16,
+    /// The window in nanoseconds over which the limit applies.
+    #[serde(default = "default_window_ns")]
+    window_ns: i64,
+}
+
+fn default_window_ns() -> i64 {
+    NANOS_PER_SECOND
 }
 
 impl Default for OrderRateLimits {
     fn default() -> Self {
         Self {
-            orders_per_second: 10,
+            orders_per_window: 10,
+            window_ns: default_window_ns(),
         }
     }
 }
 
 impl OrderRateLimits {
-    /// Create a new instance if `orders_per_second` != 0
+    /// Create a new instance allowing `orders_per_second` if it is != 0
     pub fn new(orders_per_second: u16) -> crate::Result<Self> {
-        if orders_per_second == 0 {
+        Self::per_window(orders_per_second, default_window_ns().into())
+    }
+
+    /// Create a new instance allowing `orders_per_window` within every `window_ns`,
+    /// if both are greater than zero.
+    pub fn per_window(orders_per_window: u16, window_ns: TimestampNs) -> crate::Result<Self> {
+        if orders_per_window == 0 || window_ns <= TimestampNs::from(0) {
             return Err(Error::ConfigError(ConfigError::InvalidOrderLimits));
         }
-        Ok(Self { orders_per_second })
+        Ok(Self {
+            orders_per_window,
+            window_ns: window_ns.into(),
+        })
+    }
+
+    /// The window over which the limit applies.
+    #[inline]
+    pub fn window_ns(&self) -> TimestampNs {
+        self.window_ns.into()
     }
 }
@@ -0,0 +1,8 @@
+/// Whether an order adds liquidity to the book or takes liquidity from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityRole {
+    /// The order rests in the book and pays the maker fee.
+    Maker,
+    /// The order crosses the spread and pays the taker fee.
+    Taker,
+}
@@ -0,0 +1,109 @@
+use const_decimal::Decimal;
+
+use num_traits::{Signed, Zero};
+
+use super::{Currency, Mon, QuoteCurrency, Side};
+
+/// Determines at which price a market order is filled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MarketFillPrice {
+    /// Buy orders are filled at the ask and sell orders at the bid,
+    /// which is the mid price plus half the spread as the implicit cost of crossing it on entry and on exit.
+    #[default]
+    BidAsk,
+    /// The order is filled at the mid price, while half the spread is charged on top of the fee as an explicit cost.
+    /// The total cost equals the one of `BidAsk`, but the fill price is not skewed by the spread
+    /// and the spread cost is reported and collected as part of the fee of the `Fill`, including for liquidations.
+    MidPrice,
+}
+
+impl MarketFillPrice {
+    /// The fill price of a market order on `side`, given the current `bid` and `ask`.
+    #[inline]
+    pub fn fill_price<I, const D: u8>(
+        &self,
+        side: Side,
+        bid: QuoteCurrency<I, D>,
+        ask: QuoteCurrency<I, D>,
+    ) -> QuoteCurrency<I, D>
+    where
+        I: Mon<D>,
+    {
+        match (self, side) {
+            (MarketFillPrice::BidAsk, Side::Buy) => ask,
+            (MarketFillPrice::BidAsk, Side::Sell) => bid,
+            (MarketFillPrice::MidPrice, _) => (bid + ask) / Decimal::TWO,
+        }
+    }
+
+    /// The explicit cost of crossing half the spread with a market order of `quantity` on `side`,
+    /// denoted in the margin currency. It is zero for `BidAsk`, where the spread is part of the fill price.
+    #[inline]
+    pub fn spread_cost<I, const D: u8, BaseOrQuote>(
+        &self,
+        side: Side,
+        quantity: BaseOrQuote,
+        bid: QuoteCurrency<I, D>,
+        ask: QuoteCurrency<I, D>,
+    ) -> BaseOrQuote::PairedCurrency
+    where
+        I: Mon<D>,
+        BaseOrQuote: Currency<I, D>,
+    {
+        match self {
+            MarketFillPrice::BidAsk => BaseOrQuote::PairedCurrency::zero(),
+            MarketFillPrice::MidPrice => {
+                let away_price = match side {
+                    Side::Buy => ask,
+                    Side::Sell => bid,
+                };
+                (BaseOrQuote::PairedCurrency::convert_from(quantity, away_price)
+                    - BaseOrQuote::PairedCurrency::convert_from(
+                        quantity,
+                        self.fill_price(side, bid, ask),
+                    ))
+                .abs()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BaseCurrency;
+
+    #[test]
+    fn market_fill_price() {
+        let bid = QuoteCurrency::<i64, 5>::new(100, 0);
+        let ask = QuoteCurrency::new(102, 0);
+        assert_eq!(MarketFillPrice::BidAsk.fill_price(Side::Buy, bid, ask), ask);
+        assert_eq!(
+            MarketFillPrice::BidAsk.fill_price(Side::Sell, bid, ask),
+            bid
+        );
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(
+                MarketFillPrice::MidPrice.fill_price(side, bid, ask),
+                QuoteCurrency::new(101, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn market_fill_price_spread_cost() {
+        let bid = QuoteCurrency::<i64, 5>::new(100, 0);
+        let ask = QuoteCurrency::new(102, 0);
+        let qty = BaseCurrency::new(5, 0);
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(
+                MarketFillPrice::BidAsk.spread_cost(side, qty, bid, ask),
+                QuoteCurrency::zero()
+            );
+            assert_eq!(
+                MarketFillPrice::MidPrice.spread_cost(side, qty, bid, ask),
+                QuoteCurrency::new(5, 0)
+            );
+        }
+    }
+}
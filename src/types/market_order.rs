@@ -12,7 +12,7 @@ use super::{
 /// - `BaseOrQuote`: Either `BaseCurrency` or `QuoteCurrency` depending on the futures type.
 /// - `UserOrderId`: The type of user order id to use. Set to `()` if you don't need one.
 /// - `OrderStatus`: The status of the order for each stage, contains different information based on the stage.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters, Serialize, Deserialize)]
 pub struct MarketOrder<I, const D: u8, BaseOrQuote, UserOrderIdT, OrderStatus>
 where
     I: Mon<D>,
@@ -110,6 +110,12 @@ where
         })
     }
 
+    /// Modify the `quantity`, which must be greater than zero.
+    pub(crate) fn set_quantity(&mut self, quantity: BaseOrQuote) {
+        assert!(quantity > BaseOrQuote::zero());
+        self.quantity = quantity;
+    }
+
     /// Take in the order metadata provided by the exchange and coverts the order to the `Pending` state.
     pub fn into_pending(
         self,
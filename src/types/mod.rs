@@ -1,33 +1,58 @@
+mod closed_trade;
+mod crossed_market_policy;
 mod errors;
 mod fee;
+mod fill;
 mod leverage;
+mod limit_fill_price;
 mod limit_order;
+mod limit_order_submission;
 mod limits;
+mod liquidity_role;
+mod market_fill_price;
 mod market_order;
 mod order_id;
 mod order_meta;
 mod order_status;
 mod order_update;
+mod position_sizing;
+mod price_source;
 mod re_pricing;
+mod self_cross_policy;
 mod side;
 mod smol_currency;
 mod timestamp_ns;
+mod update_outcome;
+mod validation_mode;
 
+pub use closed_trade::ClosedTrade;
+pub(crate) use closed_trade::TradeBlotter;
+pub use crossed_market_policy::CrossedMarketPolicy;
 pub use errors::*;
-pub use fee::{Fee, Maker, Taker};
+pub use fee::{Fee, FeeMode, FeeModel, Maker, Taker};
+pub use fill::Fill;
 pub use leverage::Leverage;
+pub use limit_fill_price::LimitFillPrice;
 pub use limit_order::LimitOrder;
+pub use limit_order_submission::LimitOrderSubmission;
 pub use limits::OrderRateLimits;
+pub use liquidity_role::LiquidityRole;
+pub use market_fill_price::MarketFillPrice;
 pub use market_order::MarketOrder;
 pub use order_id::OrderId;
 pub use order_meta::ExchangeOrderMeta;
 pub use order_status::{Filled, FilledQuantity, NewOrder, Pending};
 pub use order_update::LimitOrderUpdate;
+pub use position_sizing::PositionSizing;
+pub use price_source::{PriceSource, PriceSources};
 pub use re_pricing::RePricing;
+pub use self_cross_policy::SelfCrossPolicy;
 pub use side::Side;
 pub use smol_currency::{BaseCurrency, Currency, MarginCurrency, Mon, QuoteCurrency};
 pub(crate) use timestamp_ns::NANOS_PER_SECOND;
 pub use timestamp_ns::TimestampNs;
+pub use update_outcome::UpdateOutcome;
+pub use validation_mode::ValidationMode;
 
 /// Natural Logarithmic Returns newtype wrapping a borrowed slice of generic floats.
 pub struct LnReturns<'a, T: num_traits::Float>(pub &'a [T]);
@@ -74,6 +99,26 @@ where
     }
 }
 
+/// What is locked by the account and why, denoted in the margin currency.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MarginBreakdown<I, const D: u8, BaseOrQuote>
+where
+    I: Mon<D>,
+    BaseOrQuote: MarginCurrency<I, D>,
+{
+    /// The initial margin locked for the position.
+    pub initial_margin: BaseOrQuote,
+    /// The maintenance margin of the position at its entry price, below which its equity must not fall.
+    /// It is part of the `initial_margin` and does not lock any additional balance.
+    pub maintenance_margin: BaseOrQuote,
+    /// The margin reserved for the open limit orders.
+    pub order_margin: BaseOrQuote,
+    /// The fees of the position which are settled once it is reduced.
+    pub outstanding_fees: BaseOrQuote,
+    /// Just a marker type.
+    pub _q: std::marker::PhantomData<QuoteCurrency<I, D>>,
+}
+
 /// A custom user order id must satisfy this trait bound.
 pub trait UserOrderId:
     Clone + Copy + Eq + PartialEq + std::fmt::Debug + std::fmt::Display + Default
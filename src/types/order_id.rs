@@ -1,7 +1,19 @@
 use std::fmt::Display;
 
 /// The type for the global order id sequence number used by the exchange.
-#[derive(Debug, Default, Clone, Copy, std::hash::Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    std::hash::Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
 pub struct OrderId(u64);
 
 impl From<u64> for OrderId {
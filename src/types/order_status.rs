@@ -4,7 +4,7 @@ use super::{Currency, Mon, QuoteCurrency, TimestampNs, order_meta::ExchangeOrder
 
 /// A new order has not been received by the exchange and has thus some pieces of information not available.
 /// This also means the various filters (e.g `PriceFilter` and `QuantityFilter`) have not been checked.
-#[derive(Debug, Clone, Eq, PartialEq, derive_more::Display)]
+#[derive(Debug, Clone, Eq, PartialEq, derive_more::Display, Serialize, Deserialize)]
 pub struct NewOrder;
 
 /// The order is pending execution, but it already has additional information filled in by the exchange.
@@ -16,4 +16,6 @@ where
     /// The limit order was fully filled.
     // TODO: add the filled quantity
     FullyFilled(LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Filled<I, D, BaseOrQuote>>),
+    /// The limit order was cancelled once the `cancel_latency` elapsed.
+    Cancelled(LimitOrder<I, D, BaseOrQuote, UserOrderIdT, Pending<I, D, BaseOrQuote>>),
 }
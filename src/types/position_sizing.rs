@@ -0,0 +1,9 @@
+/// Which wallet balance to use when sizing an order by a fraction of the balance.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PositionSizing {
+    /// Size off the current wallet balance, so profits and losses compound over time.
+    #[default]
+    Compounding,
+    /// Size off the starting wallet balance, so the order size does not depend on past performance.
+    Fixed,
+}
@@ -0,0 +1,70 @@
+use super::ConfigError;
+
+/// A price the mark price can be taken from, in the priority order of `MarkMethod::Fallback`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    /// The price of the last trade.
+    LastTrade,
+    /// The mid price of the best bid and ask.
+    MidPrice,
+    /// The best bid for a long position and the best ask for a short position.
+    BidAsk,
+}
+
+/// The `PriceSource`s of `MarkMethod::Fallback` in order of priority.
+/// Each source occurs at most once, so they fit into a fixed array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceSources {
+    sources: [PriceSource; 3],
+    len: u8,
+}
+
+impl PriceSources {
+    /// Create a new instance from the `sources` in order of priority.
+    /// Errors if `sources` is empty or contains a source more than once.
+    pub fn new(sources: &[PriceSource]) -> Result<Self, ConfigError> {
+        if sources.is_empty() {
+            return Err(ConfigError::InvalidPriceSources);
+        }
+        let mut unique = [PriceSource::LastTrade; 3];
+        for (i, source) in sources.iter().enumerate() {
+            // Without duplicates there are at most as many sources as there are variants.
+            if sources[..i].contains(source) {
+                return Err(ConfigError::InvalidPriceSources);
+            }
+            unique[i] = *source;
+        }
+        Ok(Self {
+            sources: unique,
+            len: sources.len() as u8,
+        })
+    }
+
+    /// The sources in order of priority.
+    #[inline]
+    pub fn as_slice(&self) -> &[PriceSource] {
+        &self.sources[..self.len as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_sources() {
+        let sources = PriceSources::new(&[PriceSource::LastTrade, PriceSource::MidPrice]).unwrap();
+        assert_eq!(
+            sources.as_slice(),
+            &[PriceSource::LastTrade, PriceSource::MidPrice]
+        );
+        assert_eq!(
+            PriceSources::new(&[]),
+            Err(ConfigError::InvalidPriceSources)
+        );
+        assert_eq!(
+            PriceSources::new(&[PriceSource::BidAsk, PriceSource::BidAsk]),
+            Err(ConfigError::InvalidPriceSources)
+        );
+    }
+}
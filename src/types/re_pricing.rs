@@ -1,12 +1,12 @@
 /// When the limit order is priced at marketable prices (e.g a buy at or above the ask price),
 /// decide what to do.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RePricing {
-    // TODO: impl `Marketable`, requiring proper order book and matching engine i suppose.
-    // A limit order is marketable if it can take liquidity from the book.
-    // if the entry price locks or crosses an away market quotation it will immediately be filled
-    // and the remaining quantity will rest in the book afterwards, if any.
-    // Marketable,
+    /// A limit order is marketable if it can take liquidity from the book.
+    /// If the entry price locks or crosses an away market quotation it will immediately be filled as a taker
+    /// at the away market quotation, which is never worse than the limit price.
+    /// As there is no order book depth, the whole quantity is filled.
+    Marketable,
     /// If at the time of entry an order locks or crosses an away market quotation, the
     /// order will be immediately canceled back to the member.
     /// Good-Til-Crossing (GTX), sometimes referred to as limit maker or post-only orders,
@@ -0,0 +1,13 @@
+/// How a new limit order is handled which would cross a resting limit order of the opposite side,
+/// e.g. a sell at 100 while a buy at 101 is resting, which would trade with itself in a single account.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SelfCrossPolicy {
+    /// The new order is rejected with `Error::SelfCross`.
+    #[default]
+    Reject,
+    /// The crossed resting orders are cancelled once the new order passed all checks and is accepted.
+    /// They are reported as `LimitOrderUpdate::Cancelled` with the `UpdateOutcome` of the next `Exchange::update_state`.
+    CancelResting,
+    /// The new order is accepted and the resting orders remain active.
+    Allow,
+}
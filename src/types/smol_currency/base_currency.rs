@@ -3,7 +3,8 @@ use std::ops::Neg;
 use const_decimal::{Decimal, ParseDecimalError};
 use num_traits::{Num, One, Signed, Zero};
 
-use super::{Currency, MarginCurrency, Mon, QuoteCurrency};
+use super::{Currency, MarginCurrency, Mon, QuoteCurrency, fmt_decimal};
+use crate::{order_filters::TickRounding, utils::decimal_from_f64_rounded};
 
 /// Representation of a Base currency,
 /// e.g in the symbol BTCUSD, the prefix BTC is the `BaseCurrency` and the postfix `USD` is the `QuoteCurrency`.
@@ -30,6 +31,8 @@ use super::{Currency, MarginCurrency, Mon, QuoteCurrency};
     derive_more::Neg,
     derive_more::From,
     derive_more::AsRef,
+    Serialize,
+    Deserialize,
 )]
 #[mul(forward)]
 #[div(forward)]
@@ -50,6 +53,12 @@ where
         )
     }
 
+    /// Create a new instance from an `f64`, rounding it to the decimal precision `D` according to `rounding`.
+    /// NaN and infinite values are rejected.
+    pub fn try_from_f64(val: f64, rounding: TickRounding) -> crate::Result<Self> {
+        decimal_from_f64_rounded(val, rounding).map(Self)
+    }
+
     /// Round a number to a multiple of a given `quantum` toward zero.
     /// general ref: <https://en.wikipedia.org/wiki/Quantization_(signal_processing)>
     ///
@@ -89,6 +98,10 @@ where
 }
 
 /// Inverse futures where the `Base` currency is used as margin currency.
+/// The contract quantity and prices are denominated in `QuoteCurrency`,
+/// while margin, fees and profit and loss are denominated in `BaseCurrency`.
+/// This relationship is enforced by the type system through `PairedCurrency`,
+/// so an inverse `Exchange` is always generic over `QuoteCurrency` with `BaseCurrency` as its margin.
 ///
 /// # Generics:
 /// - `I`: The numeric data type of `Decimal`.
@@ -110,6 +123,20 @@ where
             - BaseCurrency::convert_from(quantity, exit_price)
     }
 
+    #[inline]
+    fn exit_price_for_pnl(
+        entry_price: QuoteCurrency<I, D>,
+        pnl: Self,
+        quantity: QuoteCurrency<I, D>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        let value_at_exit = BaseCurrency::convert_from(quantity, entry_price) - pnl;
+        if quantity.is_zero() || value_at_exit.is_zero() {
+            return None;
+        }
+        let exit_price = QuoteCurrency::from(*quantity.as_ref() / *value_at_exit.as_ref());
+        (exit_price > QuoteCurrency::zero()).then_some(exit_price)
+    }
+
     #[inline]
     fn price_paid_for_qty(total_cost: Self, quantity: Decimal<I, D>) -> QuoteCurrency<I, D> {
         if total_cost.is_zero() {
@@ -244,7 +271,8 @@ where
     I: Mon<D>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} Base", self.0)
+        fmt_decimal(&self.0, f)?;
+        write!(f, " Base")
     }
 }
 
@@ -32,6 +32,22 @@ where
         quantity: Self::PairedCurrency,
     ) -> Self;
 
+    /// Compute the exit price at which a position realizes the profit and loss `pnl`,
+    /// which is the inverse of `pnl`.
+    ///
+    /// # Arguments:
+    /// `entry_price`: The price at which the position was initiated.
+    /// `pnl`: The targeted profit and loss.
+    /// `quantity`: The amount of contracts in the position. must be negative if short.
+    ///
+    /// # Returns:
+    /// The exit price, or `None` if `quantity` is zero or no positive price realizes the `pnl`.
+    fn exit_price_for_pnl(
+        entry_price: QuoteCurrency<I, D>,
+        pnl: Self,
+        quantity: Self::PairedCurrency,
+    ) -> Option<QuoteCurrency<I, D>>;
+
     /// Compute the price paid for the `total_cost` for `quantity` number of contracts.
     fn price_paid_for_qty(total_cost: Self, quantity: Decimal<I, D>) -> QuoteCurrency<I, D>;
 }
@@ -59,9 +59,45 @@ where
     fn convert_from(units: Self::PairedCurrency, price_per_unit: QuoteCurrency<I, D>) -> Self;
 }
 
+/// Write a `Decimal` to the formatter, honoring the formatters precision if one is provided (e.g `{:.2}`).
+/// This allows controlling the displayed decimal places independent of the stored precision `D`.
+/// When displaying fewer places than stored, the value is truncated towards zero.
+pub(crate) fn fmt_decimal<I, const D: u8>(
+    value: &Decimal<I, D>,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result
+where
+    I: Mon<D>,
+{
+    let Some(precision) = f.precision() else {
+        return write!(f, "{value}");
+    };
+    let s = value.to_string();
+    let (integer, fraction) = s.split_once('.').unwrap_or((&s, ""));
+    let fraction: String = fraction
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(precision)
+        .collect();
+    // Truncating may leave a negative zero, which is displayed without the sign.
+    let is_zero = integer.trim_start_matches('-').chars().all(|c| c == '0')
+        && fraction.chars().all(|c| c == '0');
+    let integer = if is_zero {
+        integer.trim_start_matches('-')
+    } else {
+        integer
+    };
+    if precision == 0 {
+        write!(f, "{integer}")
+    } else {
+        write!(f, "{integer}.{fraction}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{order_filters::TickRounding, prelude::Error};
 
     #[test]
     fn convert_base_to_quote() {
@@ -86,6 +122,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn currency_display_precision() {
+        let v = QuoteCurrency::<i64, 5>::new(123456, 5);
+        assert_eq!(&v.to_string(), "1.23456 Quote");
+        assert_eq!(&format!("{v:.5}"), "1.23456 Quote");
+        assert_eq!(&format!("{v:.2}"), "1.23 Quote");
+        assert_eq!(&format!("{v:.0}"), "1 Quote");
+        assert_eq!(&format!("{v:.7}"), "1.2345600 Quote");
+        let v = BaseCurrency::<i64, 5>::new(-5, 1);
+        assert_eq!(&format!("{v:.1}"), "-0.5 Base");
+        assert_eq!(&format!("{v:.0}"), "0 Base");
+        assert_eq!(
+            &format!("{:.3}", BaseCurrency::<i64, 5>::new(-12345, 3)),
+            "-12.345 Base"
+        );
+    }
+
     #[test]
     fn quote_currency_pnl() {
         assert_eq!(
@@ -109,4 +162,119 @@ mod tests {
             BaseCurrency::new(25, 1)
         )
     }
+
+    #[test]
+    fn quote_currency_exit_price_for_pnl() {
+        let entry_price = QuoteCurrency::<i64, 4>::new(100, 0);
+        assert_eq!(
+            QuoteCurrency::exit_price_for_pnl(
+                entry_price,
+                QuoteCurrency::new(50, 0),
+                BaseCurrency::new(5, 0)
+            ),
+            Some(QuoteCurrency::new(110, 0))
+        );
+        assert_eq!(
+            QuoteCurrency::exit_price_for_pnl(
+                entry_price,
+                QuoteCurrency::new(50, 0),
+                BaseCurrency::new(-5, 0)
+            ),
+            Some(QuoteCurrency::new(90, 0))
+        );
+        assert_eq!(
+            QuoteCurrency::exit_price_for_pnl(
+                entry_price,
+                QuoteCurrency::new(-500, 0),
+                BaseCurrency::new(5, 0)
+            ),
+            None
+        );
+        assert_eq!(
+            QuoteCurrency::exit_price_for_pnl(
+                entry_price,
+                QuoteCurrency::new(50, 0),
+                BaseCurrency::zero()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn base_currency_exit_price_for_pnl() {
+        let entry_price = QuoteCurrency::<i32, 4>::new(100, 0);
+        assert_eq!(
+            BaseCurrency::exit_price_for_pnl(
+                entry_price,
+                BaseCurrency::new(25, 1),
+                QuoteCurrency::new(500, 0)
+            ),
+            Some(QuoteCurrency::new(200, 0))
+        );
+        assert_eq!(
+            BaseCurrency::exit_price_for_pnl(
+                entry_price,
+                BaseCurrency::new(-25, 1),
+                QuoteCurrency::new(-500, 0)
+            ),
+            Some(QuoteCurrency::new(200, 0))
+        );
+        // A long position can not gain more than its value at entry.
+        assert_eq!(
+            BaseCurrency::exit_price_for_pnl(
+                entry_price,
+                BaseCurrency::new(5, 0),
+                QuoteCurrency::new(500, 0)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn currency_try_from_f64() {
+        assert_eq!(
+            QuoteCurrency::<i64, 5>::try_from_f64(f64::NAN, TickRounding::Nearest),
+            Err(Error::NonFiniteFloat)
+        );
+        assert_eq!(
+            BaseCurrency::<i64, 5>::try_from_f64(f64::INFINITY, TickRounding::Down),
+            Err(Error::NonFiniteFloat)
+        );
+        // Exact values are not affected by the rounding.
+        for rounding in [TickRounding::Down, TickRounding::Up, TickRounding::Nearest] {
+            assert_eq!(
+                QuoteCurrency::<i64, 5>::try_from_f64(3.14159, rounding),
+                Ok(QuoteCurrency::new(314159, 5))
+            );
+        }
+        assert_eq!(
+            QuoteCurrency::<i64, 2>::try_from_f64(1.2345, TickRounding::Down),
+            Ok(QuoteCurrency::new(123, 2))
+        );
+        assert_eq!(
+            QuoteCurrency::<i64, 2>::try_from_f64(1.2345, TickRounding::Up),
+            Ok(QuoteCurrency::new(124, 2))
+        );
+        assert_eq!(
+            BaseCurrency::<i64, 2>::try_from_f64(1.2375, TickRounding::Nearest),
+            Ok(BaseCurrency::new(124, 2))
+        );
+        assert_eq!(
+            BaseCurrency::<i64, 2>::try_from_f64(-1.2345, TickRounding::Down),
+            Ok(BaseCurrency::new(-124, 2))
+        );
+        // Values which don't fit into the integer type are rejected instead of saturated.
+        assert_eq!(
+            QuoteCurrency::<i64, 5>::try_from_f64(1e30, TickRounding::Nearest),
+            Err(Error::IntegerConversion)
+        );
+        assert_eq!(
+            BaseCurrency::<i64, 5>::try_from_f64(-1e30, TickRounding::Down),
+            Err(Error::IntegerConversion)
+        );
+        assert_eq!(
+            QuoteCurrency::<i32, 2>::try_from_f64(1e8, TickRounding::Nearest),
+            Err(Error::IntegerConversion)
+        );
+    }
 }
@@ -3,7 +3,8 @@ use std::ops::Neg;
 use const_decimal::{Decimal, ParseDecimalError};
 use num_traits::{Num, One, Signed, Zero};
 
-use super::{BaseCurrency, Currency, MarginCurrency, Mon};
+use super::{BaseCurrency, Currency, MarginCurrency, Mon, fmt_decimal};
+use crate::{order_filters::TickRounding, utils::decimal_from_f64_rounded};
 
 /// Representation of a Quote currency,
 /// e.g in the symbol BTCUSD, the prefix BTC is the `BaseCurrency` and the postfix `USD` is the `QuoteCurrency`.
@@ -30,6 +31,8 @@ use super::{BaseCurrency, Currency, MarginCurrency, Mon};
     derive_more::Neg,
     derive_more::From,
     derive_more::AsRef,
+    Serialize,
+    Deserialize,
 )]
 #[mul(forward)]
 #[div(forward)]
@@ -47,14 +50,32 @@ where
         Self(Decimal::try_from_scaled(integer, scale).expect("Make sure the inputs are correct."))
     }
 
+    /// Create a new instance from an `f64`, rounding it to the decimal precision `D` according to `rounding`.
+    /// NaN and infinite values are rejected.
+    pub fn try_from_f64(val: f64, rounding: TickRounding) -> crate::Result<Self> {
+        decimal_from_f64_rounded(val, rounding).map(Self)
+    }
+
+    /// The price at which the equity of a long position entered at `self`,
+    /// being the initial margin plus unrealized profit and loss, equals the maintenance margin.
     #[inline]
-    pub(crate) fn liquidation_price_long(&self, maint_margin_req: Decimal<I, D>) -> Self {
-        Self(self.0 * (Decimal::one() - maint_margin_req))
+    pub(crate) fn liquidation_price_long(
+        &self,
+        init_margin_req: Decimal<I, D>,
+        maint_margin_req: Decimal<I, D>,
+    ) -> Self {
+        Self(self.0 * (Decimal::one() - init_margin_req + maint_margin_req))
     }
 
+    /// The price at which the equity of a short position entered at `self`,
+    /// being the initial margin plus unrealized profit and loss, equals the maintenance margin.
     #[inline]
-    pub(crate) fn liquidation_price_short(&self, maint_margin_req: Decimal<I, D>) -> Self {
-        Self(self.0 * (Decimal::one() + maint_margin_req))
+    pub(crate) fn liquidation_price_short(
+        &self,
+        init_margin_req: Decimal<I, D>,
+        maint_margin_req: Decimal<I, D>,
+    ) -> Self {
+        Self(self.0 * (Decimal::one() + init_margin_req - maint_margin_req))
     }
 
     pub(crate) fn new_weighted_price(
@@ -126,6 +147,18 @@ where
             - QuoteCurrency::convert_from(quantity, entry_price)
     }
 
+    fn exit_price_for_pnl(
+        entry_price: QuoteCurrency<I, D>,
+        pnl: Self,
+        quantity: BaseCurrency<I, D>,
+    ) -> Option<QuoteCurrency<I, D>> {
+        if quantity.is_zero() {
+            return None;
+        }
+        let exit_price = entry_price + QuoteCurrency(*pnl.as_ref() / *quantity.as_ref());
+        (exit_price > QuoteCurrency::zero()).then_some(exit_price)
+    }
+
     fn price_paid_for_qty(total_cost: Self, quantity: Decimal<I, D>) -> QuoteCurrency<I, D> {
         if quantity.is_zero() {
             return QuoteCurrency::zero();
@@ -260,7 +293,8 @@ where
     I: Mon<D>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} Quote", self.0)
+        fmt_decimal(&self.0, f)?;
+        write!(f, " Quote")
     }
 }
 
@@ -299,6 +333,33 @@ mod test {
         assert_eq!(v.div(QuoteCurrency::new(2, 0)), QuoteCurrency::new(4, 0));
     }
 
+    #[test]
+    fn quote_currency_abs_signum() {
+        let v = QuoteCurrency::<i64, 5>::new(-25, 1);
+        assert_eq!(v.abs(), QuoteCurrency::new(25, 1));
+        assert_eq!(v.signum(), QuoteCurrency::new(-1, 0));
+        assert_eq!(v.abs().signum(), QuoteCurrency::one());
+        assert_eq!(QuoteCurrency::<i64, 5>::zero().abs(), QuoteCurrency::zero());
+        assert_eq!(
+            QuoteCurrency::<i64, 5>::zero().signum(),
+            QuoteCurrency::zero()
+        );
+    }
+
+    #[test]
+    fn quote_currency_min_max_clamp() {
+        let lower = QuoteCurrency::<i64, 5>::new(90, 0);
+        let upper = QuoteCurrency::new(110, 0);
+        let v = QuoteCurrency::new(100, 0);
+        assert_eq!(v.min(lower), lower);
+        assert_eq!(v.max(lower), v);
+        assert_eq!(v.clamp(lower, upper), v);
+        assert_eq!(lower.clamp(lower, upper), lower);
+        assert_eq!(upper.clamp(lower, upper), upper);
+        assert_eq!(QuoteCurrency::new(895, 1).clamp(lower, upper), lower);
+        assert_eq!(QuoteCurrency::new(1105, 1).clamp(lower, upper), upper);
+    }
+
     #[test]
     fn quote_currency_price_paid_for_qty() {
         assert_eq!(
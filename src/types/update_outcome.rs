@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use super::{Currency, LimitOrderUpdate, Mon, UserOrderId};
+
+/// What happened to the account during a single `Exchange::update_state`,
+/// so strategies can react to it without diffing the state of the `Exchange`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UpdateOutcome<'a, I, const D: u8, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D> + Display,
+    BaseOrQuote: Currency<I, D> + Display,
+    UserOrderIdT: UserOrderId + Display,
+{
+    /// The fills and cancellations of limit orders, in the order they happened,
+    /// starting with the resting orders cancelled by a self-crossing order since the previous update.
+    pub limit_order_updates: &'a Vec<LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>>,
+    /// Whether the position failed the maintenance margin check and was closed at the market with this update.
+    /// The fills of limit orders before the liquidation are part of the `limit_order_updates`.
+    pub liquidated: bool,
+    /// Whether the account equity fell below the `min_account_balance` with this update,
+    /// which cancelled all active limit orders, closed the position and halts all trading.
+    pub blown_up: bool,
+    /// Whether the contract reached its `expiry` with this update,
+    /// which cancelled all active limit orders and settled the position.
+    pub expired: bool,
+}
+
+impl<I, const D: u8, BaseOrQuote, UserOrderIdT> UpdateOutcome<'_, I, D, BaseOrQuote, UserOrderIdT>
+where
+    I: Mon<D> + Display,
+    BaseOrQuote: Currency<I, D> + Display,
+    UserOrderIdT: UserOrderId + Display,
+{
+    /// The partial and full fills of limit orders.
+    pub fn fills(
+        &self,
+    ) -> impl Iterator<Item = &LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>> {
+        self.limit_order_updates.iter().filter(|update| {
+            matches!(
+                update,
+                LimitOrderUpdate::PartiallyFilled(_) | LimitOrderUpdate::FullyFilled(_)
+            )
+        })
+    }
+
+    /// The cancellations of limit orders, either by a self-crossing order, once their `cancel_latency` elapsed,
+    /// or when the account blows up or the contract reaches its `expiry`.
+    pub fn cancellations(
+        &self,
+    ) -> impl Iterator<Item = &LimitOrderUpdate<I, D, BaseOrQuote, UserOrderIdT>> {
+        self.limit_order_updates
+            .iter()
+            .filter(|update| matches!(update, LimitOrderUpdate::Cancelled(_)))
+    }
+}
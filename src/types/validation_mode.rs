@@ -0,0 +1,10 @@
+/// How orders which violate the `PriceFilter` or `QuantityFilter` of the contract are handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// The quantity and limit price are rounded to the closest valid values, so such orders are never rejected.
+    /// Useful for quick prototyping.
+    Lenient,
+    /// The order is rejected with the violated filter.
+    #[default]
+    Strict,
+}
@@ -1,10 +1,10 @@
 use assert2::assert;
 use const_decimal::Decimal;
 
-use crate::prelude::*;
+use crate::{order_filters::TickRounding, prelude::*};
 
 /// When no user specified order id is required.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NoUserOrderId;
 
 impl std::fmt::Display for NoUserOrderId {
@@ -56,6 +56,32 @@ pub fn decimal_from_f64<I: Mon<D>, const D: u8>(val: f64) -> Result<Decimal<I, D
         .ok_or(Error::UnableToCreateDecimal)
 }
 
+/// Create a `Decimal` from an `f64` value, rounding it to the decimal precision `D` according to `rounding`.
+/// NaN and infinite values, as well as those which don't fit into `I`, are rejected.
+pub(crate) fn decimal_from_f64_rounded<I: Mon<D>, const D: u8>(
+    val: f64,
+    rounding: TickRounding,
+) -> Result<Decimal<I, D>> {
+    if !val.is_finite() {
+        return Err(Error::NonFiniteFloat);
+    }
+    let scaled = val * 10_f64.powi(D as i32);
+    let nearest = scaled.round();
+    // Values which only miss the precision due to the binary representation of `f64` are exact.
+    let scaled = if (scaled - nearest).abs() <= scaled.abs() * f64::EPSILON * 4.0 {
+        nearest
+    } else {
+        match rounding {
+            TickRounding::Down => scaled.floor(),
+            TickRounding::Up => scaled.ceil(),
+            TickRounding::Nearest => nearest,
+        }
+    };
+    // Converting the `f64` directly rejects values outside the range of `I` instead of saturating them.
+    Decimal::try_from_scaled(I::from(scaled).ok_or(Error::IntegerConversion)?, D)
+        .ok_or(Error::UnableToCreateDecimal)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use const_decimal::Decimal;
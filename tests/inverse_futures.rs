@@ -1,7 +1,8 @@
 //! Test file for the inverse futures mode of the exchange
 
 use lfest::{
-    MockTransactionAccounting, mock_exchange_inverse, prelude::*, test_fee_maker, test_fee_taker,
+    DECIMALS, MockTransactionAccounting, mock_exchange_inverse, prelude::*, test_fee_maker,
+    test_fee_taker,
 };
 use num_traits::{One, Zero};
 
@@ -368,13 +369,15 @@ fn inv_short_market_loss_full() {
     );
     assert_eq!(exchange.position().outstanding_fees(), fees);
 
-    assert_eq!(
-        exchange.update_state(&Bba {
-            bid: QuoteCurrency::new(1999, 0),
-            ask: QuoteCurrency::new(2000, 0),
-            timestamp_exchange_ns: 2.into()
-        }),
-        Err(Error::RiskError(RiskError::Liquidate))
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid: QuoteCurrency::new(1999, 0),
+                ask: QuoteCurrency::new(2000, 0),
+                timestamp_exchange_ns: 2.into()
+            })
+            .unwrap()
+            .liquidated
     );
 
     assert_eq!(exchange.position(), &Position::Neutral);
@@ -780,13 +783,15 @@ fn inv_short_market_loss_partial() {
 
     let bid = QuoteCurrency::new(1999, 0);
     let ask = QuoteCurrency::new(2000, 0);
-    assert_eq!(
-        exchange.update_state(&Bba {
-            bid,
-            ask,
-            timestamp_exchange_ns: 2.into()
-        }),
-        Err(Error::RiskError(RiskError::Liquidate))
+    assert!(
+        exchange
+            .update_state(&Bba {
+                bid,
+                ask,
+                timestamp_exchange_ns: 2.into()
+            })
+            .unwrap()
+            .liquidated
     );
     assert_eq!(exchange.position(), &Position::Neutral);
     assert_eq!(
@@ -1016,3 +1021,118 @@ fn inv_execute_limit() {
         }
     );
 }
+
+/// Every transfer is double entry, so the user accounts, the fee account and the treasury always sum to zero.
+fn assert_ledger_balanced(
+    exchange: &Exchange<
+        i64,
+        DECIMALS,
+        QuoteCurrency<i64, DECIMALS>,
+        NoUserOrderId,
+        InMemoryTransactionAccounting<i64, DECIMALS, BaseCurrency<i64, DECIMALS>>,
+    >,
+) {
+    let balances = exchange.user_balances();
+    assert_eq!(
+        balances.available_wallet_balance
+            + balances.position_margin
+            + balances.order_margin
+            + exchange.fees_paid()
+            + exchange.treasury_balance(),
+        BaseCurrency::zero()
+    );
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn inv_reconcile_ledger_accounts() {
+    let mut exchange = mock_exchange_inverse(BaseCurrency::new(1, 0));
+    assert_ledger_balanced(&exchange);
+    let _ = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(999, 0),
+            ask: QuoteCurrency::new(1000, 0),
+            timestamp_exchange_ns: 0.into(),
+        })
+        .unwrap();
+
+    // Margin, pnl and fees of inverse futures are denominated in `BaseCurrency`.
+    let o = MarketOrder::new(Side::Buy, QuoteCurrency::new(500, 0)).unwrap();
+    exchange.submit_market_order(o).unwrap();
+    assert_eq!(
+        exchange.user_balances().position_margin,
+        BaseCurrency::new(5, 1)
+    );
+    assert_eq!(exchange.outstanding_fees(), BaseCurrency::new(3, 4));
+    assert_ledger_balanced(&exchange);
+
+    let limit_order = exchange
+        .submit_limit_order(
+            LimitOrder::new(
+                Side::Buy,
+                QuoteCurrency::new(900, 0),
+                QuoteCurrency::new(100, 0),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .resting()
+        .unwrap();
+    assert_eq!(
+        exchange.user_balances().order_margin,
+        BaseCurrency::new(11111, 5)
+    );
+    assert_ledger_balanced(&exchange);
+
+    let _ = exchange
+        .update_state(&Bba {
+            bid: QuoteCurrency::new(1250, 0),
+            ask: QuoteCurrency::new(1251, 0),
+            timestamp_exchange_ns: 1.into(),
+        })
+        .unwrap();
+    assert_eq!(
+        exchange
+            .position()
+            .unrealized_pnl(QuoteCurrency::new(1250, 0), QuoteCurrency::new(1251, 0)),
+        BaseCurrency::new(1, 1)
+    );
+
+    // Close half of the position, realizing half of the profit.
+    let o = MarketOrder::new(Side::Sell, QuoteCurrency::new(250, 0)).unwrap();
+    exchange.submit_market_order(o).unwrap();
+    assert_eq!(exchange.position().quantity(), QuoteCurrency::new(250, 0));
+    assert_eq!(exchange.treasury_balance(), BaseCurrency::new(-105, 2));
+    assert_eq!(exchange.fees_paid(), BaseCurrency::new(42, 5));
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: BaseCurrency::new(68847, 5),
+            position_margin: BaseCurrency::new(25, 2),
+            order_margin: BaseCurrency::new(11111, 5),
+            _q: std::marker::PhantomData
+        }
+    );
+    assert_ledger_balanced(&exchange);
+
+    exchange
+        .cancel_limit_order(CancelBy::OrderId(limit_order.id()))
+        .unwrap();
+    assert_ledger_balanced(&exchange);
+
+    let o = MarketOrder::new(Side::Sell, QuoteCurrency::new(250, 0)).unwrap();
+    exchange.submit_market_order(o).unwrap();
+    assert_eq!(exchange.position(), &Position::Neutral);
+    assert_eq!(exchange.treasury_balance(), BaseCurrency::new(-11, 1));
+    assert_eq!(exchange.fees_paid(), BaseCurrency::new(54, 5));
+    assert_eq!(
+        exchange.user_balances(),
+        UserBalances {
+            available_wallet_balance: BaseCurrency::new(109946, 5),
+            position_margin: BaseCurrency::zero(),
+            order_margin: BaseCurrency::zero(),
+            _q: std::marker::PhantomData
+        }
+    );
+    assert_ledger_balanced(&exchange);
+}